@@ -1,3 +1,5 @@
+use reflection::data::Data;
+
 #[derive(
     Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
 )]
@@ -88,3 +90,17 @@ impl Image {
         }
     }
 }
+
+/// Includes the pixel data skipped by serde, so debuggers can inspect and preview it
+impl From<&Image> for Data {
+    fn from(image: &Image) -> Self {
+        Data::Struct {
+            name: "Image",
+            fields: vec![
+                ("data", Data::ByteArray(image.data.clone())),
+                ("width", Data::U32(image.width)),
+                ("height", Data::U32(image.height)),
+            ],
+        }
+    }
+}
@@ -1,17 +1,29 @@
 use rusb::UsbContext;
-
-use crate::report::{
-    global_state::UsagePage,
-    input_report::{InputReport, InputValue, ReportValue},
-    report_descriptor::{item_data::Constness, DataType, ReportDescriptor},
+use std::time::Duration;
+
+use crate::{
+    report::{
+        global_state::UsagePage,
+        input_report::{InputReport, InputValue, ReportValue},
+        local_state::Usage,
+        output_report::OutputReport,
+        report_descriptor::{
+            item_data::{Constness, Dimensionality},
+            DataType, ReportDescriptor, ReportDescriptorItem, ReportLayout,
+        },
+    },
+    HidError, HidErrorKind,
 };
 
 /// Device abstration containing all information necessary to read and interpret input
 #[derive(Debug, Clone)]
 pub struct Device<T: UsbContext> {
     device: rusb::Device<T>,
+    interface: u8,
     interrupt_input: u8,
+    interrupt_output: Option<u8>,
     report_desc: ReportDescriptor,
+    layout: ReportLayout,
 }
 
 impl<T: UsbContext> Device<T> {
@@ -22,125 +34,214 @@ impl<T: UsbContext> Device<T> {
     ) -> Self {
         Device {
             device,
+            interface: 0,
             interrupt_input,
+            interrupt_output: None,
+            layout: report_desc.layout(),
             report_desc,
         }
     }
 
-    pub fn parse(&self, input: impl Iterator<Item = u8>) -> InputReport {
-        parse_input_report(&self.report_desc, input)
+    /// Set the HID interface number, used for control transfers
+    pub fn with_interface(mut self, interface: u8) -> Self {
+        self.interface = interface;
+        self
     }
 
-    pub fn input_buffer_len(&self) -> usize {
-        let mut bits = 0;
-
-        for item in self.report_desc.iter() {
-            if item.data_type != DataType::Feature {
-                let report_size = item.global_state.report_size.expect("Unknown report size");
-                let report_count = item
-                    .global_state
-                    .report_count
-                    .expect("Unknown report count");
-                let size = report_size as u16 * report_count as u16;
-                bits += size + (size % 8)
+    /// Set the interrupt OUT endpoint, used for sending output reports
+    pub fn with_interrupt_output(mut self, interrupt_output: u8) -> Self {
+        self.interrupt_output = Some(interrupt_output);
+        self
+    }
+
+    pub fn report_descriptor(&self) -> &ReportDescriptor {
+        &self.report_desc
+    }
+
+    pub fn layout(&self) -> &ReportLayout {
+        &self.layout
+    }
+
+    /// Create an empty output report for this device
+    pub fn output_report(&self, report_id: Option<u8>) -> OutputReport {
+        OutputReport::output(&self.report_desc, report_id)
+    }
+
+    /// Create an empty feature report for this device
+    pub fn feature_report(&self, report_id: Option<u8>) -> OutputReport {
+        OutputReport::feature(&self.report_desc, report_id)
+    }
+
+    /// Send an output or feature report
+    ///
+    /// Output reports go over the interrupt OUT endpoint if the device has one,
+    /// otherwise reports are sent with a SET_REPORT control transfer.
+    pub fn send_report(
+        &self,
+        device_handle: &rusb::DeviceHandle<T>,
+        report: &OutputReport,
+        timeout: Duration,
+    ) -> Result<usize, rusb::Error> {
+        match (report.data_type(), self.interrupt_output) {
+            (DataType::Output, Some(endpoint)) => {
+                report.send_interrupt(device_handle, endpoint, timeout)
             }
+            _ => report.send_control(device_handle, self.interface, timeout),
         }
+    }
+
+    pub fn parse(&self, input: impl Iterator<Item = u8>) -> Result<InputReport, HidError> {
+        parse_input_report_with_layout(&self.layout, input)
+    }
 
-        (bits / 8) as usize
+    /// Size of the buffer needed to receive any of the device's input reports,
+    /// including the report ID byte
+    pub fn input_buffer_len(&self) -> usize {
+        self.layout.max_len(DataType::Input)
     }
 
     pub fn open(&self) -> Result<rusb::DeviceHandle<T>, rusb::Error> {
         self.device.open()
     }
 
+    pub fn interface(&self) -> u8 {
+        self.interface
+    }
+
     pub fn interrupt_input(&self) -> u8 {
         self.interrupt_input
     }
+
+    pub fn interrupt_output(&self) -> Option<u8> {
+        self.interrupt_output
+    }
 }
 
 /// Parse the bytes of an input report into a vector of input values
+///
+/// Fields are read as two's complement signed values if their logical minimum is negative.
+/// Array items yield one value per active selector, carrying the selected usage,
+/// and are omitted when the selector lies outside the logical range (i.e. nothing is selected).
+///
+/// Returns an error if the input is shorter than the report described by `report_desc`.
 pub fn parse_input_report(
     report_desc: &ReportDescriptor,
+    input: impl Iterator<Item = u8>,
+) -> Result<InputReport, HidError> {
+    parse_input_report_with_layout(&report_desc.layout(), input)
+}
+
+/// Parse the bytes of an input report using a precomputed [`ReportLayout`]
+///
+/// For devices that use report IDs, the leading ID byte selects which items are read;
+/// unknown IDs are returned as an error.
+pub fn parse_input_report_with_layout(
+    layout: &ReportLayout,
     mut input: impl Iterator<Item = u8>,
-) -> InputReport {
+) -> Result<InputReport, HidError> {
     let mut current_byte = 0u8;
     let mut current_len = 0u8;
+    let mut offset = 0usize;
 
-    let mut read = |count: usize| -> ReportValue {
-        let mut bits = vec![];
+    // Read `count` bits, least significant first
+    let mut read = |count: usize| -> Result<u64, HidError> {
+        let mut out = 0u64;
 
-        for _ in 0..count {
+        for i in 0..count {
             if current_len == 0 {
-                current_byte = input.next().unwrap();
+                current_byte = input
+                    .next()
+                    .ok_or_else(|| HidError::new(offset, HidErrorKind::UnexpectedEnd))?;
                 current_len = 8;
+                offset += 1;
             }
 
-            bits.insert(0, current_byte & 1 > 0);
-            current_byte = current_byte >> 1;
+            if i < 64 {
+                out |= ((current_byte & 1) as u64) << i;
+            }
+            current_byte >>= 1;
             current_len -= 1;
         }
 
-        let mut out = 0;
-        for bit in &bits {
-            out = (out << 1) | if *bit { 1 } else { 0 }
-        }
-
-        match bits.len() {
-            1 => ReportValue::Bool(out > 0),
-            2..=8 => ReportValue::U8(out as u8),
-            9..=16 => ReportValue::U16(out as u16),
-            17..=32 => ReportValue::U32(out as u32),
-            _ => panic!("Invalid value size"),
-        }
+        Ok(out)
     };
 
-    let mut out = vec![];
-    let report_id = if report_desc
-        .iter()
-        .find(|item| item.global_state.report_id.is_some())
-        .is_some()
-    {
-        match read(8) {
-            ReportValue::U8(report_id) => Some(report_id),
-            _ => unreachable!(),
-        }
+    let report_id = if layout.uses_report_ids() {
+        Some(read(8)? as u8)
     } else {
         None
     };
 
-    for item in report_desc.iter() {
-        if let (Some(report_id), Some(item_report_id)) = (report_id, item.global_state.report_id) {
-            if item_report_id != report_id {
-                continue;
-            }
+    let report = match (layout.report(DataType::Input, report_id), report_id) {
+        (Some(report), _) => report,
+        (None, Some(report_id)) => {
+            return Err(HidError::new(0, HidErrorKind::UnknownReportId(report_id)))
+        }
+        (None, None) => return Ok(InputReport::new(vec![])),
+    };
+
+    let mut out = vec![];
+    for item in report.items() {
+        let size = item.global_state.report_size.unwrap_or(0) as usize
+            * item.global_state.report_count.unwrap_or(0) as usize;
+        let bits = read(size)?;
+
+        // Skip over const padding
+        if item.main_data.constness == Constness::Constant || size == 0 {
+            continue;
         }
 
-        if item.data_type != DataType::Feature {
-            let data = read(
-                item.global_state.report_size.unwrap() as usize
-                    * item.global_state.report_count.unwrap() as usize,
-            );
+        let signed = matches!(item.global_state.logical_minimum, Some(min) if min < 0);
+        let data = ReportValue::from_bits(bits, size.min(64), signed);
 
-            // Skip over const padding
-            if item.main_data.constness == Constness::Constant {
-                continue;
+        if item.main_data.dimensionality == Dimensionality::Array {
+            if let Some(item) = array_selection(item, &data) {
+                out.push(InputValue::new(item, data));
             }
+            continue;
+        }
 
-            if let Some(usage_page) = item.global_state.usage_page {
-                match usage_page {
-                    UsagePage::Button => {
-                        out.push(InputValue::new(item.clone(), data));
-                    }
-                    UsagePage::GenericDesktop => {
-                        if item.local_state.usage.is_some() {
-                            out.push(InputValue::new(item.clone(), data));
-                        }
-                    }
-                    _ => (),
-                }
+        // Keep buttons, and items whose usage is known to one of the usage tables
+        match (item.global_state.usage_page, item.local_state.usage) {
+            (Some(UsagePage::Button), _) => out.push(InputValue::new(*item, data)),
+            (Some(_), Some(usage)) if !matches!(usage, Usage::Other(_)) => {
+                out.push(InputValue::new(*item, data))
             }
+            _ => (),
+        }
+    }
+
+    Ok(InputReport::new(out))
+}
+
+/// Resolve the usage selected by an array item, returning a copy of the item with that usage
+///
+/// Returns None if the selector lies outside the logical range, or selects the undefined usage 0.
+fn array_selection(
+    item: &ReportDescriptorItem,
+    selector: &ReportValue,
+) -> Option<ReportDescriptorItem> {
+    let selector = selector.as_i64();
+    let logical_min = item.global_state.logical_minimum.unwrap_or(0) as i64;
+    if selector < logical_min {
+        return None;
+    }
+    if let Some(logical_max) = item.global_state.logical_maximum {
+        if selector > logical_max as i64 {
+            return None;
+        }
+    }
+
+    let mut item = *item;
+    if let (Some(usage_page), Some(usage_minimum)) =
+        (item.global_state.usage_page, item.local_state.usage_minimum)
+    {
+        let usage = usage_minimum as i64 + (selector - logical_min);
+        if usage <= 0 {
+            return None;
         }
+        item.local_state.usage = Some(Usage::new(usage_page, usage as u32));
     }
 
-    InputReport::new(out)
+    Some(item)
 }
@@ -77,6 +77,14 @@ impl<T: UsbContext> Devices<T> {
                             continue;
                         }
 
+                        let interrupt_output = interface_desc
+                            .endpoint_descriptors()
+                            .find(|endpoint_desc| {
+                                endpoint_desc.transfer_type() == TransferType::Interrupt
+                                    && endpoint_desc.direction() == Direction::Out
+                            })
+                            .map(|endpoint_desc| endpoint_desc.address());
+
                         for endpoint_desc in interface_desc.endpoint_descriptors() {
                             if endpoint_desc.transfer_type() != TransferType::Interrupt
                                 || endpoint_desc.direction() != Direction::In
@@ -88,14 +96,24 @@ impl<T: UsbContext> Devices<T> {
 
                             let report_desc = if let Some(report) = report_descriptors.get(vid, pid)
                             {
-                                ReportDescriptor::new(report.iter().copied())
+                                match ReportDescriptor::new(report.iter().copied()) {
+                                    Ok(report_desc) => report_desc,
+                                    Err(e) => {
+                                        println!("\tInvalid report descriptor: {}", e);
+                                        continue;
+                                    }
+                                }
                             } else {
                                 println!("\tInvalid report descriptor");
                                 continue;
                             };
 
                             let device_id = DeviceId::new(vid, pid);
-                            let device = Device::new(device, interrupt_input, report_desc);
+                            let mut device = Device::new(device, interrupt_input, report_desc)
+                                .with_interface(interface_desc.interface_number());
+                            if let Some(interrupt_output) = interrupt_output {
+                                device = device.with_interrupt_output(interrupt_output);
+                            }
                             let device_name = DeviceName::new(manufacturer, product);
 
                             self.devices.insert(device_id, device);
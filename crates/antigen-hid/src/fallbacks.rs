@@ -1,6 +1,6 @@
-//! Device data dumped via usbdk
+//! Report descriptors dumped via usbdk, for devices whose descriptors libusb can't read
 
-use antigen_hid::report::report_descriptor::ReportDescriptorData;
+use crate::report::report_descriptor::ReportDescriptorData;
 
 pub fn fallback_t16000m() -> ReportDescriptorData {
     let mut overrides = ReportDescriptorData::default();
@@ -1,3 +1,5 @@
+use crate::{HidError, HidErrorKind};
+
 /// The HID descriptor contains information about the device's Report Descriptor,
 #[allow(non_snake_case)]
 #[derive(Debug, Clone)]
@@ -31,18 +33,28 @@ pub struct HidDescriptor {
 #[allow(non_snake_case)]
 impl HidDescriptor {
     /// Parse a [`HidDescriptor`] from the bytes interleaved between interface and endpoint descriptors.
-    pub fn parse(mut extra: impl Iterator<Item = u8>) -> Self {
-        let bLength = extra.next().unwrap();
-        let bDescriptorType_Hid = extra.next().unwrap();
-        let bcdHID = u16::from_le_bytes([extra.next().unwrap(), extra.next().unwrap()]);
-        let bCountryCode = extra.next().unwrap();
-        let bNumDescriptors = extra.next().unwrap();
-        let bDescriptorType_Class = extra.next().unwrap();
-        let wDescriptorLength = u16::from_le_bytes([extra.next().unwrap(), extra.next().unwrap()]);
+    pub fn parse(extra: impl Iterator<Item = u8>) -> Result<Self, HidError> {
+        let mut extra = extra.enumerate();
+        let mut offset = 0;
+        let mut next = || match extra.next() {
+            Some((i, byte)) => {
+                offset = i + 1;
+                Ok(byte)
+            }
+            None => Err(HidError::new(offset, HidErrorKind::UnexpectedEnd)),
+        };
+
+        let bLength = next()?;
+        let bDescriptorType_Hid = next()?;
+        let bcdHID = u16::from_le_bytes([next()?, next()?]);
+        let bCountryCode = next()?;
+        let bNumDescriptors = next()?;
+        let bDescriptorType_Class = next()?;
+        let wDescriptorLength = u16::from_le_bytes([next()?, next()?]);
 
         let mut optional = vec![];
-        while let (Some(bDescriptorType), Some(wDescriptorLengthA), Some(wDescriptorLengthB)) =
-            (extra.next(), extra.next(), extra.next())
+        while let (Ok(bDescriptorType), Ok(wDescriptorLengthA), Ok(wDescriptorLengthB)) =
+            (next(), next(), next())
         {
             optional.push((
                 bDescriptorType,
@@ -50,7 +62,7 @@ impl HidDescriptor {
             ))
         }
 
-        HidDescriptor {
+        Ok(HidDescriptor {
             bLength,
             bDescriptorType_Hid,
             bcdHID,
@@ -59,7 +71,7 @@ impl HidDescriptor {
             bDescriptorType_Class,
             wDescriptorLength,
             optional,
-        }
+        })
     }
 }
 
@@ -0,0 +1,88 @@
+/// An error parsing HID descriptors or reports
+///
+/// Records the byte offset into the input where parsing failed and,
+/// for report descriptors, the prefix byte of the offending item.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HidError {
+    offset: usize,
+    tag: Option<u8>,
+    kind: HidErrorKind,
+}
+
+impl HidError {
+    pub fn new(offset: usize, kind: HidErrorKind) -> Self {
+        HidError {
+            offset,
+            tag: None,
+            kind,
+        }
+    }
+
+    pub fn with_tag(mut self, tag: u8) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    /// Byte offset into the parsed input
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Prefix byte of the report descriptor item being parsed
+    pub fn tag(&self) -> Option<u8> {
+        self.tag
+    }
+
+    pub fn kind(&self) -> HidErrorKind {
+        self.kind
+    }
+}
+
+impl std::fmt::Display for HidError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.offset)?;
+        if let Some(tag) = self.tag {
+            write!(f, " (item 0x{:02x})", tag)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for HidError {}
+
+/// HidError variants
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HidErrorKind {
+    /// Input ended partway through a descriptor, item or report
+    UnexpectedEnd,
+    /// A main item was declared before any Report Size
+    MissingReportSize,
+    /// A main item was declared before any Report Count
+    MissingReportCount,
+    /// Report Count exceeds the supported number of fields per main item
+    ReportCountTooLarge(u32),
+    /// A Pop item without a matching Push
+    PopWithoutPush,
+    /// More collections than can be addressed by a [`CollectionId`](crate::report::report_descriptor::CollectionId)
+    TooManyCollections,
+    /// An input report with a report ID the descriptor doesn't declare
+    UnknownReportId(u8),
+}
+
+impl std::fmt::Display for HidErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HidErrorKind::UnexpectedEnd => f.write_str("Unexpected end of input"),
+            HidErrorKind::MissingReportSize => f.write_str("Missing report size"),
+            HidErrorKind::MissingReportCount => f.write_str("Missing report count"),
+            HidErrorKind::ReportCountTooLarge(count) => {
+                write!(f, "Report count {} too large", count)
+            }
+            HidErrorKind::PopWithoutPush => f.write_str("Pop without matching push"),
+            HidErrorKind::TooManyCollections => f.write_str("Too many collections"),
+            HidErrorKind::UnknownReportId(report_id) => {
+                write!(f, "Unknown report ID {}", report_id)
+            }
+        }
+    }
+}
@@ -7,8 +7,15 @@
 //!         correct behavior on windows
 
 pub mod devices;
+pub mod fallbacks;
+pub mod polling;
 pub mod report;
 
 mod hid_descriptor;
+mod hid_error;
 
 pub use hid_descriptor::*;
+pub use hid_error::*;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+
+use crate::report::{
+    global_state::UsagePage,
+    input_report::{HatDirection, InputReport, InputValue},
+    report_descriptor::ReportDescriptorItem,
+};
+
+use super::{DeviceState, InputEvent};
+
+/// Identifies a value across reports: its item, and which occurrence of that item it is
+type ItemKey = (ReportDescriptorItem, usize);
+
+/// Diffs consecutive input reports from a device into [`InputEvent`]s
+///
+/// Reports are only compared against the previous report with the same ID,
+/// so devices that interleave several input reports don't produce spurious changes.
+#[derive(Debug, Default, Clone)]
+pub struct ChangeTracker {
+    deadzone: f64,
+    state: DeviceState,
+    axes: BTreeMap<ItemKey, f64>,
+}
+
+impl ChangeTracker {
+    /// Create a tracker that reports axis movements larger than `deadzone`,
+    /// measured in normalized units from 0 to 1
+    pub fn new(deadzone: f64) -> Self {
+        ChangeTracker {
+            deadzone,
+            ..Default::default()
+        }
+    }
+
+    pub fn deadzone(&self) -> f64 {
+        self.deadzone
+    }
+
+    pub fn state(&self) -> &DeviceState {
+        &self.state
+    }
+
+    /// Record a new report, returning the changes since the previous report with the same ID
+    ///
+    /// Buttons held in the first report are reported as pressed,
+    /// while axes take their first value as a baseline.
+    pub fn update(&mut self, report_id: Option<u8>, report: InputReport) -> Vec<InputEvent> {
+        let current = keyed(&report);
+        let mut previous = self
+            .state
+            .insert(report_id, report)
+            .map(|previous| keyed(&previous).into_iter().collect::<BTreeMap<_, _>>())
+            .unwrap_or_default();
+
+        let mut events = vec![];
+        for (key, value) in current {
+            let previous = previous.remove(&key);
+
+            if is_button(&value) {
+                match (
+                    matches!(previous, Some(v) if is_pressed(&v)),
+                    is_pressed(&value),
+                ) {
+                    (false, true) => events.push(InputEvent::ButtonPressed(value)),
+                    (true, false) => events.push(InputEvent::ButtonReleased(value)),
+                    _ => (),
+                }
+            } else if let Some(direction) = value.hat_switch() {
+                let previous = previous
+                    .and_then(|previous| previous.hat_switch())
+                    .unwrap_or(HatDirection::Centered);
+                if direction != previous {
+                    events.push(InputEvent::HatChanged {
+                        value,
+                        previous,
+                        direction,
+                    });
+                }
+            } else if let Some(normalized) = value.normalized() {
+                match self.axes.get_mut(&key) {
+                    Some(last) => {
+                        let delta = normalized - *last;
+                        if delta.abs() > self.deadzone {
+                            *last = normalized;
+                            events.push(InputEvent::AxisMoved { value, delta });
+                        }
+                    }
+                    None => {
+                        self.axes.insert(key, normalized);
+                    }
+                }
+            }
+        }
+
+        // Values missing from the new report, ex. keys no longer selected by an array item
+        for (_, value) in previous {
+            if is_button(&value) && is_pressed(&value) {
+                events.push(InputEvent::ButtonReleased(value));
+            }
+        }
+
+        events
+    }
+}
+
+fn keyed(report: &InputReport) -> Vec<(ItemKey, InputValue)> {
+    let mut occurrences = BTreeMap::<ReportDescriptorItem, usize>::new();
+    report
+        .iter()
+        .map(|value| {
+            let occurrence = occurrences.entry(*value.data_item()).or_insert(0);
+            let key = (*value.data_item(), *occurrence);
+            *occurrence += 1;
+            (key, *value)
+        })
+        .collect()
+}
+
+fn is_button(value: &InputValue) -> bool {
+    matches!(
+        value.data_item().global_state.usage_page,
+        Some(UsagePage::Button) | Some(UsagePage::KeyboardKeypad)
+    )
+}
+
+fn is_pressed(value: &InputValue) -> bool {
+    value.report_value().as_i64() != 0
+}
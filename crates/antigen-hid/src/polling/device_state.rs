@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+
+use crate::report::input_report::{InputReport, InputValue};
+
+/// The most recent input report received for each report ID of a device
+#[derive(Debug, Default, Clone)]
+pub struct DeviceState {
+    reports: BTreeMap<Option<u8>, InputReport>,
+}
+
+impl DeviceState {
+    pub(crate) fn insert(
+        &mut self,
+        report_id: Option<u8>,
+        report: InputReport,
+    ) -> Option<InputReport> {
+        self.reports.insert(report_id, report)
+    }
+
+    /// The latest report with the given ID, or None if it hasn't been received yet
+    pub fn report(&self, report_id: Option<u8>) -> Option<&InputReport> {
+        self.reports.get(&report_id)
+    }
+
+    pub fn reports(&self) -> impl Iterator<Item = (Option<u8>, &InputReport)> {
+        self.reports
+            .iter()
+            .map(|(report_id, report)| (*report_id, report))
+    }
+
+    /// Values from the latest report of every report ID
+    pub fn values(&self) -> impl Iterator<Item = &InputValue> {
+        self.reports.values().flat_map(InputReport::iter)
+    }
+}
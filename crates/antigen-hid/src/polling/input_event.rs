@@ -0,0 +1,105 @@
+use crate::{
+    devices::DeviceId,
+    report::input_report::{HatDirection, InputValue},
+};
+
+/// A change in the state of a single input item
+#[derive(Debug, Copy, Clone)]
+pub enum InputEvent {
+    /// A button went from zero to non-zero
+    ButtonPressed(InputValue),
+    /// A button went from non-zero to zero, or was deselected by an array item
+    ButtonReleased(InputValue),
+    /// An axis moved further than the deadzone since its last event
+    AxisMoved {
+        value: InputValue,
+        /// Change in [`InputValue::normalized`] since the last event for this axis
+        delta: f64,
+    },
+    /// A hat switch changed direction
+    HatChanged {
+        value: InputValue,
+        previous: HatDirection,
+        direction: HatDirection,
+    },
+}
+
+impl InputEvent {
+    /// The input value after the change
+    pub fn value(&self) -> &InputValue {
+        match self {
+            InputEvent::ButtonPressed(value)
+            | InputEvent::ButtonReleased(value)
+            | InputEvent::AxisMoved { value, .. }
+            | InputEvent::HatChanged { value, .. } => value,
+        }
+    }
+}
+
+impl std::fmt::Display for InputEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = |value: &InputValue| match value.data_item().local_state.usage {
+            Some(usage) => usage.to_string(),
+            None => "Unknown".to_string(),
+        };
+
+        match self {
+            InputEvent::ButtonPressed(value) => write!(f, "{} pressed", name(value)),
+            InputEvent::ButtonReleased(value) => write!(f, "{} released", name(value)),
+            InputEvent::AxisMoved { value, delta } => match value.normalized() {
+                Some(normalized) => {
+                    write!(
+                        f,
+                        "{} moved to {:.3} ({:+.3})",
+                        name(value),
+                        normalized,
+                        delta
+                    )
+                }
+                None => write!(f, "{} moved", name(value)),
+            },
+            InputEvent::HatChanged {
+                value,
+                previous,
+                direction,
+            } => write!(
+                f,
+                "{} changed from {} to {}",
+                name(value),
+                previous,
+                direction
+            ),
+        }
+    }
+}
+
+/// An event published by a [`Poller`](super::Poller)
+// Events are moved through the channel once, so boxing input events would only add an allocation
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Copy, Clone)]
+pub enum DeviceEvent {
+    Input(DeviceId, InputEvent),
+    /// Reading from the device failed, and its polling thread has stopped
+    Disconnected(DeviceId, rusb::Error),
+}
+
+impl DeviceEvent {
+    pub fn device_id(&self) -> DeviceId {
+        match self {
+            DeviceEvent::Input(device_id, _) | DeviceEvent::Disconnected(device_id, _) => {
+                *device_id
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DeviceEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let device_id = self.device_id();
+        write!(f, "[{:04x}:{:04x}] ", device_id.vid(), device_id.pid())?;
+        match self {
+            DeviceEvent::Input(_, event) => event.fmt(f),
+            DeviceEvent::Disconnected(_, e) => write!(f, "Disconnected: {}", e),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Background polling of HID devices, publishing changes in their input as events
+mod change_tracker;
+mod device_state;
+mod input_event;
+
+pub use change_tracker::*;
+pub use device_state::*;
+pub use input_event::*;
+
+use crossbeam_channel::{Receiver, Sender};
+use rusb::UsbContext;
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::devices::{Device, DeviceId};
+
+/// Axis movement below which no [`InputEvent::AxisMoved`] is published, in normalized units
+pub const DEFAULT_DEADZONE: f64 = 0.01;
+
+/// How long each interrupt read waits before checking whether polling has stopped
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Reads input from devices on one background thread each
+///
+/// Changes are published as [`DeviceEvent`]s on a channel,
+/// and the latest state of each device can be read at any time.
+/// Polling threads stop when the poller is dropped.
+#[derive(Debug)]
+pub struct Poller {
+    deadzone: f64,
+    timeout: Duration,
+    sender: Sender<DeviceEvent>,
+    receiver: Receiver<DeviceEvent>,
+    state: Arc<RwLock<BTreeMap<DeviceId, DeviceState>>>,
+    stop: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl Default for Poller {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Poller {
+            deadzone: DEFAULT_DEADZONE,
+            timeout: DEFAULT_READ_TIMEOUT,
+            sender,
+            receiver,
+            state: Default::default(),
+            stop: Default::default(),
+            threads: Default::default(),
+        }
+    }
+}
+
+impl Poller {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the axis deadzone for devices polled after this call
+    pub fn with_deadzone(mut self, deadzone: f64) -> Self {
+        self.deadzone = deadzone;
+        self
+    }
+
+    /// Set the interrupt read timeout for devices polled after this call
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Open a device and start polling it on a new thread
+    pub fn poll<T: UsbContext + 'static>(
+        &mut self,
+        device_id: DeviceId,
+        device: Device<T>,
+    ) -> Result<(), rusb::Error> {
+        let device_handle = device.open()?;
+
+        // Threads from before a call to `stop` have been joined, so polling can resume
+        self.stop.store(false, Ordering::Relaxed);
+
+        let tracker = ChangeTracker::new(self.deadzone);
+        let timeout = self.timeout;
+        let sender = self.sender.clone();
+        let state = self.state.clone();
+        let stop = self.stop.clone();
+
+        self.threads.push(std::thread::spawn(move || {
+            let mut tracker = tracker;
+            let mut buf = vec![0; device.input_buffer_len()];
+
+            while !stop.load(Ordering::Relaxed) {
+                let len =
+                    match device_handle.read_interrupt(device.interrupt_input(), &mut buf, timeout)
+                    {
+                        Ok(len) => len,
+                        Err(rusb::Error::Timeout) => continue,
+                        Err(e) => {
+                            let _ = sender.send(DeviceEvent::Disconnected(device_id, e));
+                            break;
+                        }
+                    };
+
+                // Drop reports that are too short for the descriptor or have an unknown ID
+                let report = match device.parse(buf[..len].iter().copied()) {
+                    Ok(report) => report,
+                    Err(_) => continue,
+                };
+
+                let report_id = if device.layout().uses_report_ids() {
+                    buf.first().copied()
+                } else {
+                    None
+                };
+
+                let events = tracker.update(report_id, report);
+                state
+                    .write()
+                    .unwrap()
+                    .insert(device_id, tracker.state().clone());
+
+                for event in events {
+                    if sender.send(DeviceEvent::Input(device_id, event)).is_err() {
+                        return;
+                    }
+                }
+            }
+        }));
+
+        Ok(())
+    }
+
+    /// Channel of input changes and disconnections from all polled devices
+    pub fn events(&self) -> &Receiver<DeviceEvent> {
+        &self.receiver
+    }
+
+    /// The latest state of a device, or None if no input has been received from it
+    pub fn snapshot(&self, device_id: DeviceId) -> Option<DeviceState> {
+        self.state.read().unwrap().get(&device_id).cloned()
+    }
+
+    /// The latest state of every device that has received input
+    pub fn snapshots(&self) -> BTreeMap<DeviceId, DeviceState> {
+        self.state.read().unwrap().clone()
+    }
+
+    /// Stop all polling threads and wait for them to finish
+    ///
+    /// Devices polled afterwards start new threads as usual.
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -23,6 +23,23 @@ pub struct GlobalState {
     pub report_count: Option<u32>,
 }
 
+impl GlobalState {
+    /// Decode the unit exponent, a 4-bit two's complement value
+    pub fn exponent(&self) -> i32 {
+        match self.unit_exponent {
+            Some(exponent) => {
+                let nibble = (exponent & 0xF) as i32;
+                if nibble >= 8 {
+                    nibble - 16
+                } else {
+                    nibble
+                }
+            }
+            None => 0,
+        }
+    }
+}
+
 impl std::fmt::Debug for GlobalState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut s = f.debug_struct("GlobalState");
@@ -3,6 +3,7 @@
 pub enum UnitCurrent {
     None,
     Ampere,
+    Reserved(u32),
 }
 
 impl From<u32> for UnitCurrent {
@@ -10,7 +11,7 @@ impl From<u32> for UnitCurrent {
         match v {
             0x0 => UnitCurrent::None,
             0x1..=0x4 => UnitCurrent::Ampere,
-            _ => panic!("Invalid unit")
+            v => UnitCurrent::Reserved(v),
         }
     }
 }
@@ -6,6 +6,7 @@ pub enum UnitLength {
     Radians,
     Inch,
     Degrees,
+    Reserved(u32),
 }
 
 impl From<u32> for UnitLength {
@@ -16,7 +17,7 @@ impl From<u32> for UnitLength {
             0x2 => UnitLength::Radians,
             0x3 => UnitLength::Inch,
             0x4 => UnitLength::Degrees,
-            _ => panic!("Invalid unit")
+            v => UnitLength::Reserved(v),
         }
     }
 }
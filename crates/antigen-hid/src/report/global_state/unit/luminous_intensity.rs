@@ -3,6 +3,7 @@
 pub enum UnitLuminousIntensity {
     None,
     Candela,
+    Reserved(u32),
 }
 
 impl From<u32> for UnitLuminousIntensity {
@@ -10,7 +11,7 @@ impl From<u32> for UnitLuminousIntensity {
         match v {
             0x0 => UnitLuminousIntensity::None,
             0x1..=0x4 => UnitLuminousIntensity::Candela,
-            _ => panic!("Invalid unit")
+            v => UnitLuminousIntensity::Reserved(v),
         }
     }
 }
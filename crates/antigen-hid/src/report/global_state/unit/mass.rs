@@ -3,7 +3,8 @@
 pub enum UnitMass {
     None,
     Gram,
-    Slug
+    Slug,
+    Reserved(u32),
 }
 
 impl From<u32> for UnitMass {
@@ -12,7 +13,7 @@ impl From<u32> for UnitMass {
             0x0 => UnitMass::None,
             0x1..=0x2 => UnitMass::Gram,
             0x3..=0x4 => UnitMass::Slug,
-            _ => panic!("Invalid unit")
+            v => UnitMass::Reserved(v),
         }
     }
 }
@@ -40,7 +40,7 @@ impl From<u32> for Unit {
             0x50 => Unit::Current(UnitCurrent::from(v & 0x0F)),
             0x60 => Unit::LuminousIntensity(UnitLuminousIntensity::from(v & 0x0F)),
             0x70 => Unit::Reserved(v & 0x0F),
-            _ => panic!("Invalid unit")
+            _ => Unit::Reserved(v),
         }
     }
 }
@@ -6,6 +6,7 @@ pub enum UnitSystem {
     SiRotation,
     EnglishLinear,
     EnglishRotation,
+    Reserved(u32),
 }
 
 impl From<u32> for UnitSystem {
@@ -16,7 +17,7 @@ impl From<u32> for UnitSystem {
             0x2 => UnitSystem::SiRotation,
             0x3 => UnitSystem::EnglishLinear,
             0x4 => UnitSystem::EnglishRotation,
-            _ => panic!("Invalid unit")
+            v => UnitSystem::Reserved(v),
         }
     }
 }
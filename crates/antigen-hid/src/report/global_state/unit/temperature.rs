@@ -4,6 +4,7 @@ pub enum UnitTemperature {
     None,
     Kelvin,
     Farenheit,
+    Reserved(u32),
 }
 
 impl From<u32> for UnitTemperature {
@@ -12,7 +13,7 @@ impl From<u32> for UnitTemperature {
             0x0 => UnitTemperature::None,
             0x1..=0x2 => UnitTemperature::Kelvin,
             0x3..=0x4 => UnitTemperature::Farenheit,
-            _ => panic!("Invalid unit")
+            v => UnitTemperature::Reserved(v),
         }
     }
 }
@@ -3,6 +3,7 @@
 pub enum UnitTime {
     None,
     Seconds,
+    Reserved(u32),
 }
 
 impl From<u32> for UnitTime {
@@ -10,7 +11,7 @@ impl From<u32> for UnitTime {
         match v {
             0x0 => UnitTime::None,
             0x1..=0x4 => UnitTime::Seconds,
-            _ => panic!("Invalid unit")
+            v => UnitTime::Reserved(v),
         }
     }
 }
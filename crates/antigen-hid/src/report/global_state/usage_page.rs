@@ -62,3 +62,39 @@ impl From<u32> for UsagePage {
     }
 }
 
+
+/// Raw usage page ID
+///
+/// Pages decoded from a range of IDs map back to the first ID in that range.
+impl From<UsagePage> for u32 {
+    fn from(usage_page: UsagePage) -> Self {
+        match usage_page {
+            UsagePage::GenericDesktop => 0x01,
+            UsagePage::SimulationControls => 0x02,
+            UsagePage::VRControls => 0x03,
+            UsagePage::SportsControls => 0x04,
+            UsagePage::GameControls => 0x05,
+            UsagePage::GenericDeviceControls => 0x06,
+            UsagePage::KeyboardKeypad => 0x07,
+            UsagePage::Led => 0x08,
+            UsagePage::Button => 0x09,
+            UsagePage::Ordinal => 0x0A,
+            UsagePage::TelephonyDevice => 0x0B,
+            UsagePage::Consumer => 0x0C,
+            UsagePage::Digitizers => 0x0D,
+            UsagePage::Unicode => 0x10,
+            UsagePage::AlphanumericDisplay => 0x14,
+            UsagePage::MedicalInstrument => 0x40,
+            UsagePage::Monitor => 0x80,
+            UsagePage::Power => 0x84,
+            UsagePage::BarCodeScanner => 0x8C,
+            UsagePage::Scale => 0x8D,
+            UsagePage::MagneticStripeReadingDevices => 0x8E,
+            UsagePage::ReservedPointOfSale => 0x8F,
+            UsagePage::CameraControl => 0x90,
+            UsagePage::Arcade => 0x91,
+            UsagePage::VendorDefined => 0xFF00,
+            UsagePage::Reserved(usage_page) => usage_page,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+/// Direction reported by a `HatSwitch` usage
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HatDirection {
+    Centered,
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl HatDirection {
+    const CLOCKWISE: [HatDirection; 8] = [
+        HatDirection::Up,
+        HatDirection::UpRight,
+        HatDirection::Right,
+        HatDirection::DownRight,
+        HatDirection::Down,
+        HatDirection::DownLeft,
+        HatDirection::Left,
+        HatDirection::UpLeft,
+    ];
+
+    /// Decode a hat switch value given its logical range
+    ///
+    /// Positions are numbered clockwise from up, and values outside the range are the null state.
+    /// Only four and eight-position hats are supported, with four-position hats skipping the diagonals;
+    /// other ranges always decode as [`HatDirection::Centered`].
+    pub fn from_logical(value: i64, logical_min: i64, logical_max: i64) -> Self {
+        if value < logical_min || value > logical_max {
+            return HatDirection::Centered;
+        }
+
+        let positions = logical_max - logical_min + 1;
+        let step = match positions {
+            4 => 2,
+            8 => 1,
+            _ => return HatDirection::Centered,
+        };
+
+        HatDirection::CLOCKWISE[((value - logical_min) * step) as usize]
+    }
+
+    /// Angle clockwise from up in degrees, or None if centered
+    pub fn angle(&self) -> Option<f64> {
+        HatDirection::CLOCKWISE
+            .iter()
+            .position(|direction| direction == self)
+            .map(|i| i as f64 * 45.0)
+    }
+
+    /// Horizontal component, with right as positive
+    pub fn x(&self) -> i8 {
+        match self {
+            HatDirection::UpRight | HatDirection::Right | HatDirection::DownRight => 1,
+            HatDirection::DownLeft | HatDirection::Left | HatDirection::UpLeft => -1,
+            _ => 0,
+        }
+    }
+
+    /// Vertical component, with up as positive
+    pub fn y(&self) -> i8 {
+        match self {
+            HatDirection::UpLeft | HatDirection::Up | HatDirection::UpRight => 1,
+            HatDirection::DownRight | HatDirection::Down | HatDirection::DownLeft => -1,
+            _ => 0,
+        }
+    }
+}
+
+impl Default for HatDirection {
+    fn default() -> Self {
+        HatDirection::Centered
+    }
+}
+
+impl std::fmt::Display for HatDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            HatDirection::Centered => "Centered",
+            HatDirection::Up => "Up",
+            HatDirection::UpRight => "UpRight",
+            HatDirection::Right => "Right",
+            HatDirection::DownRight => "DownRight",
+            HatDirection::Down => "Down",
+            HatDirection::DownLeft => "DownLeft",
+            HatDirection::Left => "Left",
+            HatDirection::UpLeft => "UpLeft",
+        })
+    }
+}
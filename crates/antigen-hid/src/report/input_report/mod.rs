@@ -1,9 +1,16 @@
 //! HID Input Report
+mod hat_direction;
+mod physical_value;
 mod report_value;
 
+pub use hat_direction::*;
+pub use physical_value::*;
 pub use report_value::*;
 
-use crate::report::report_descriptor::ReportDescriptorItem;
+use crate::report::{
+    local_state::{GenericDesktopUsage, Usage},
+    report_descriptor::ReportDescriptorItem,
+};
 
 /// An input report parsed from raw HID input
 #[derive(Debug, Default, Clone)]
@@ -46,41 +53,75 @@ impl InputValue {
         &self.report_value
     }
 
-    pub fn physical(&self) -> Option<ReportValue> {
-        let logical_min = if let Some(logical_min) = self.data_item.global_state.logical_minimum {
-            logical_min
+    /// Logical minimum and maximum, if both are present and form a non-empty range
+    fn logical_range(&self) -> Option<(i64, i64)> {
+        let global_state = &self.data_item.global_state;
+        let logical_min = global_state.logical_minimum? as i64;
+        let logical_max = global_state.logical_maximum? as i64;
+        if logical_max > logical_min {
+            Some((logical_min, logical_max))
         } else {
-            return None;
-        };
+            None
+        }
+    }
 
-        let logical_max = if let Some(logical_max) = self.data_item.global_state.logical_maximum {
-            logical_max
-        } else {
+    /// Map the logical value into physical units
+    ///
+    /// Physical bounds default to the logical bounds if absent or both zero, as per the HID spec.
+    /// Returns None if the logical range is unknown, or the value lies outside it (ex. a null state).
+    pub fn physical(&self) -> Option<PhysicalValue> {
+        let (logical_min, logical_max) = self.logical_range()?;
+        let value = self.report_value.as_i64();
+        if value < logical_min || value > logical_max {
             return None;
-        };
+        }
 
-        let logical_range = logical_max - logical_min;
+        let global_state = &self.data_item.global_state;
+        let (physical_min, physical_max) =
+            match (global_state.physical_minimum, global_state.physical_maximum) {
+                (Some(min), Some(max)) if min != 0 || max != 0 => (min as f64, max as f64),
+                _ => (logical_min as f64, logical_max as f64),
+            };
 
-        let physical_min = if let Some(physical_min) = self.data_item.global_state.physical_minimum
-        {
-            physical_min
-        } else {
-            return None;
-        };
+        let t = (value - logical_min) as f64 / (logical_max - logical_min) as f64;
+        let physical = physical_min + t * (physical_max - physical_min);
 
-        let physical_max = if let Some(physical_max) = self.data_item.global_state.physical_maximum
-        {
-            physical_max
-        } else {
-            return None;
-        };
+        Some(PhysicalValue {
+            value: physical * 10f64.powi(global_state.exponent()),
+            unit: global_state.unit,
+        })
+    }
 
-        let physical_range = physical_max - physical_min;
+    /// Position of the logical value within its range, from 0 to 1
+    ///
+    /// Suitable for triggers, sliders and other one-sided axes.
+    pub fn normalized(&self) -> Option<f64> {
+        let (logical_min, logical_max) = self.logical_range()?;
+        let value = self.report_value.as_i64().max(logical_min).min(logical_max);
+        Some((value - logical_min) as f64 / (logical_max - logical_min) as f64)
+    }
 
-        None
+    /// Position of the logical value within its range, from -1 to 1
+    ///
+    /// Suitable for sticks and other centered axes.
+    pub fn normalized_signed(&self) -> Option<f64> {
+        self.normalized().map(|normalized| normalized * 2.0 - 1.0)
     }
 
-    pub fn normalized(&self) -> Option<ReportValue> {
-        None
+    /// Decode the value as a direction, if this item is a hat switch
+    pub fn hat_switch(&self) -> Option<HatDirection> {
+        match self.data_item.local_state.usage {
+            Some(Usage::GenericDesktop(GenericDesktopUsage::HatSwitch)) => (),
+            _ => return None,
+        }
+
+        let global_state = &self.data_item.global_state;
+        let logical_min = global_state.logical_minimum? as i64;
+        let logical_max = global_state.logical_maximum? as i64;
+        Some(HatDirection::from_logical(
+            self.report_value.as_i64(),
+            logical_min,
+            logical_max,
+        ))
     }
 }
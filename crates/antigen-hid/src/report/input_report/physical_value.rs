@@ -0,0 +1,18 @@
+use crate::report::global_state::unit::Unit;
+
+/// A logical value scaled into physical units
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PhysicalValue {
+    /// Value in physical units, with the unit exponent applied
+    pub value: f64,
+    pub unit: Option<Unit>,
+}
+
+impl std::fmt::Display for PhysicalValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.unit {
+            Some(unit) => write!(f, "{} {:?}", self.value, unit),
+            None => self.value.fmt(f),
+        }
+    }
+}
@@ -5,6 +5,71 @@ pub enum ReportValue {
     U8(u8),
     U16(u16),
     U32(u32),
+    U64(u64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+}
+
+impl ReportValue {
+    /// Create a value from the low `size` bits of `bits`
+    ///
+    /// Signed fields are sign-extended from their most significant bit,
+    /// and stored in the narrowest variant that fits them.
+    pub fn from_bits(bits: u64, size: usize, signed: bool) -> Self {
+        assert!(size > 0 && size <= 64, "Invalid value size");
+
+        if signed {
+            let shift = 64 - size;
+            let value = ((bits << shift) as i64) >> shift;
+            match size {
+                1..=8 => ReportValue::I8(value as i8),
+                9..=16 => ReportValue::I16(value as i16),
+                17..=32 => ReportValue::I32(value as i32),
+                _ => ReportValue::I64(value),
+            }
+        } else {
+            match size {
+                1 => ReportValue::Bool(bits & 1 > 0),
+                2..=8 => ReportValue::U8(bits as u8),
+                9..=16 => ReportValue::U16(bits as u16),
+                17..=32 => ReportValue::U32(bits as u32),
+                _ => ReportValue::U64(bits),
+            }
+        }
+    }
+
+    /// Integer representation of the value, with booleans as 0 or 1
+    ///
+    /// Unsigned 64-bit values above [`i64::MAX`] wrap around.
+    pub fn as_i64(&self) -> i64 {
+        match self {
+            ReportValue::Bool(v) => *v as i64,
+            ReportValue::U8(v) => *v as i64,
+            ReportValue::U16(v) => *v as i64,
+            ReportValue::U32(v) => *v as i64,
+            ReportValue::U64(v) => *v as i64,
+            ReportValue::I8(v) => *v as i64,
+            ReportValue::I16(v) => *v as i64,
+            ReportValue::I32(v) => *v as i64,
+            ReportValue::I64(v) => *v,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            ReportValue::U64(v) => *v as f64,
+            _ => self.as_i64() as f64,
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            ReportValue::I8(_) | ReportValue::I16(_) | ReportValue::I32(_) | ReportValue::I64(_)
+        )
+    }
 }
 
 impl std::fmt::Display for ReportValue {
@@ -14,7 +79,11 @@ impl std::fmt::Display for ReportValue {
             ReportValue::U8(v) => v.fmt(f),
             ReportValue::U16(v) => v.fmt(f),
             ReportValue::U32(v) => v.fmt(f),
+            ReportValue::U64(v) => v.fmt(f),
+            ReportValue::I8(v) => v.fmt(f),
+            ReportValue::I16(v) => v.fmt(f),
+            ReportValue::I32(v) => v.fmt(f),
+            ReportValue::I64(v) => v.fmt(f),
         }
     }
 }
-
@@ -0,0 +1,43 @@
+/// Buttons are identified by ordinal, starting from 1 for the primary button
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ButtonUsage {
+    NoButtonPressed,
+    Button(u32),
+}
+
+impl ButtonUsage {
+    /// 1-based button number, or None for [`ButtonUsage::NoButtonPressed`]
+    pub fn ordinal(&self) -> Option<u32> {
+        match self {
+            ButtonUsage::NoButtonPressed => None,
+            ButtonUsage::Button(ordinal) => Some(*ordinal),
+        }
+    }
+}
+
+impl std::fmt::Display for ButtonUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ButtonUsage::NoButtonPressed => f.write_str("NoButtonPressed"),
+            ButtonUsage::Button(ordinal) => write!(f, "Button {}", ordinal),
+        }
+    }
+}
+
+impl From<u32> for ButtonUsage {
+    fn from(v: u32) -> Self {
+        match v {
+            0 => ButtonUsage::NoButtonPressed,
+            ordinal => ButtonUsage::Button(ordinal),
+        }
+    }
+}
+
+impl From<ButtonUsage> for u32 {
+    fn from(usage: ButtonUsage) -> Self {
+        match usage {
+            ButtonUsage::NoButtonPressed => 0,
+            ButtonUsage::Button(ordinal) => ordinal,
+        }
+    }
+}
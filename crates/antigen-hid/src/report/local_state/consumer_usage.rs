@@ -0,0 +1,349 @@
+/// Media keys, application launch buttons and other general consumer controls
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConsumerUsage {
+    Unassigned,
+    ConsumerControl,
+    NumericKeyPad,
+    ProgrammableButtons,
+    Microphone,
+    Headphone,
+    GraphicEqualizer,
+    Plus10,
+    Plus100,
+    AmPm,
+    Power,
+    Reset,
+    Sleep,
+    SleepAfter,
+    SleepMode,
+    Illumination,
+    FunctionButtons,
+    Menu,
+    MenuPick,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    MenuEscape,
+    MenuValueIncrease,
+    MenuValueDecrease,
+    DataOnScreen,
+    ClosedCaption,
+    Snapshot,
+    DisplayBrightnessIncrement,
+    DisplayBrightnessDecrement,
+    Selection,
+    AssignSelection,
+    ModeStep,
+    RecallLast,
+    Play,
+    Pause,
+    Record,
+    FastForward,
+    Rewind,
+    ScanNextTrack,
+    ScanPreviousTrack,
+    Stop,
+    Eject,
+    RandomPlay,
+    Repeat,
+    PlayPause,
+    PlaySkip,
+    Volume,
+    Balance,
+    Mute,
+    Bass,
+    Treble,
+    BassBoost,
+    VolumeIncrement,
+    VolumeDecrement,
+    AlConsumerControlConfiguration,
+    AlEmailReader,
+    AlCalculator,
+    AlLocalMachineBrowser,
+    AlInternetBrowser,
+    AcNew,
+    AcOpen,
+    AcClose,
+    AcSave,
+    AcPrint,
+    AcUndo,
+    AcCopy,
+    AcCut,
+    AcPaste,
+    AcSearch,
+    AcHome,
+    AcBack,
+    AcForward,
+    AcStop,
+    AcRefresh,
+    AcBookmarks,
+    AcZoomIn,
+    AcZoomOut,
+    AcPan,
+    Reserved(u32),
+}
+
+impl std::fmt::Display for ConsumerUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConsumerUsage::Unassigned => "Unassigned",
+            ConsumerUsage::ConsumerControl => "ConsumerControl",
+            ConsumerUsage::NumericKeyPad => "NumericKeyPad",
+            ConsumerUsage::ProgrammableButtons => "ProgrammableButtons",
+            ConsumerUsage::Microphone => "Microphone",
+            ConsumerUsage::Headphone => "Headphone",
+            ConsumerUsage::GraphicEqualizer => "GraphicEqualizer",
+            ConsumerUsage::Plus10 => "Plus10",
+            ConsumerUsage::Plus100 => "Plus100",
+            ConsumerUsage::AmPm => "AmPm",
+            ConsumerUsage::Power => "Power",
+            ConsumerUsage::Reset => "Reset",
+            ConsumerUsage::Sleep => "Sleep",
+            ConsumerUsage::SleepAfter => "SleepAfter",
+            ConsumerUsage::SleepMode => "SleepMode",
+            ConsumerUsage::Illumination => "Illumination",
+            ConsumerUsage::FunctionButtons => "FunctionButtons",
+            ConsumerUsage::Menu => "Menu",
+            ConsumerUsage::MenuPick => "MenuPick",
+            ConsumerUsage::MenuUp => "MenuUp",
+            ConsumerUsage::MenuDown => "MenuDown",
+            ConsumerUsage::MenuLeft => "MenuLeft",
+            ConsumerUsage::MenuRight => "MenuRight",
+            ConsumerUsage::MenuEscape => "MenuEscape",
+            ConsumerUsage::MenuValueIncrease => "MenuValueIncrease",
+            ConsumerUsage::MenuValueDecrease => "MenuValueDecrease",
+            ConsumerUsage::DataOnScreen => "DataOnScreen",
+            ConsumerUsage::ClosedCaption => "ClosedCaption",
+            ConsumerUsage::Snapshot => "Snapshot",
+            ConsumerUsage::DisplayBrightnessIncrement => "DisplayBrightnessIncrement",
+            ConsumerUsage::DisplayBrightnessDecrement => "DisplayBrightnessDecrement",
+            ConsumerUsage::Selection => "Selection",
+            ConsumerUsage::AssignSelection => "AssignSelection",
+            ConsumerUsage::ModeStep => "ModeStep",
+            ConsumerUsage::RecallLast => "RecallLast",
+            ConsumerUsage::Play => "Play",
+            ConsumerUsage::Pause => "Pause",
+            ConsumerUsage::Record => "Record",
+            ConsumerUsage::FastForward => "FastForward",
+            ConsumerUsage::Rewind => "Rewind",
+            ConsumerUsage::ScanNextTrack => "ScanNextTrack",
+            ConsumerUsage::ScanPreviousTrack => "ScanPreviousTrack",
+            ConsumerUsage::Stop => "Stop",
+            ConsumerUsage::Eject => "Eject",
+            ConsumerUsage::RandomPlay => "RandomPlay",
+            ConsumerUsage::Repeat => "Repeat",
+            ConsumerUsage::PlayPause => "PlayPause",
+            ConsumerUsage::PlaySkip => "PlaySkip",
+            ConsumerUsage::Volume => "Volume",
+            ConsumerUsage::Balance => "Balance",
+            ConsumerUsage::Mute => "Mute",
+            ConsumerUsage::Bass => "Bass",
+            ConsumerUsage::Treble => "Treble",
+            ConsumerUsage::BassBoost => "BassBoost",
+            ConsumerUsage::VolumeIncrement => "VolumeIncrement",
+            ConsumerUsage::VolumeDecrement => "VolumeDecrement",
+            ConsumerUsage::AlConsumerControlConfiguration => "AlConsumerControlConfiguration",
+            ConsumerUsage::AlEmailReader => "AlEmailReader",
+            ConsumerUsage::AlCalculator => "AlCalculator",
+            ConsumerUsage::AlLocalMachineBrowser => "AlLocalMachineBrowser",
+            ConsumerUsage::AlInternetBrowser => "AlInternetBrowser",
+            ConsumerUsage::AcNew => "AcNew",
+            ConsumerUsage::AcOpen => "AcOpen",
+            ConsumerUsage::AcClose => "AcClose",
+            ConsumerUsage::AcSave => "AcSave",
+            ConsumerUsage::AcPrint => "AcPrint",
+            ConsumerUsage::AcUndo => "AcUndo",
+            ConsumerUsage::AcCopy => "AcCopy",
+            ConsumerUsage::AcCut => "AcCut",
+            ConsumerUsage::AcPaste => "AcPaste",
+            ConsumerUsage::AcSearch => "AcSearch",
+            ConsumerUsage::AcHome => "AcHome",
+            ConsumerUsage::AcBack => "AcBack",
+            ConsumerUsage::AcForward => "AcForward",
+            ConsumerUsage::AcStop => "AcStop",
+            ConsumerUsage::AcRefresh => "AcRefresh",
+            ConsumerUsage::AcBookmarks => "AcBookmarks",
+            ConsumerUsage::AcZoomIn => "AcZoomIn",
+            ConsumerUsage::AcZoomOut => "AcZoomOut",
+            ConsumerUsage::AcPan => "AcPan",
+            ConsumerUsage::Reserved(v) => return write!(f, "Reserved(0x{:x})", v),
+        })
+    }
+}
+
+impl From<u32> for ConsumerUsage {
+    fn from(v: u32) -> Self {
+        match v {
+            0x00 => ConsumerUsage::Unassigned,
+            0x01 => ConsumerUsage::ConsumerControl,
+            0x02 => ConsumerUsage::NumericKeyPad,
+            0x03 => ConsumerUsage::ProgrammableButtons,
+            0x04 => ConsumerUsage::Microphone,
+            0x05 => ConsumerUsage::Headphone,
+            0x06 => ConsumerUsage::GraphicEqualizer,
+            0x20 => ConsumerUsage::Plus10,
+            0x21 => ConsumerUsage::Plus100,
+            0x22 => ConsumerUsage::AmPm,
+            0x30 => ConsumerUsage::Power,
+            0x31 => ConsumerUsage::Reset,
+            0x32 => ConsumerUsage::Sleep,
+            0x33 => ConsumerUsage::SleepAfter,
+            0x34 => ConsumerUsage::SleepMode,
+            0x35 => ConsumerUsage::Illumination,
+            0x36 => ConsumerUsage::FunctionButtons,
+            0x40 => ConsumerUsage::Menu,
+            0x41 => ConsumerUsage::MenuPick,
+            0x42 => ConsumerUsage::MenuUp,
+            0x43 => ConsumerUsage::MenuDown,
+            0x44 => ConsumerUsage::MenuLeft,
+            0x45 => ConsumerUsage::MenuRight,
+            0x46 => ConsumerUsage::MenuEscape,
+            0x47 => ConsumerUsage::MenuValueIncrease,
+            0x48 => ConsumerUsage::MenuValueDecrease,
+            0x60 => ConsumerUsage::DataOnScreen,
+            0x61 => ConsumerUsage::ClosedCaption,
+            0x65 => ConsumerUsage::Snapshot,
+            0x6F => ConsumerUsage::DisplayBrightnessIncrement,
+            0x70 => ConsumerUsage::DisplayBrightnessDecrement,
+            0x80 => ConsumerUsage::Selection,
+            0x81 => ConsumerUsage::AssignSelection,
+            0x82 => ConsumerUsage::ModeStep,
+            0x83 => ConsumerUsage::RecallLast,
+            0xB0 => ConsumerUsage::Play,
+            0xB1 => ConsumerUsage::Pause,
+            0xB2 => ConsumerUsage::Record,
+            0xB3 => ConsumerUsage::FastForward,
+            0xB4 => ConsumerUsage::Rewind,
+            0xB5 => ConsumerUsage::ScanNextTrack,
+            0xB6 => ConsumerUsage::ScanPreviousTrack,
+            0xB7 => ConsumerUsage::Stop,
+            0xB8 => ConsumerUsage::Eject,
+            0xB9 => ConsumerUsage::RandomPlay,
+            0xBC => ConsumerUsage::Repeat,
+            0xCD => ConsumerUsage::PlayPause,
+            0xCE => ConsumerUsage::PlaySkip,
+            0xE0 => ConsumerUsage::Volume,
+            0xE1 => ConsumerUsage::Balance,
+            0xE2 => ConsumerUsage::Mute,
+            0xE3 => ConsumerUsage::Bass,
+            0xE4 => ConsumerUsage::Treble,
+            0xE5 => ConsumerUsage::BassBoost,
+            0xE9 => ConsumerUsage::VolumeIncrement,
+            0xEA => ConsumerUsage::VolumeDecrement,
+            0x183 => ConsumerUsage::AlConsumerControlConfiguration,
+            0x18A => ConsumerUsage::AlEmailReader,
+            0x192 => ConsumerUsage::AlCalculator,
+            0x194 => ConsumerUsage::AlLocalMachineBrowser,
+            0x196 => ConsumerUsage::AlInternetBrowser,
+            0x201 => ConsumerUsage::AcNew,
+            0x202 => ConsumerUsage::AcOpen,
+            0x203 => ConsumerUsage::AcClose,
+            0x207 => ConsumerUsage::AcSave,
+            0x208 => ConsumerUsage::AcPrint,
+            0x21A => ConsumerUsage::AcUndo,
+            0x21B => ConsumerUsage::AcCopy,
+            0x21C => ConsumerUsage::AcCut,
+            0x21D => ConsumerUsage::AcPaste,
+            0x221 => ConsumerUsage::AcSearch,
+            0x223 => ConsumerUsage::AcHome,
+            0x224 => ConsumerUsage::AcBack,
+            0x225 => ConsumerUsage::AcForward,
+            0x226 => ConsumerUsage::AcStop,
+            0x227 => ConsumerUsage::AcRefresh,
+            0x22A => ConsumerUsage::AcBookmarks,
+            0x22D => ConsumerUsage::AcZoomIn,
+            0x22E => ConsumerUsage::AcZoomOut,
+            0x238 => ConsumerUsage::AcPan,
+            _ => ConsumerUsage::Reserved(v),
+        }
+    }
+}
+
+impl From<ConsumerUsage> for u32 {
+    fn from(usage: ConsumerUsage) -> Self {
+        match usage {
+            ConsumerUsage::Unassigned => 0x00,
+            ConsumerUsage::ConsumerControl => 0x01,
+            ConsumerUsage::NumericKeyPad => 0x02,
+            ConsumerUsage::ProgrammableButtons => 0x03,
+            ConsumerUsage::Microphone => 0x04,
+            ConsumerUsage::Headphone => 0x05,
+            ConsumerUsage::GraphicEqualizer => 0x06,
+            ConsumerUsage::Plus10 => 0x20,
+            ConsumerUsage::Plus100 => 0x21,
+            ConsumerUsage::AmPm => 0x22,
+            ConsumerUsage::Power => 0x30,
+            ConsumerUsage::Reset => 0x31,
+            ConsumerUsage::Sleep => 0x32,
+            ConsumerUsage::SleepAfter => 0x33,
+            ConsumerUsage::SleepMode => 0x34,
+            ConsumerUsage::Illumination => 0x35,
+            ConsumerUsage::FunctionButtons => 0x36,
+            ConsumerUsage::Menu => 0x40,
+            ConsumerUsage::MenuPick => 0x41,
+            ConsumerUsage::MenuUp => 0x42,
+            ConsumerUsage::MenuDown => 0x43,
+            ConsumerUsage::MenuLeft => 0x44,
+            ConsumerUsage::MenuRight => 0x45,
+            ConsumerUsage::MenuEscape => 0x46,
+            ConsumerUsage::MenuValueIncrease => 0x47,
+            ConsumerUsage::MenuValueDecrease => 0x48,
+            ConsumerUsage::DataOnScreen => 0x60,
+            ConsumerUsage::ClosedCaption => 0x61,
+            ConsumerUsage::Snapshot => 0x65,
+            ConsumerUsage::DisplayBrightnessIncrement => 0x6F,
+            ConsumerUsage::DisplayBrightnessDecrement => 0x70,
+            ConsumerUsage::Selection => 0x80,
+            ConsumerUsage::AssignSelection => 0x81,
+            ConsumerUsage::ModeStep => 0x82,
+            ConsumerUsage::RecallLast => 0x83,
+            ConsumerUsage::Play => 0xB0,
+            ConsumerUsage::Pause => 0xB1,
+            ConsumerUsage::Record => 0xB2,
+            ConsumerUsage::FastForward => 0xB3,
+            ConsumerUsage::Rewind => 0xB4,
+            ConsumerUsage::ScanNextTrack => 0xB5,
+            ConsumerUsage::ScanPreviousTrack => 0xB6,
+            ConsumerUsage::Stop => 0xB7,
+            ConsumerUsage::Eject => 0xB8,
+            ConsumerUsage::RandomPlay => 0xB9,
+            ConsumerUsage::Repeat => 0xBC,
+            ConsumerUsage::PlayPause => 0xCD,
+            ConsumerUsage::PlaySkip => 0xCE,
+            ConsumerUsage::Volume => 0xE0,
+            ConsumerUsage::Balance => 0xE1,
+            ConsumerUsage::Mute => 0xE2,
+            ConsumerUsage::Bass => 0xE3,
+            ConsumerUsage::Treble => 0xE4,
+            ConsumerUsage::BassBoost => 0xE5,
+            ConsumerUsage::VolumeIncrement => 0xE9,
+            ConsumerUsage::VolumeDecrement => 0xEA,
+            ConsumerUsage::AlConsumerControlConfiguration => 0x183,
+            ConsumerUsage::AlEmailReader => 0x18A,
+            ConsumerUsage::AlCalculator => 0x192,
+            ConsumerUsage::AlLocalMachineBrowser => 0x194,
+            ConsumerUsage::AlInternetBrowser => 0x196,
+            ConsumerUsage::AcNew => 0x201,
+            ConsumerUsage::AcOpen => 0x202,
+            ConsumerUsage::AcClose => 0x203,
+            ConsumerUsage::AcSave => 0x207,
+            ConsumerUsage::AcPrint => 0x208,
+            ConsumerUsage::AcUndo => 0x21A,
+            ConsumerUsage::AcCopy => 0x21B,
+            ConsumerUsage::AcCut => 0x21C,
+            ConsumerUsage::AcPaste => 0x21D,
+            ConsumerUsage::AcSearch => 0x221,
+            ConsumerUsage::AcHome => 0x223,
+            ConsumerUsage::AcBack => 0x224,
+            ConsumerUsage::AcForward => 0x225,
+            ConsumerUsage::AcStop => 0x226,
+            ConsumerUsage::AcRefresh => 0x227,
+            ConsumerUsage::AcBookmarks => 0x22A,
+            ConsumerUsage::AcZoomIn => 0x22D,
+            ConsumerUsage::AcZoomOut => 0x22E,
+            ConsumerUsage::AcPan => 0x238,
+            ConsumerUsage::Reserved(v) => v,
+        }
+    }
+}
@@ -0,0 +1,237 @@
+/// Describes pens, touch screens and other digitizing devices
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DigitizerUsage {
+    Undefined,
+    Digitizer,
+    Pen,
+    LightPen,
+    TouchScreen,
+    TouchPad,
+    WhiteBoard,
+    CoordinateMeasuringMachine,
+    ThreeDDigitizer,
+    StereoPlotter,
+    ArticulatedArm,
+    Armature,
+    MultiplePointDigitizer,
+    FreeSpaceWand,
+    DeviceConfiguration,
+    Stylus,
+    Puck,
+    Finger,
+    DeviceSettings,
+    TipPressure,
+    BarrelPressure,
+    InRange,
+    Touch,
+    Untouch,
+    Tap,
+    Quality,
+    DataValid,
+    TransducerIndex,
+    TabletFunctionKeys,
+    ProgramChangeKeys,
+    BatteryStrength,
+    Invert,
+    XTilt,
+    YTilt,
+    Azimuth,
+    Altitude,
+    Twist,
+    TipSwitch,
+    SecondaryTipSwitch,
+    BarrelSwitch,
+    Eraser,
+    TabletPick,
+    TouchValid,
+    Width,
+    Height,
+    ContactIdentifier,
+    DeviceMode,
+    DeviceIdentifier,
+    ContactCount,
+    ContactCountMaximum,
+    SecondaryBarrelSwitch,
+    TransducerSerialNumber,
+    Reserved(u32),
+}
+
+impl std::fmt::Display for DigitizerUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DigitizerUsage::Undefined => "Undefined",
+            DigitizerUsage::Digitizer => "Digitizer",
+            DigitizerUsage::Pen => "Pen",
+            DigitizerUsage::LightPen => "LightPen",
+            DigitizerUsage::TouchScreen => "TouchScreen",
+            DigitizerUsage::TouchPad => "TouchPad",
+            DigitizerUsage::WhiteBoard => "WhiteBoard",
+            DigitizerUsage::CoordinateMeasuringMachine => "CoordinateMeasuringMachine",
+            DigitizerUsage::ThreeDDigitizer => "ThreeDDigitizer",
+            DigitizerUsage::StereoPlotter => "StereoPlotter",
+            DigitizerUsage::ArticulatedArm => "ArticulatedArm",
+            DigitizerUsage::Armature => "Armature",
+            DigitizerUsage::MultiplePointDigitizer => "MultiplePointDigitizer",
+            DigitizerUsage::FreeSpaceWand => "FreeSpaceWand",
+            DigitizerUsage::DeviceConfiguration => "DeviceConfiguration",
+            DigitizerUsage::Stylus => "Stylus",
+            DigitizerUsage::Puck => "Puck",
+            DigitizerUsage::Finger => "Finger",
+            DigitizerUsage::DeviceSettings => "DeviceSettings",
+            DigitizerUsage::TipPressure => "TipPressure",
+            DigitizerUsage::BarrelPressure => "BarrelPressure",
+            DigitizerUsage::InRange => "InRange",
+            DigitizerUsage::Touch => "Touch",
+            DigitizerUsage::Untouch => "Untouch",
+            DigitizerUsage::Tap => "Tap",
+            DigitizerUsage::Quality => "Quality",
+            DigitizerUsage::DataValid => "DataValid",
+            DigitizerUsage::TransducerIndex => "TransducerIndex",
+            DigitizerUsage::TabletFunctionKeys => "TabletFunctionKeys",
+            DigitizerUsage::ProgramChangeKeys => "ProgramChangeKeys",
+            DigitizerUsage::BatteryStrength => "BatteryStrength",
+            DigitizerUsage::Invert => "Invert",
+            DigitizerUsage::XTilt => "XTilt",
+            DigitizerUsage::YTilt => "YTilt",
+            DigitizerUsage::Azimuth => "Azimuth",
+            DigitizerUsage::Altitude => "Altitude",
+            DigitizerUsage::Twist => "Twist",
+            DigitizerUsage::TipSwitch => "TipSwitch",
+            DigitizerUsage::SecondaryTipSwitch => "SecondaryTipSwitch",
+            DigitizerUsage::BarrelSwitch => "BarrelSwitch",
+            DigitizerUsage::Eraser => "Eraser",
+            DigitizerUsage::TabletPick => "TabletPick",
+            DigitizerUsage::TouchValid => "TouchValid",
+            DigitizerUsage::Width => "Width",
+            DigitizerUsage::Height => "Height",
+            DigitizerUsage::ContactIdentifier => "ContactIdentifier",
+            DigitizerUsage::DeviceMode => "DeviceMode",
+            DigitizerUsage::DeviceIdentifier => "DeviceIdentifier",
+            DigitizerUsage::ContactCount => "ContactCount",
+            DigitizerUsage::ContactCountMaximum => "ContactCountMaximum",
+            DigitizerUsage::SecondaryBarrelSwitch => "SecondaryBarrelSwitch",
+            DigitizerUsage::TransducerSerialNumber => "TransducerSerialNumber",
+            DigitizerUsage::Reserved(v) => return write!(f, "Reserved(0x{:x})", v),
+        })
+    }
+}
+
+impl From<u32> for DigitizerUsage {
+    fn from(v: u32) -> Self {
+        match v {
+            0x00 => DigitizerUsage::Undefined,
+            0x01 => DigitizerUsage::Digitizer,
+            0x02 => DigitizerUsage::Pen,
+            0x03 => DigitizerUsage::LightPen,
+            0x04 => DigitizerUsage::TouchScreen,
+            0x05 => DigitizerUsage::TouchPad,
+            0x06 => DigitizerUsage::WhiteBoard,
+            0x07 => DigitizerUsage::CoordinateMeasuringMachine,
+            0x08 => DigitizerUsage::ThreeDDigitizer,
+            0x09 => DigitizerUsage::StereoPlotter,
+            0x0A => DigitizerUsage::ArticulatedArm,
+            0x0B => DigitizerUsage::Armature,
+            0x0C => DigitizerUsage::MultiplePointDigitizer,
+            0x0D => DigitizerUsage::FreeSpaceWand,
+            0x0E => DigitizerUsage::DeviceConfiguration,
+            0x20 => DigitizerUsage::Stylus,
+            0x21 => DigitizerUsage::Puck,
+            0x22 => DigitizerUsage::Finger,
+            0x23 => DigitizerUsage::DeviceSettings,
+            0x30 => DigitizerUsage::TipPressure,
+            0x31 => DigitizerUsage::BarrelPressure,
+            0x32 => DigitizerUsage::InRange,
+            0x33 => DigitizerUsage::Touch,
+            0x34 => DigitizerUsage::Untouch,
+            0x35 => DigitizerUsage::Tap,
+            0x36 => DigitizerUsage::Quality,
+            0x37 => DigitizerUsage::DataValid,
+            0x38 => DigitizerUsage::TransducerIndex,
+            0x39 => DigitizerUsage::TabletFunctionKeys,
+            0x3A => DigitizerUsage::ProgramChangeKeys,
+            0x3B => DigitizerUsage::BatteryStrength,
+            0x3C => DigitizerUsage::Invert,
+            0x3D => DigitizerUsage::XTilt,
+            0x3E => DigitizerUsage::YTilt,
+            0x3F => DigitizerUsage::Azimuth,
+            0x40 => DigitizerUsage::Altitude,
+            0x41 => DigitizerUsage::Twist,
+            0x42 => DigitizerUsage::TipSwitch,
+            0x43 => DigitizerUsage::SecondaryTipSwitch,
+            0x44 => DigitizerUsage::BarrelSwitch,
+            0x45 => DigitizerUsage::Eraser,
+            0x46 => DigitizerUsage::TabletPick,
+            0x47 => DigitizerUsage::TouchValid,
+            0x48 => DigitizerUsage::Width,
+            0x49 => DigitizerUsage::Height,
+            0x51 => DigitizerUsage::ContactIdentifier,
+            0x52 => DigitizerUsage::DeviceMode,
+            0x53 => DigitizerUsage::DeviceIdentifier,
+            0x54 => DigitizerUsage::ContactCount,
+            0x55 => DigitizerUsage::ContactCountMaximum,
+            0x5A => DigitizerUsage::SecondaryBarrelSwitch,
+            0x5B => DigitizerUsage::TransducerSerialNumber,
+            _ => DigitizerUsage::Reserved(v),
+        }
+    }
+}
+
+impl From<DigitizerUsage> for u32 {
+    fn from(usage: DigitizerUsage) -> Self {
+        match usage {
+            DigitizerUsage::Undefined => 0x00,
+            DigitizerUsage::Digitizer => 0x01,
+            DigitizerUsage::Pen => 0x02,
+            DigitizerUsage::LightPen => 0x03,
+            DigitizerUsage::TouchScreen => 0x04,
+            DigitizerUsage::TouchPad => 0x05,
+            DigitizerUsage::WhiteBoard => 0x06,
+            DigitizerUsage::CoordinateMeasuringMachine => 0x07,
+            DigitizerUsage::ThreeDDigitizer => 0x08,
+            DigitizerUsage::StereoPlotter => 0x09,
+            DigitizerUsage::ArticulatedArm => 0x0A,
+            DigitizerUsage::Armature => 0x0B,
+            DigitizerUsage::MultiplePointDigitizer => 0x0C,
+            DigitizerUsage::FreeSpaceWand => 0x0D,
+            DigitizerUsage::DeviceConfiguration => 0x0E,
+            DigitizerUsage::Stylus => 0x20,
+            DigitizerUsage::Puck => 0x21,
+            DigitizerUsage::Finger => 0x22,
+            DigitizerUsage::DeviceSettings => 0x23,
+            DigitizerUsage::TipPressure => 0x30,
+            DigitizerUsage::BarrelPressure => 0x31,
+            DigitizerUsage::InRange => 0x32,
+            DigitizerUsage::Touch => 0x33,
+            DigitizerUsage::Untouch => 0x34,
+            DigitizerUsage::Tap => 0x35,
+            DigitizerUsage::Quality => 0x36,
+            DigitizerUsage::DataValid => 0x37,
+            DigitizerUsage::TransducerIndex => 0x38,
+            DigitizerUsage::TabletFunctionKeys => 0x39,
+            DigitizerUsage::ProgramChangeKeys => 0x3A,
+            DigitizerUsage::BatteryStrength => 0x3B,
+            DigitizerUsage::Invert => 0x3C,
+            DigitizerUsage::XTilt => 0x3D,
+            DigitizerUsage::YTilt => 0x3E,
+            DigitizerUsage::Azimuth => 0x3F,
+            DigitizerUsage::Altitude => 0x40,
+            DigitizerUsage::Twist => 0x41,
+            DigitizerUsage::TipSwitch => 0x42,
+            DigitizerUsage::SecondaryTipSwitch => 0x43,
+            DigitizerUsage::BarrelSwitch => 0x44,
+            DigitizerUsage::Eraser => 0x45,
+            DigitizerUsage::TabletPick => 0x46,
+            DigitizerUsage::TouchValid => 0x47,
+            DigitizerUsage::Width => 0x48,
+            DigitizerUsage::Height => 0x49,
+            DigitizerUsage::ContactIdentifier => 0x51,
+            DigitizerUsage::DeviceMode => 0x52,
+            DigitizerUsage::DeviceIdentifier => 0x53,
+            DigitizerUsage::ContactCount => 0x54,
+            DigitizerUsage::ContactCountMaximum => 0x55,
+            DigitizerUsage::SecondaryBarrelSwitch => 0x5A,
+            DigitizerUsage::TransducerSerialNumber => 0x5B,
+            DigitizerUsage::Reserved(v) => v,
+        }
+    }
+}
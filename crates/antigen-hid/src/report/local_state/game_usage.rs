@@ -0,0 +1,149 @@
+/// Controls specific to game devices, such as guns and pinball machines
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GameUsage {
+    Undefined,
+    ThreeDGameController,
+    PinballDevice,
+    GunDevice,
+    PointOfView,
+    TurnRightLeft,
+    PitchForwardBackward,
+    RollRightLeft,
+    MoveRightLeft,
+    MoveForwardBackward,
+    MoveUpDown,
+    LeanRightLeft,
+    LeanForwardBackward,
+    HeightOfPov,
+    Flipper,
+    SecondaryFlipper,
+    Bump,
+    NewGame,
+    ShootBall,
+    Player,
+    GunBolt,
+    GunClip,
+    GunSelector,
+    GunSingleShot,
+    GunBurst,
+    GunAutomatic,
+    GunSafety,
+    GamepadFireJump,
+    GamepadTrigger,
+    FormFittingGamepad,
+    Reserved(u32),
+}
+
+impl std::fmt::Display for GameUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GameUsage::Undefined => "Undefined",
+            GameUsage::ThreeDGameController => "ThreeDGameController",
+            GameUsage::PinballDevice => "PinballDevice",
+            GameUsage::GunDevice => "GunDevice",
+            GameUsage::PointOfView => "PointOfView",
+            GameUsage::TurnRightLeft => "TurnRightLeft",
+            GameUsage::PitchForwardBackward => "PitchForwardBackward",
+            GameUsage::RollRightLeft => "RollRightLeft",
+            GameUsage::MoveRightLeft => "MoveRightLeft",
+            GameUsage::MoveForwardBackward => "MoveForwardBackward",
+            GameUsage::MoveUpDown => "MoveUpDown",
+            GameUsage::LeanRightLeft => "LeanRightLeft",
+            GameUsage::LeanForwardBackward => "LeanForwardBackward",
+            GameUsage::HeightOfPov => "HeightOfPov",
+            GameUsage::Flipper => "Flipper",
+            GameUsage::SecondaryFlipper => "SecondaryFlipper",
+            GameUsage::Bump => "Bump",
+            GameUsage::NewGame => "NewGame",
+            GameUsage::ShootBall => "ShootBall",
+            GameUsage::Player => "Player",
+            GameUsage::GunBolt => "GunBolt",
+            GameUsage::GunClip => "GunClip",
+            GameUsage::GunSelector => "GunSelector",
+            GameUsage::GunSingleShot => "GunSingleShot",
+            GameUsage::GunBurst => "GunBurst",
+            GameUsage::GunAutomatic => "GunAutomatic",
+            GameUsage::GunSafety => "GunSafety",
+            GameUsage::GamepadFireJump => "GamepadFireJump",
+            GameUsage::GamepadTrigger => "GamepadTrigger",
+            GameUsage::FormFittingGamepad => "FormFittingGamepad",
+            GameUsage::Reserved(v) => return write!(f, "Reserved(0x{:x})", v),
+        })
+    }
+}
+
+impl From<u32> for GameUsage {
+    fn from(v: u32) -> Self {
+        match v {
+            0x00 => GameUsage::Undefined,
+            0x01 => GameUsage::ThreeDGameController,
+            0x02 => GameUsage::PinballDevice,
+            0x03 => GameUsage::GunDevice,
+            0x20 => GameUsage::PointOfView,
+            0x21 => GameUsage::TurnRightLeft,
+            0x22 => GameUsage::PitchForwardBackward,
+            0x23 => GameUsage::RollRightLeft,
+            0x24 => GameUsage::MoveRightLeft,
+            0x25 => GameUsage::MoveForwardBackward,
+            0x26 => GameUsage::MoveUpDown,
+            0x27 => GameUsage::LeanRightLeft,
+            0x28 => GameUsage::LeanForwardBackward,
+            0x29 => GameUsage::HeightOfPov,
+            0x2A => GameUsage::Flipper,
+            0x2B => GameUsage::SecondaryFlipper,
+            0x2C => GameUsage::Bump,
+            0x2D => GameUsage::NewGame,
+            0x2E => GameUsage::ShootBall,
+            0x2F => GameUsage::Player,
+            0x30 => GameUsage::GunBolt,
+            0x31 => GameUsage::GunClip,
+            0x32 => GameUsage::GunSelector,
+            0x33 => GameUsage::GunSingleShot,
+            0x34 => GameUsage::GunBurst,
+            0x35 => GameUsage::GunAutomatic,
+            0x36 => GameUsage::GunSafety,
+            0x37 => GameUsage::GamepadFireJump,
+            0x39 => GameUsage::GamepadTrigger,
+            0x3A => GameUsage::FormFittingGamepad,
+            _ => GameUsage::Reserved(v),
+        }
+    }
+}
+
+impl From<GameUsage> for u32 {
+    fn from(usage: GameUsage) -> Self {
+        match usage {
+            GameUsage::Undefined => 0x00,
+            GameUsage::ThreeDGameController => 0x01,
+            GameUsage::PinballDevice => 0x02,
+            GameUsage::GunDevice => 0x03,
+            GameUsage::PointOfView => 0x20,
+            GameUsage::TurnRightLeft => 0x21,
+            GameUsage::PitchForwardBackward => 0x22,
+            GameUsage::RollRightLeft => 0x23,
+            GameUsage::MoveRightLeft => 0x24,
+            GameUsage::MoveForwardBackward => 0x25,
+            GameUsage::MoveUpDown => 0x26,
+            GameUsage::LeanRightLeft => 0x27,
+            GameUsage::LeanForwardBackward => 0x28,
+            GameUsage::HeightOfPov => 0x29,
+            GameUsage::Flipper => 0x2A,
+            GameUsage::SecondaryFlipper => 0x2B,
+            GameUsage::Bump => 0x2C,
+            GameUsage::NewGame => 0x2D,
+            GameUsage::ShootBall => 0x2E,
+            GameUsage::Player => 0x2F,
+            GameUsage::GunBolt => 0x30,
+            GameUsage::GunClip => 0x31,
+            GameUsage::GunSelector => 0x32,
+            GameUsage::GunSingleShot => 0x33,
+            GameUsage::GunBurst => 0x34,
+            GameUsage::GunAutomatic => 0x35,
+            GameUsage::GunSafety => 0x36,
+            GameUsage::GamepadFireJump => 0x37,
+            GameUsage::GamepadTrigger => 0x39,
+            GameUsage::FormFittingGamepad => 0x3A,
+            GameUsage::Reserved(v) => v,
+        }
+    }
+}
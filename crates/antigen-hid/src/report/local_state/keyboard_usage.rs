@@ -0,0 +1,905 @@
+/// Key codes reported by keyboards and keypads
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeyboardUsage {
+    NoEvent,
+    ErrorRollOver,
+    PostFail,
+    ErrorUndefined,
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Key1,
+    Key2,
+    Key3,
+    Key4,
+    Key5,
+    Key6,
+    Key7,
+    Key8,
+    Key9,
+    Key0,
+    Enter,
+    Escape,
+    Backspace,
+    Tab,
+    Space,
+    Minus,
+    Equals,
+    LeftBracket,
+    RightBracket,
+    Backslash,
+    NonUsHash,
+    Semicolon,
+    Apostrophe,
+    Grave,
+    Comma,
+    Period,
+    Slash,
+    CapsLock,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+    Insert,
+    Home,
+    PageUp,
+    Delete,
+    End,
+    PageDown,
+    Right,
+    Left,
+    Down,
+    Up,
+    NumLock,
+    KeypadDivide,
+    KeypadMultiply,
+    KeypadSubtract,
+    KeypadAdd,
+    KeypadEnter,
+    Keypad1,
+    Keypad2,
+    Keypad3,
+    Keypad4,
+    Keypad5,
+    Keypad6,
+    Keypad7,
+    Keypad8,
+    Keypad9,
+    Keypad0,
+    KeypadPeriod,
+    NonUsBackslash,
+    Application,
+    Power,
+    KeypadEquals,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    Execute,
+    Help,
+    Menu,
+    Select,
+    Stop,
+    Again,
+    Undo,
+    Cut,
+    Copy,
+    Paste,
+    Find,
+    Mute,
+    VolumeUp,
+    VolumeDown,
+    LockingCapsLock,
+    LockingNumLock,
+    LockingScrollLock,
+    KeypadComma,
+    KeypadEqualSign,
+    International1,
+    International2,
+    International3,
+    International4,
+    International5,
+    International6,
+    International7,
+    International8,
+    International9,
+    Lang1,
+    Lang2,
+    Lang3,
+    Lang4,
+    Lang5,
+    Lang6,
+    Lang7,
+    Lang8,
+    Lang9,
+    AlternateErase,
+    SysReq,
+    Cancel,
+    Clear,
+    Prior,
+    Return,
+    Separator,
+    Out,
+    Oper,
+    ClearAgain,
+    CrSel,
+    ExSel,
+    Keypad00,
+    Keypad000,
+    ThousandsSeparator,
+    DecimalSeparator,
+    CurrencyUnit,
+    CurrencySubUnit,
+    KeypadLeftParenthesis,
+    KeypadRightParenthesis,
+    KeypadLeftBrace,
+    KeypadRightBrace,
+    KeypadTab,
+    KeypadBackspace,
+    KeypadA,
+    KeypadB,
+    KeypadC,
+    KeypadD,
+    KeypadE,
+    KeypadF,
+    KeypadXor,
+    KeypadCaret,
+    KeypadPercent,
+    KeypadLessThan,
+    KeypadGreaterThan,
+    KeypadAmpersand,
+    KeypadDoubleAmpersand,
+    KeypadBar,
+    KeypadDoubleBar,
+    KeypadColon,
+    KeypadHash,
+    KeypadSpace,
+    KeypadAt,
+    KeypadExclamation,
+    KeypadMemoryStore,
+    KeypadMemoryRecall,
+    KeypadMemoryClear,
+    KeypadMemoryAdd,
+    KeypadMemorySubtract,
+    KeypadMemoryMultiply,
+    KeypadMemoryDivide,
+    KeypadPlusMinus,
+    KeypadClear,
+    KeypadClearEntry,
+    KeypadBinary,
+    KeypadOctal,
+    KeypadDecimal,
+    KeypadHexadecimal,
+    LeftControl,
+    LeftShift,
+    LeftAlt,
+    LeftGui,
+    RightControl,
+    RightShift,
+    RightAlt,
+    RightGui,
+    Reserved(u32),
+}
+
+impl std::fmt::Display for KeyboardUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            KeyboardUsage::NoEvent => "NoEvent",
+            KeyboardUsage::ErrorRollOver => "ErrorRollOver",
+            KeyboardUsage::PostFail => "PostFail",
+            KeyboardUsage::ErrorUndefined => "ErrorUndefined",
+            KeyboardUsage::A => "A",
+            KeyboardUsage::B => "B",
+            KeyboardUsage::C => "C",
+            KeyboardUsage::D => "D",
+            KeyboardUsage::E => "E",
+            KeyboardUsage::F => "F",
+            KeyboardUsage::G => "G",
+            KeyboardUsage::H => "H",
+            KeyboardUsage::I => "I",
+            KeyboardUsage::J => "J",
+            KeyboardUsage::K => "K",
+            KeyboardUsage::L => "L",
+            KeyboardUsage::M => "M",
+            KeyboardUsage::N => "N",
+            KeyboardUsage::O => "O",
+            KeyboardUsage::P => "P",
+            KeyboardUsage::Q => "Q",
+            KeyboardUsage::R => "R",
+            KeyboardUsage::S => "S",
+            KeyboardUsage::T => "T",
+            KeyboardUsage::U => "U",
+            KeyboardUsage::V => "V",
+            KeyboardUsage::W => "W",
+            KeyboardUsage::X => "X",
+            KeyboardUsage::Y => "Y",
+            KeyboardUsage::Z => "Z",
+            KeyboardUsage::Key1 => "1",
+            KeyboardUsage::Key2 => "2",
+            KeyboardUsage::Key3 => "3",
+            KeyboardUsage::Key4 => "4",
+            KeyboardUsage::Key5 => "5",
+            KeyboardUsage::Key6 => "6",
+            KeyboardUsage::Key7 => "7",
+            KeyboardUsage::Key8 => "8",
+            KeyboardUsage::Key9 => "9",
+            KeyboardUsage::Key0 => "0",
+            KeyboardUsage::Enter => "Enter",
+            KeyboardUsage::Escape => "Escape",
+            KeyboardUsage::Backspace => "Backspace",
+            KeyboardUsage::Tab => "Tab",
+            KeyboardUsage::Space => "Space",
+            KeyboardUsage::Minus => "Minus",
+            KeyboardUsage::Equals => "Equals",
+            KeyboardUsage::LeftBracket => "LeftBracket",
+            KeyboardUsage::RightBracket => "RightBracket",
+            KeyboardUsage::Backslash => "Backslash",
+            KeyboardUsage::NonUsHash => "NonUsHash",
+            KeyboardUsage::Semicolon => "Semicolon",
+            KeyboardUsage::Apostrophe => "Apostrophe",
+            KeyboardUsage::Grave => "Grave",
+            KeyboardUsage::Comma => "Comma",
+            KeyboardUsage::Period => "Period",
+            KeyboardUsage::Slash => "Slash",
+            KeyboardUsage::CapsLock => "CapsLock",
+            KeyboardUsage::F1 => "F1",
+            KeyboardUsage::F2 => "F2",
+            KeyboardUsage::F3 => "F3",
+            KeyboardUsage::F4 => "F4",
+            KeyboardUsage::F5 => "F5",
+            KeyboardUsage::F6 => "F6",
+            KeyboardUsage::F7 => "F7",
+            KeyboardUsage::F8 => "F8",
+            KeyboardUsage::F9 => "F9",
+            KeyboardUsage::F10 => "F10",
+            KeyboardUsage::F11 => "F11",
+            KeyboardUsage::F12 => "F12",
+            KeyboardUsage::PrintScreen => "PrintScreen",
+            KeyboardUsage::ScrollLock => "ScrollLock",
+            KeyboardUsage::Pause => "Pause",
+            KeyboardUsage::Insert => "Insert",
+            KeyboardUsage::Home => "Home",
+            KeyboardUsage::PageUp => "PageUp",
+            KeyboardUsage::Delete => "Delete",
+            KeyboardUsage::End => "End",
+            KeyboardUsage::PageDown => "PageDown",
+            KeyboardUsage::Right => "Right",
+            KeyboardUsage::Left => "Left",
+            KeyboardUsage::Down => "Down",
+            KeyboardUsage::Up => "Up",
+            KeyboardUsage::NumLock => "NumLock",
+            KeyboardUsage::KeypadDivide => "KeypadDivide",
+            KeyboardUsage::KeypadMultiply => "KeypadMultiply",
+            KeyboardUsage::KeypadSubtract => "KeypadSubtract",
+            KeyboardUsage::KeypadAdd => "KeypadAdd",
+            KeyboardUsage::KeypadEnter => "KeypadEnter",
+            KeyboardUsage::Keypad1 => "Keypad1",
+            KeyboardUsage::Keypad2 => "Keypad2",
+            KeyboardUsage::Keypad3 => "Keypad3",
+            KeyboardUsage::Keypad4 => "Keypad4",
+            KeyboardUsage::Keypad5 => "Keypad5",
+            KeyboardUsage::Keypad6 => "Keypad6",
+            KeyboardUsage::Keypad7 => "Keypad7",
+            KeyboardUsage::Keypad8 => "Keypad8",
+            KeyboardUsage::Keypad9 => "Keypad9",
+            KeyboardUsage::Keypad0 => "Keypad0",
+            KeyboardUsage::KeypadPeriod => "KeypadPeriod",
+            KeyboardUsage::NonUsBackslash => "NonUsBackslash",
+            KeyboardUsage::Application => "Application",
+            KeyboardUsage::Power => "Power",
+            KeyboardUsage::KeypadEquals => "KeypadEquals",
+            KeyboardUsage::F13 => "F13",
+            KeyboardUsage::F14 => "F14",
+            KeyboardUsage::F15 => "F15",
+            KeyboardUsage::F16 => "F16",
+            KeyboardUsage::F17 => "F17",
+            KeyboardUsage::F18 => "F18",
+            KeyboardUsage::F19 => "F19",
+            KeyboardUsage::F20 => "F20",
+            KeyboardUsage::F21 => "F21",
+            KeyboardUsage::F22 => "F22",
+            KeyboardUsage::F23 => "F23",
+            KeyboardUsage::F24 => "F24",
+            KeyboardUsage::Execute => "Execute",
+            KeyboardUsage::Help => "Help",
+            KeyboardUsage::Menu => "Menu",
+            KeyboardUsage::Select => "Select",
+            KeyboardUsage::Stop => "Stop",
+            KeyboardUsage::Again => "Again",
+            KeyboardUsage::Undo => "Undo",
+            KeyboardUsage::Cut => "Cut",
+            KeyboardUsage::Copy => "Copy",
+            KeyboardUsage::Paste => "Paste",
+            KeyboardUsage::Find => "Find",
+            KeyboardUsage::Mute => "Mute",
+            KeyboardUsage::VolumeUp => "VolumeUp",
+            KeyboardUsage::VolumeDown => "VolumeDown",
+            KeyboardUsage::LockingCapsLock => "LockingCapsLock",
+            KeyboardUsage::LockingNumLock => "LockingNumLock",
+            KeyboardUsage::LockingScrollLock => "LockingScrollLock",
+            KeyboardUsage::KeypadComma => "KeypadComma",
+            KeyboardUsage::KeypadEqualSign => "KeypadEqualSign",
+            KeyboardUsage::International1 => "International1",
+            KeyboardUsage::International2 => "International2",
+            KeyboardUsage::International3 => "International3",
+            KeyboardUsage::International4 => "International4",
+            KeyboardUsage::International5 => "International5",
+            KeyboardUsage::International6 => "International6",
+            KeyboardUsage::International7 => "International7",
+            KeyboardUsage::International8 => "International8",
+            KeyboardUsage::International9 => "International9",
+            KeyboardUsage::Lang1 => "Lang1",
+            KeyboardUsage::Lang2 => "Lang2",
+            KeyboardUsage::Lang3 => "Lang3",
+            KeyboardUsage::Lang4 => "Lang4",
+            KeyboardUsage::Lang5 => "Lang5",
+            KeyboardUsage::Lang6 => "Lang6",
+            KeyboardUsage::Lang7 => "Lang7",
+            KeyboardUsage::Lang8 => "Lang8",
+            KeyboardUsage::Lang9 => "Lang9",
+            KeyboardUsage::AlternateErase => "AlternateErase",
+            KeyboardUsage::SysReq => "SysReq",
+            KeyboardUsage::Cancel => "Cancel",
+            KeyboardUsage::Clear => "Clear",
+            KeyboardUsage::Prior => "Prior",
+            KeyboardUsage::Return => "Return",
+            KeyboardUsage::Separator => "Separator",
+            KeyboardUsage::Out => "Out",
+            KeyboardUsage::Oper => "Oper",
+            KeyboardUsage::ClearAgain => "ClearAgain",
+            KeyboardUsage::CrSel => "CrSel",
+            KeyboardUsage::ExSel => "ExSel",
+            KeyboardUsage::Keypad00 => "Keypad00",
+            KeyboardUsage::Keypad000 => "Keypad000",
+            KeyboardUsage::ThousandsSeparator => "ThousandsSeparator",
+            KeyboardUsage::DecimalSeparator => "DecimalSeparator",
+            KeyboardUsage::CurrencyUnit => "CurrencyUnit",
+            KeyboardUsage::CurrencySubUnit => "CurrencySubUnit",
+            KeyboardUsage::KeypadLeftParenthesis => "KeypadLeftParenthesis",
+            KeyboardUsage::KeypadRightParenthesis => "KeypadRightParenthesis",
+            KeyboardUsage::KeypadLeftBrace => "KeypadLeftBrace",
+            KeyboardUsage::KeypadRightBrace => "KeypadRightBrace",
+            KeyboardUsage::KeypadTab => "KeypadTab",
+            KeyboardUsage::KeypadBackspace => "KeypadBackspace",
+            KeyboardUsage::KeypadA => "KeypadA",
+            KeyboardUsage::KeypadB => "KeypadB",
+            KeyboardUsage::KeypadC => "KeypadC",
+            KeyboardUsage::KeypadD => "KeypadD",
+            KeyboardUsage::KeypadE => "KeypadE",
+            KeyboardUsage::KeypadF => "KeypadF",
+            KeyboardUsage::KeypadXor => "KeypadXor",
+            KeyboardUsage::KeypadCaret => "KeypadCaret",
+            KeyboardUsage::KeypadPercent => "KeypadPercent",
+            KeyboardUsage::KeypadLessThan => "KeypadLessThan",
+            KeyboardUsage::KeypadGreaterThan => "KeypadGreaterThan",
+            KeyboardUsage::KeypadAmpersand => "KeypadAmpersand",
+            KeyboardUsage::KeypadDoubleAmpersand => "KeypadDoubleAmpersand",
+            KeyboardUsage::KeypadBar => "KeypadBar",
+            KeyboardUsage::KeypadDoubleBar => "KeypadDoubleBar",
+            KeyboardUsage::KeypadColon => "KeypadColon",
+            KeyboardUsage::KeypadHash => "KeypadHash",
+            KeyboardUsage::KeypadSpace => "KeypadSpace",
+            KeyboardUsage::KeypadAt => "KeypadAt",
+            KeyboardUsage::KeypadExclamation => "KeypadExclamation",
+            KeyboardUsage::KeypadMemoryStore => "KeypadMemoryStore",
+            KeyboardUsage::KeypadMemoryRecall => "KeypadMemoryRecall",
+            KeyboardUsage::KeypadMemoryClear => "KeypadMemoryClear",
+            KeyboardUsage::KeypadMemoryAdd => "KeypadMemoryAdd",
+            KeyboardUsage::KeypadMemorySubtract => "KeypadMemorySubtract",
+            KeyboardUsage::KeypadMemoryMultiply => "KeypadMemoryMultiply",
+            KeyboardUsage::KeypadMemoryDivide => "KeypadMemoryDivide",
+            KeyboardUsage::KeypadPlusMinus => "KeypadPlusMinus",
+            KeyboardUsage::KeypadClear => "KeypadClear",
+            KeyboardUsage::KeypadClearEntry => "KeypadClearEntry",
+            KeyboardUsage::KeypadBinary => "KeypadBinary",
+            KeyboardUsage::KeypadOctal => "KeypadOctal",
+            KeyboardUsage::KeypadDecimal => "KeypadDecimal",
+            KeyboardUsage::KeypadHexadecimal => "KeypadHexadecimal",
+            KeyboardUsage::LeftControl => "LeftControl",
+            KeyboardUsage::LeftShift => "LeftShift",
+            KeyboardUsage::LeftAlt => "LeftAlt",
+            KeyboardUsage::LeftGui => "LeftGui",
+            KeyboardUsage::RightControl => "RightControl",
+            KeyboardUsage::RightShift => "RightShift",
+            KeyboardUsage::RightAlt => "RightAlt",
+            KeyboardUsage::RightGui => "RightGui",
+            KeyboardUsage::Reserved(v) => return write!(f, "Reserved(0x{:x})", v),
+        })
+    }
+}
+
+impl From<u32> for KeyboardUsage {
+    fn from(v: u32) -> Self {
+        match v {
+            0x00 => KeyboardUsage::NoEvent,
+            0x01 => KeyboardUsage::ErrorRollOver,
+            0x02 => KeyboardUsage::PostFail,
+            0x03 => KeyboardUsage::ErrorUndefined,
+            0x04 => KeyboardUsage::A,
+            0x05 => KeyboardUsage::B,
+            0x06 => KeyboardUsage::C,
+            0x07 => KeyboardUsage::D,
+            0x08 => KeyboardUsage::E,
+            0x09 => KeyboardUsage::F,
+            0x0A => KeyboardUsage::G,
+            0x0B => KeyboardUsage::H,
+            0x0C => KeyboardUsage::I,
+            0x0D => KeyboardUsage::J,
+            0x0E => KeyboardUsage::K,
+            0x0F => KeyboardUsage::L,
+            0x10 => KeyboardUsage::M,
+            0x11 => KeyboardUsage::N,
+            0x12 => KeyboardUsage::O,
+            0x13 => KeyboardUsage::P,
+            0x14 => KeyboardUsage::Q,
+            0x15 => KeyboardUsage::R,
+            0x16 => KeyboardUsage::S,
+            0x17 => KeyboardUsage::T,
+            0x18 => KeyboardUsage::U,
+            0x19 => KeyboardUsage::V,
+            0x1A => KeyboardUsage::W,
+            0x1B => KeyboardUsage::X,
+            0x1C => KeyboardUsage::Y,
+            0x1D => KeyboardUsage::Z,
+            0x1E => KeyboardUsage::Key1,
+            0x1F => KeyboardUsage::Key2,
+            0x20 => KeyboardUsage::Key3,
+            0x21 => KeyboardUsage::Key4,
+            0x22 => KeyboardUsage::Key5,
+            0x23 => KeyboardUsage::Key6,
+            0x24 => KeyboardUsage::Key7,
+            0x25 => KeyboardUsage::Key8,
+            0x26 => KeyboardUsage::Key9,
+            0x27 => KeyboardUsage::Key0,
+            0x28 => KeyboardUsage::Enter,
+            0x29 => KeyboardUsage::Escape,
+            0x2A => KeyboardUsage::Backspace,
+            0x2B => KeyboardUsage::Tab,
+            0x2C => KeyboardUsage::Space,
+            0x2D => KeyboardUsage::Minus,
+            0x2E => KeyboardUsage::Equals,
+            0x2F => KeyboardUsage::LeftBracket,
+            0x30 => KeyboardUsage::RightBracket,
+            0x31 => KeyboardUsage::Backslash,
+            0x32 => KeyboardUsage::NonUsHash,
+            0x33 => KeyboardUsage::Semicolon,
+            0x34 => KeyboardUsage::Apostrophe,
+            0x35 => KeyboardUsage::Grave,
+            0x36 => KeyboardUsage::Comma,
+            0x37 => KeyboardUsage::Period,
+            0x38 => KeyboardUsage::Slash,
+            0x39 => KeyboardUsage::CapsLock,
+            0x3A => KeyboardUsage::F1,
+            0x3B => KeyboardUsage::F2,
+            0x3C => KeyboardUsage::F3,
+            0x3D => KeyboardUsage::F4,
+            0x3E => KeyboardUsage::F5,
+            0x3F => KeyboardUsage::F6,
+            0x40 => KeyboardUsage::F7,
+            0x41 => KeyboardUsage::F8,
+            0x42 => KeyboardUsage::F9,
+            0x43 => KeyboardUsage::F10,
+            0x44 => KeyboardUsage::F11,
+            0x45 => KeyboardUsage::F12,
+            0x46 => KeyboardUsage::PrintScreen,
+            0x47 => KeyboardUsage::ScrollLock,
+            0x48 => KeyboardUsage::Pause,
+            0x49 => KeyboardUsage::Insert,
+            0x4A => KeyboardUsage::Home,
+            0x4B => KeyboardUsage::PageUp,
+            0x4C => KeyboardUsage::Delete,
+            0x4D => KeyboardUsage::End,
+            0x4E => KeyboardUsage::PageDown,
+            0x4F => KeyboardUsage::Right,
+            0x50 => KeyboardUsage::Left,
+            0x51 => KeyboardUsage::Down,
+            0x52 => KeyboardUsage::Up,
+            0x53 => KeyboardUsage::NumLock,
+            0x54 => KeyboardUsage::KeypadDivide,
+            0x55 => KeyboardUsage::KeypadMultiply,
+            0x56 => KeyboardUsage::KeypadSubtract,
+            0x57 => KeyboardUsage::KeypadAdd,
+            0x58 => KeyboardUsage::KeypadEnter,
+            0x59 => KeyboardUsage::Keypad1,
+            0x5A => KeyboardUsage::Keypad2,
+            0x5B => KeyboardUsage::Keypad3,
+            0x5C => KeyboardUsage::Keypad4,
+            0x5D => KeyboardUsage::Keypad5,
+            0x5E => KeyboardUsage::Keypad6,
+            0x5F => KeyboardUsage::Keypad7,
+            0x60 => KeyboardUsage::Keypad8,
+            0x61 => KeyboardUsage::Keypad9,
+            0x62 => KeyboardUsage::Keypad0,
+            0x63 => KeyboardUsage::KeypadPeriod,
+            0x64 => KeyboardUsage::NonUsBackslash,
+            0x65 => KeyboardUsage::Application,
+            0x66 => KeyboardUsage::Power,
+            0x67 => KeyboardUsage::KeypadEquals,
+            0x68 => KeyboardUsage::F13,
+            0x69 => KeyboardUsage::F14,
+            0x6A => KeyboardUsage::F15,
+            0x6B => KeyboardUsage::F16,
+            0x6C => KeyboardUsage::F17,
+            0x6D => KeyboardUsage::F18,
+            0x6E => KeyboardUsage::F19,
+            0x6F => KeyboardUsage::F20,
+            0x70 => KeyboardUsage::F21,
+            0x71 => KeyboardUsage::F22,
+            0x72 => KeyboardUsage::F23,
+            0x73 => KeyboardUsage::F24,
+            0x74 => KeyboardUsage::Execute,
+            0x75 => KeyboardUsage::Help,
+            0x76 => KeyboardUsage::Menu,
+            0x77 => KeyboardUsage::Select,
+            0x78 => KeyboardUsage::Stop,
+            0x79 => KeyboardUsage::Again,
+            0x7A => KeyboardUsage::Undo,
+            0x7B => KeyboardUsage::Cut,
+            0x7C => KeyboardUsage::Copy,
+            0x7D => KeyboardUsage::Paste,
+            0x7E => KeyboardUsage::Find,
+            0x7F => KeyboardUsage::Mute,
+            0x80 => KeyboardUsage::VolumeUp,
+            0x81 => KeyboardUsage::VolumeDown,
+            0x82 => KeyboardUsage::LockingCapsLock,
+            0x83 => KeyboardUsage::LockingNumLock,
+            0x84 => KeyboardUsage::LockingScrollLock,
+            0x85 => KeyboardUsage::KeypadComma,
+            0x86 => KeyboardUsage::KeypadEqualSign,
+            0x87 => KeyboardUsage::International1,
+            0x88 => KeyboardUsage::International2,
+            0x89 => KeyboardUsage::International3,
+            0x8A => KeyboardUsage::International4,
+            0x8B => KeyboardUsage::International5,
+            0x8C => KeyboardUsage::International6,
+            0x8D => KeyboardUsage::International7,
+            0x8E => KeyboardUsage::International8,
+            0x8F => KeyboardUsage::International9,
+            0x90 => KeyboardUsage::Lang1,
+            0x91 => KeyboardUsage::Lang2,
+            0x92 => KeyboardUsage::Lang3,
+            0x93 => KeyboardUsage::Lang4,
+            0x94 => KeyboardUsage::Lang5,
+            0x95 => KeyboardUsage::Lang6,
+            0x96 => KeyboardUsage::Lang7,
+            0x97 => KeyboardUsage::Lang8,
+            0x98 => KeyboardUsage::Lang9,
+            0x99 => KeyboardUsage::AlternateErase,
+            0x9A => KeyboardUsage::SysReq,
+            0x9B => KeyboardUsage::Cancel,
+            0x9C => KeyboardUsage::Clear,
+            0x9D => KeyboardUsage::Prior,
+            0x9E => KeyboardUsage::Return,
+            0x9F => KeyboardUsage::Separator,
+            0xA0 => KeyboardUsage::Out,
+            0xA1 => KeyboardUsage::Oper,
+            0xA2 => KeyboardUsage::ClearAgain,
+            0xA3 => KeyboardUsage::CrSel,
+            0xA4 => KeyboardUsage::ExSel,
+            0xB0 => KeyboardUsage::Keypad00,
+            0xB1 => KeyboardUsage::Keypad000,
+            0xB2 => KeyboardUsage::ThousandsSeparator,
+            0xB3 => KeyboardUsage::DecimalSeparator,
+            0xB4 => KeyboardUsage::CurrencyUnit,
+            0xB5 => KeyboardUsage::CurrencySubUnit,
+            0xB6 => KeyboardUsage::KeypadLeftParenthesis,
+            0xB7 => KeyboardUsage::KeypadRightParenthesis,
+            0xB8 => KeyboardUsage::KeypadLeftBrace,
+            0xB9 => KeyboardUsage::KeypadRightBrace,
+            0xBA => KeyboardUsage::KeypadTab,
+            0xBB => KeyboardUsage::KeypadBackspace,
+            0xBC => KeyboardUsage::KeypadA,
+            0xBD => KeyboardUsage::KeypadB,
+            0xBE => KeyboardUsage::KeypadC,
+            0xBF => KeyboardUsage::KeypadD,
+            0xC0 => KeyboardUsage::KeypadE,
+            0xC1 => KeyboardUsage::KeypadF,
+            0xC2 => KeyboardUsage::KeypadXor,
+            0xC3 => KeyboardUsage::KeypadCaret,
+            0xC4 => KeyboardUsage::KeypadPercent,
+            0xC5 => KeyboardUsage::KeypadLessThan,
+            0xC6 => KeyboardUsage::KeypadGreaterThan,
+            0xC7 => KeyboardUsage::KeypadAmpersand,
+            0xC8 => KeyboardUsage::KeypadDoubleAmpersand,
+            0xC9 => KeyboardUsage::KeypadBar,
+            0xCA => KeyboardUsage::KeypadDoubleBar,
+            0xCB => KeyboardUsage::KeypadColon,
+            0xCC => KeyboardUsage::KeypadHash,
+            0xCD => KeyboardUsage::KeypadSpace,
+            0xCE => KeyboardUsage::KeypadAt,
+            0xCF => KeyboardUsage::KeypadExclamation,
+            0xD0 => KeyboardUsage::KeypadMemoryStore,
+            0xD1 => KeyboardUsage::KeypadMemoryRecall,
+            0xD2 => KeyboardUsage::KeypadMemoryClear,
+            0xD3 => KeyboardUsage::KeypadMemoryAdd,
+            0xD4 => KeyboardUsage::KeypadMemorySubtract,
+            0xD5 => KeyboardUsage::KeypadMemoryMultiply,
+            0xD6 => KeyboardUsage::KeypadMemoryDivide,
+            0xD7 => KeyboardUsage::KeypadPlusMinus,
+            0xD8 => KeyboardUsage::KeypadClear,
+            0xD9 => KeyboardUsage::KeypadClearEntry,
+            0xDA => KeyboardUsage::KeypadBinary,
+            0xDB => KeyboardUsage::KeypadOctal,
+            0xDC => KeyboardUsage::KeypadDecimal,
+            0xDD => KeyboardUsage::KeypadHexadecimal,
+            0xE0 => KeyboardUsage::LeftControl,
+            0xE1 => KeyboardUsage::LeftShift,
+            0xE2 => KeyboardUsage::LeftAlt,
+            0xE3 => KeyboardUsage::LeftGui,
+            0xE4 => KeyboardUsage::RightControl,
+            0xE5 => KeyboardUsage::RightShift,
+            0xE6 => KeyboardUsage::RightAlt,
+            0xE7 => KeyboardUsage::RightGui,
+            _ => KeyboardUsage::Reserved(v),
+        }
+    }
+}
+
+impl From<KeyboardUsage> for u32 {
+    fn from(usage: KeyboardUsage) -> Self {
+        match usage {
+            KeyboardUsage::NoEvent => 0x00,
+            KeyboardUsage::ErrorRollOver => 0x01,
+            KeyboardUsage::PostFail => 0x02,
+            KeyboardUsage::ErrorUndefined => 0x03,
+            KeyboardUsage::A => 0x04,
+            KeyboardUsage::B => 0x05,
+            KeyboardUsage::C => 0x06,
+            KeyboardUsage::D => 0x07,
+            KeyboardUsage::E => 0x08,
+            KeyboardUsage::F => 0x09,
+            KeyboardUsage::G => 0x0A,
+            KeyboardUsage::H => 0x0B,
+            KeyboardUsage::I => 0x0C,
+            KeyboardUsage::J => 0x0D,
+            KeyboardUsage::K => 0x0E,
+            KeyboardUsage::L => 0x0F,
+            KeyboardUsage::M => 0x10,
+            KeyboardUsage::N => 0x11,
+            KeyboardUsage::O => 0x12,
+            KeyboardUsage::P => 0x13,
+            KeyboardUsage::Q => 0x14,
+            KeyboardUsage::R => 0x15,
+            KeyboardUsage::S => 0x16,
+            KeyboardUsage::T => 0x17,
+            KeyboardUsage::U => 0x18,
+            KeyboardUsage::V => 0x19,
+            KeyboardUsage::W => 0x1A,
+            KeyboardUsage::X => 0x1B,
+            KeyboardUsage::Y => 0x1C,
+            KeyboardUsage::Z => 0x1D,
+            KeyboardUsage::Key1 => 0x1E,
+            KeyboardUsage::Key2 => 0x1F,
+            KeyboardUsage::Key3 => 0x20,
+            KeyboardUsage::Key4 => 0x21,
+            KeyboardUsage::Key5 => 0x22,
+            KeyboardUsage::Key6 => 0x23,
+            KeyboardUsage::Key7 => 0x24,
+            KeyboardUsage::Key8 => 0x25,
+            KeyboardUsage::Key9 => 0x26,
+            KeyboardUsage::Key0 => 0x27,
+            KeyboardUsage::Enter => 0x28,
+            KeyboardUsage::Escape => 0x29,
+            KeyboardUsage::Backspace => 0x2A,
+            KeyboardUsage::Tab => 0x2B,
+            KeyboardUsage::Space => 0x2C,
+            KeyboardUsage::Minus => 0x2D,
+            KeyboardUsage::Equals => 0x2E,
+            KeyboardUsage::LeftBracket => 0x2F,
+            KeyboardUsage::RightBracket => 0x30,
+            KeyboardUsage::Backslash => 0x31,
+            KeyboardUsage::NonUsHash => 0x32,
+            KeyboardUsage::Semicolon => 0x33,
+            KeyboardUsage::Apostrophe => 0x34,
+            KeyboardUsage::Grave => 0x35,
+            KeyboardUsage::Comma => 0x36,
+            KeyboardUsage::Period => 0x37,
+            KeyboardUsage::Slash => 0x38,
+            KeyboardUsage::CapsLock => 0x39,
+            KeyboardUsage::F1 => 0x3A,
+            KeyboardUsage::F2 => 0x3B,
+            KeyboardUsage::F3 => 0x3C,
+            KeyboardUsage::F4 => 0x3D,
+            KeyboardUsage::F5 => 0x3E,
+            KeyboardUsage::F6 => 0x3F,
+            KeyboardUsage::F7 => 0x40,
+            KeyboardUsage::F8 => 0x41,
+            KeyboardUsage::F9 => 0x42,
+            KeyboardUsage::F10 => 0x43,
+            KeyboardUsage::F11 => 0x44,
+            KeyboardUsage::F12 => 0x45,
+            KeyboardUsage::PrintScreen => 0x46,
+            KeyboardUsage::ScrollLock => 0x47,
+            KeyboardUsage::Pause => 0x48,
+            KeyboardUsage::Insert => 0x49,
+            KeyboardUsage::Home => 0x4A,
+            KeyboardUsage::PageUp => 0x4B,
+            KeyboardUsage::Delete => 0x4C,
+            KeyboardUsage::End => 0x4D,
+            KeyboardUsage::PageDown => 0x4E,
+            KeyboardUsage::Right => 0x4F,
+            KeyboardUsage::Left => 0x50,
+            KeyboardUsage::Down => 0x51,
+            KeyboardUsage::Up => 0x52,
+            KeyboardUsage::NumLock => 0x53,
+            KeyboardUsage::KeypadDivide => 0x54,
+            KeyboardUsage::KeypadMultiply => 0x55,
+            KeyboardUsage::KeypadSubtract => 0x56,
+            KeyboardUsage::KeypadAdd => 0x57,
+            KeyboardUsage::KeypadEnter => 0x58,
+            KeyboardUsage::Keypad1 => 0x59,
+            KeyboardUsage::Keypad2 => 0x5A,
+            KeyboardUsage::Keypad3 => 0x5B,
+            KeyboardUsage::Keypad4 => 0x5C,
+            KeyboardUsage::Keypad5 => 0x5D,
+            KeyboardUsage::Keypad6 => 0x5E,
+            KeyboardUsage::Keypad7 => 0x5F,
+            KeyboardUsage::Keypad8 => 0x60,
+            KeyboardUsage::Keypad9 => 0x61,
+            KeyboardUsage::Keypad0 => 0x62,
+            KeyboardUsage::KeypadPeriod => 0x63,
+            KeyboardUsage::NonUsBackslash => 0x64,
+            KeyboardUsage::Application => 0x65,
+            KeyboardUsage::Power => 0x66,
+            KeyboardUsage::KeypadEquals => 0x67,
+            KeyboardUsage::F13 => 0x68,
+            KeyboardUsage::F14 => 0x69,
+            KeyboardUsage::F15 => 0x6A,
+            KeyboardUsage::F16 => 0x6B,
+            KeyboardUsage::F17 => 0x6C,
+            KeyboardUsage::F18 => 0x6D,
+            KeyboardUsage::F19 => 0x6E,
+            KeyboardUsage::F20 => 0x6F,
+            KeyboardUsage::F21 => 0x70,
+            KeyboardUsage::F22 => 0x71,
+            KeyboardUsage::F23 => 0x72,
+            KeyboardUsage::F24 => 0x73,
+            KeyboardUsage::Execute => 0x74,
+            KeyboardUsage::Help => 0x75,
+            KeyboardUsage::Menu => 0x76,
+            KeyboardUsage::Select => 0x77,
+            KeyboardUsage::Stop => 0x78,
+            KeyboardUsage::Again => 0x79,
+            KeyboardUsage::Undo => 0x7A,
+            KeyboardUsage::Cut => 0x7B,
+            KeyboardUsage::Copy => 0x7C,
+            KeyboardUsage::Paste => 0x7D,
+            KeyboardUsage::Find => 0x7E,
+            KeyboardUsage::Mute => 0x7F,
+            KeyboardUsage::VolumeUp => 0x80,
+            KeyboardUsage::VolumeDown => 0x81,
+            KeyboardUsage::LockingCapsLock => 0x82,
+            KeyboardUsage::LockingNumLock => 0x83,
+            KeyboardUsage::LockingScrollLock => 0x84,
+            KeyboardUsage::KeypadComma => 0x85,
+            KeyboardUsage::KeypadEqualSign => 0x86,
+            KeyboardUsage::International1 => 0x87,
+            KeyboardUsage::International2 => 0x88,
+            KeyboardUsage::International3 => 0x89,
+            KeyboardUsage::International4 => 0x8A,
+            KeyboardUsage::International5 => 0x8B,
+            KeyboardUsage::International6 => 0x8C,
+            KeyboardUsage::International7 => 0x8D,
+            KeyboardUsage::International8 => 0x8E,
+            KeyboardUsage::International9 => 0x8F,
+            KeyboardUsage::Lang1 => 0x90,
+            KeyboardUsage::Lang2 => 0x91,
+            KeyboardUsage::Lang3 => 0x92,
+            KeyboardUsage::Lang4 => 0x93,
+            KeyboardUsage::Lang5 => 0x94,
+            KeyboardUsage::Lang6 => 0x95,
+            KeyboardUsage::Lang7 => 0x96,
+            KeyboardUsage::Lang8 => 0x97,
+            KeyboardUsage::Lang9 => 0x98,
+            KeyboardUsage::AlternateErase => 0x99,
+            KeyboardUsage::SysReq => 0x9A,
+            KeyboardUsage::Cancel => 0x9B,
+            KeyboardUsage::Clear => 0x9C,
+            KeyboardUsage::Prior => 0x9D,
+            KeyboardUsage::Return => 0x9E,
+            KeyboardUsage::Separator => 0x9F,
+            KeyboardUsage::Out => 0xA0,
+            KeyboardUsage::Oper => 0xA1,
+            KeyboardUsage::ClearAgain => 0xA2,
+            KeyboardUsage::CrSel => 0xA3,
+            KeyboardUsage::ExSel => 0xA4,
+            KeyboardUsage::Keypad00 => 0xB0,
+            KeyboardUsage::Keypad000 => 0xB1,
+            KeyboardUsage::ThousandsSeparator => 0xB2,
+            KeyboardUsage::DecimalSeparator => 0xB3,
+            KeyboardUsage::CurrencyUnit => 0xB4,
+            KeyboardUsage::CurrencySubUnit => 0xB5,
+            KeyboardUsage::KeypadLeftParenthesis => 0xB6,
+            KeyboardUsage::KeypadRightParenthesis => 0xB7,
+            KeyboardUsage::KeypadLeftBrace => 0xB8,
+            KeyboardUsage::KeypadRightBrace => 0xB9,
+            KeyboardUsage::KeypadTab => 0xBA,
+            KeyboardUsage::KeypadBackspace => 0xBB,
+            KeyboardUsage::KeypadA => 0xBC,
+            KeyboardUsage::KeypadB => 0xBD,
+            KeyboardUsage::KeypadC => 0xBE,
+            KeyboardUsage::KeypadD => 0xBF,
+            KeyboardUsage::KeypadE => 0xC0,
+            KeyboardUsage::KeypadF => 0xC1,
+            KeyboardUsage::KeypadXor => 0xC2,
+            KeyboardUsage::KeypadCaret => 0xC3,
+            KeyboardUsage::KeypadPercent => 0xC4,
+            KeyboardUsage::KeypadLessThan => 0xC5,
+            KeyboardUsage::KeypadGreaterThan => 0xC6,
+            KeyboardUsage::KeypadAmpersand => 0xC7,
+            KeyboardUsage::KeypadDoubleAmpersand => 0xC8,
+            KeyboardUsage::KeypadBar => 0xC9,
+            KeyboardUsage::KeypadDoubleBar => 0xCA,
+            KeyboardUsage::KeypadColon => 0xCB,
+            KeyboardUsage::KeypadHash => 0xCC,
+            KeyboardUsage::KeypadSpace => 0xCD,
+            KeyboardUsage::KeypadAt => 0xCE,
+            KeyboardUsage::KeypadExclamation => 0xCF,
+            KeyboardUsage::KeypadMemoryStore => 0xD0,
+            KeyboardUsage::KeypadMemoryRecall => 0xD1,
+            KeyboardUsage::KeypadMemoryClear => 0xD2,
+            KeyboardUsage::KeypadMemoryAdd => 0xD3,
+            KeyboardUsage::KeypadMemorySubtract => 0xD4,
+            KeyboardUsage::KeypadMemoryMultiply => 0xD5,
+            KeyboardUsage::KeypadMemoryDivide => 0xD6,
+            KeyboardUsage::KeypadPlusMinus => 0xD7,
+            KeyboardUsage::KeypadClear => 0xD8,
+            KeyboardUsage::KeypadClearEntry => 0xD9,
+            KeyboardUsage::KeypadBinary => 0xDA,
+            KeyboardUsage::KeypadOctal => 0xDB,
+            KeyboardUsage::KeypadDecimal => 0xDC,
+            KeyboardUsage::KeypadHexadecimal => 0xDD,
+            KeyboardUsage::LeftControl => 0xE0,
+            KeyboardUsage::LeftShift => 0xE1,
+            KeyboardUsage::LeftAlt => 0xE2,
+            KeyboardUsage::LeftGui => 0xE3,
+            KeyboardUsage::RightControl => 0xE4,
+            KeyboardUsage::RightShift => 0xE5,
+            KeyboardUsage::RightAlt => 0xE6,
+            KeyboardUsage::RightGui => 0xE7,
+            KeyboardUsage::Reserved(v) => v,
+        }
+    }
+}
@@ -0,0 +1,341 @@
+/// Indicators on keyboards, consumer devices and telephony equipment
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LedUsage {
+    Undefined,
+    NumLock,
+    CapsLock,
+    ScrollLock,
+    Compose,
+    Kana,
+    Power,
+    Shift,
+    DoNotDisturb,
+    Mute,
+    ToneEnable,
+    HighCutFilter,
+    LowCutFilter,
+    EqualizerEnable,
+    SoundFieldOn,
+    SurroundOn,
+    Repeat,
+    Stereo,
+    SamplingRateDetect,
+    Spinning,
+    Cav,
+    Clv,
+    RecordingFormatDetect,
+    OffHook,
+    Ring,
+    MessageWaiting,
+    DataMode,
+    BatteryOperation,
+    BatteryOk,
+    BatteryLow,
+    Speaker,
+    HeadSet,
+    Hold,
+    Microphone,
+    Coverage,
+    NightMode,
+    SendCalls,
+    CallPickup,
+    Conference,
+    StandBy,
+    CameraOn,
+    CameraOff,
+    OnLine,
+    OffLine,
+    Busy,
+    Ready,
+    PaperOut,
+    PaperJam,
+    Remote,
+    Forward,
+    Reverse,
+    Stop,
+    Rewind,
+    FastForward,
+    Play,
+    Pause,
+    Record,
+    Error,
+    UsageSelectedIndicator,
+    UsageInUseIndicator,
+    UsageMultiModeIndicator,
+    IndicatorOn,
+    IndicatorFlash,
+    IndicatorSlowBlink,
+    IndicatorFastBlink,
+    IndicatorOff,
+    FlashOnTime,
+    SlowBlinkOnTime,
+    SlowBlinkOffTime,
+    FastBlinkOnTime,
+    FastBlinkOffTime,
+    UsageIndicatorColor,
+    IndicatorRed,
+    IndicatorGreen,
+    IndicatorAmber,
+    GenericIndicator,
+    SystemSuspend,
+    ExternalPowerConnected,
+    Reserved(u32),
+}
+
+impl std::fmt::Display for LedUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LedUsage::Undefined => "Undefined",
+            LedUsage::NumLock => "NumLock",
+            LedUsage::CapsLock => "CapsLock",
+            LedUsage::ScrollLock => "ScrollLock",
+            LedUsage::Compose => "Compose",
+            LedUsage::Kana => "Kana",
+            LedUsage::Power => "Power",
+            LedUsage::Shift => "Shift",
+            LedUsage::DoNotDisturb => "DoNotDisturb",
+            LedUsage::Mute => "Mute",
+            LedUsage::ToneEnable => "ToneEnable",
+            LedUsage::HighCutFilter => "HighCutFilter",
+            LedUsage::LowCutFilter => "LowCutFilter",
+            LedUsage::EqualizerEnable => "EqualizerEnable",
+            LedUsage::SoundFieldOn => "SoundFieldOn",
+            LedUsage::SurroundOn => "SurroundOn",
+            LedUsage::Repeat => "Repeat",
+            LedUsage::Stereo => "Stereo",
+            LedUsage::SamplingRateDetect => "SamplingRateDetect",
+            LedUsage::Spinning => "Spinning",
+            LedUsage::Cav => "Cav",
+            LedUsage::Clv => "Clv",
+            LedUsage::RecordingFormatDetect => "RecordingFormatDetect",
+            LedUsage::OffHook => "OffHook",
+            LedUsage::Ring => "Ring",
+            LedUsage::MessageWaiting => "MessageWaiting",
+            LedUsage::DataMode => "DataMode",
+            LedUsage::BatteryOperation => "BatteryOperation",
+            LedUsage::BatteryOk => "BatteryOk",
+            LedUsage::BatteryLow => "BatteryLow",
+            LedUsage::Speaker => "Speaker",
+            LedUsage::HeadSet => "HeadSet",
+            LedUsage::Hold => "Hold",
+            LedUsage::Microphone => "Microphone",
+            LedUsage::Coverage => "Coverage",
+            LedUsage::NightMode => "NightMode",
+            LedUsage::SendCalls => "SendCalls",
+            LedUsage::CallPickup => "CallPickup",
+            LedUsage::Conference => "Conference",
+            LedUsage::StandBy => "StandBy",
+            LedUsage::CameraOn => "CameraOn",
+            LedUsage::CameraOff => "CameraOff",
+            LedUsage::OnLine => "OnLine",
+            LedUsage::OffLine => "OffLine",
+            LedUsage::Busy => "Busy",
+            LedUsage::Ready => "Ready",
+            LedUsage::PaperOut => "PaperOut",
+            LedUsage::PaperJam => "PaperJam",
+            LedUsage::Remote => "Remote",
+            LedUsage::Forward => "Forward",
+            LedUsage::Reverse => "Reverse",
+            LedUsage::Stop => "Stop",
+            LedUsage::Rewind => "Rewind",
+            LedUsage::FastForward => "FastForward",
+            LedUsage::Play => "Play",
+            LedUsage::Pause => "Pause",
+            LedUsage::Record => "Record",
+            LedUsage::Error => "Error",
+            LedUsage::UsageSelectedIndicator => "UsageSelectedIndicator",
+            LedUsage::UsageInUseIndicator => "UsageInUseIndicator",
+            LedUsage::UsageMultiModeIndicator => "UsageMultiModeIndicator",
+            LedUsage::IndicatorOn => "IndicatorOn",
+            LedUsage::IndicatorFlash => "IndicatorFlash",
+            LedUsage::IndicatorSlowBlink => "IndicatorSlowBlink",
+            LedUsage::IndicatorFastBlink => "IndicatorFastBlink",
+            LedUsage::IndicatorOff => "IndicatorOff",
+            LedUsage::FlashOnTime => "FlashOnTime",
+            LedUsage::SlowBlinkOnTime => "SlowBlinkOnTime",
+            LedUsage::SlowBlinkOffTime => "SlowBlinkOffTime",
+            LedUsage::FastBlinkOnTime => "FastBlinkOnTime",
+            LedUsage::FastBlinkOffTime => "FastBlinkOffTime",
+            LedUsage::UsageIndicatorColor => "UsageIndicatorColor",
+            LedUsage::IndicatorRed => "IndicatorRed",
+            LedUsage::IndicatorGreen => "IndicatorGreen",
+            LedUsage::IndicatorAmber => "IndicatorAmber",
+            LedUsage::GenericIndicator => "GenericIndicator",
+            LedUsage::SystemSuspend => "SystemSuspend",
+            LedUsage::ExternalPowerConnected => "ExternalPowerConnected",
+            LedUsage::Reserved(v) => return write!(f, "Reserved(0x{:x})", v),
+        })
+    }
+}
+
+impl From<u32> for LedUsage {
+    fn from(v: u32) -> Self {
+        match v {
+            0x00 => LedUsage::Undefined,
+            0x01 => LedUsage::NumLock,
+            0x02 => LedUsage::CapsLock,
+            0x03 => LedUsage::ScrollLock,
+            0x04 => LedUsage::Compose,
+            0x05 => LedUsage::Kana,
+            0x06 => LedUsage::Power,
+            0x07 => LedUsage::Shift,
+            0x08 => LedUsage::DoNotDisturb,
+            0x09 => LedUsage::Mute,
+            0x0A => LedUsage::ToneEnable,
+            0x0B => LedUsage::HighCutFilter,
+            0x0C => LedUsage::LowCutFilter,
+            0x0D => LedUsage::EqualizerEnable,
+            0x0E => LedUsage::SoundFieldOn,
+            0x0F => LedUsage::SurroundOn,
+            0x10 => LedUsage::Repeat,
+            0x11 => LedUsage::Stereo,
+            0x12 => LedUsage::SamplingRateDetect,
+            0x13 => LedUsage::Spinning,
+            0x14 => LedUsage::Cav,
+            0x15 => LedUsage::Clv,
+            0x16 => LedUsage::RecordingFormatDetect,
+            0x17 => LedUsage::OffHook,
+            0x18 => LedUsage::Ring,
+            0x19 => LedUsage::MessageWaiting,
+            0x1A => LedUsage::DataMode,
+            0x1B => LedUsage::BatteryOperation,
+            0x1C => LedUsage::BatteryOk,
+            0x1D => LedUsage::BatteryLow,
+            0x1E => LedUsage::Speaker,
+            0x1F => LedUsage::HeadSet,
+            0x20 => LedUsage::Hold,
+            0x21 => LedUsage::Microphone,
+            0x22 => LedUsage::Coverage,
+            0x23 => LedUsage::NightMode,
+            0x24 => LedUsage::SendCalls,
+            0x25 => LedUsage::CallPickup,
+            0x26 => LedUsage::Conference,
+            0x27 => LedUsage::StandBy,
+            0x28 => LedUsage::CameraOn,
+            0x29 => LedUsage::CameraOff,
+            0x2A => LedUsage::OnLine,
+            0x2B => LedUsage::OffLine,
+            0x2C => LedUsage::Busy,
+            0x2D => LedUsage::Ready,
+            0x2E => LedUsage::PaperOut,
+            0x2F => LedUsage::PaperJam,
+            0x30 => LedUsage::Remote,
+            0x31 => LedUsage::Forward,
+            0x32 => LedUsage::Reverse,
+            0x33 => LedUsage::Stop,
+            0x34 => LedUsage::Rewind,
+            0x35 => LedUsage::FastForward,
+            0x36 => LedUsage::Play,
+            0x37 => LedUsage::Pause,
+            0x38 => LedUsage::Record,
+            0x39 => LedUsage::Error,
+            0x3A => LedUsage::UsageSelectedIndicator,
+            0x3B => LedUsage::UsageInUseIndicator,
+            0x3C => LedUsage::UsageMultiModeIndicator,
+            0x3D => LedUsage::IndicatorOn,
+            0x3E => LedUsage::IndicatorFlash,
+            0x3F => LedUsage::IndicatorSlowBlink,
+            0x40 => LedUsage::IndicatorFastBlink,
+            0x41 => LedUsage::IndicatorOff,
+            0x42 => LedUsage::FlashOnTime,
+            0x43 => LedUsage::SlowBlinkOnTime,
+            0x44 => LedUsage::SlowBlinkOffTime,
+            0x45 => LedUsage::FastBlinkOnTime,
+            0x46 => LedUsage::FastBlinkOffTime,
+            0x47 => LedUsage::UsageIndicatorColor,
+            0x48 => LedUsage::IndicatorRed,
+            0x49 => LedUsage::IndicatorGreen,
+            0x4A => LedUsage::IndicatorAmber,
+            0x4B => LedUsage::GenericIndicator,
+            0x4C => LedUsage::SystemSuspend,
+            0x4D => LedUsage::ExternalPowerConnected,
+            _ => LedUsage::Reserved(v),
+        }
+    }
+}
+
+impl From<LedUsage> for u32 {
+    fn from(usage: LedUsage) -> Self {
+        match usage {
+            LedUsage::Undefined => 0x00,
+            LedUsage::NumLock => 0x01,
+            LedUsage::CapsLock => 0x02,
+            LedUsage::ScrollLock => 0x03,
+            LedUsage::Compose => 0x04,
+            LedUsage::Kana => 0x05,
+            LedUsage::Power => 0x06,
+            LedUsage::Shift => 0x07,
+            LedUsage::DoNotDisturb => 0x08,
+            LedUsage::Mute => 0x09,
+            LedUsage::ToneEnable => 0x0A,
+            LedUsage::HighCutFilter => 0x0B,
+            LedUsage::LowCutFilter => 0x0C,
+            LedUsage::EqualizerEnable => 0x0D,
+            LedUsage::SoundFieldOn => 0x0E,
+            LedUsage::SurroundOn => 0x0F,
+            LedUsage::Repeat => 0x10,
+            LedUsage::Stereo => 0x11,
+            LedUsage::SamplingRateDetect => 0x12,
+            LedUsage::Spinning => 0x13,
+            LedUsage::Cav => 0x14,
+            LedUsage::Clv => 0x15,
+            LedUsage::RecordingFormatDetect => 0x16,
+            LedUsage::OffHook => 0x17,
+            LedUsage::Ring => 0x18,
+            LedUsage::MessageWaiting => 0x19,
+            LedUsage::DataMode => 0x1A,
+            LedUsage::BatteryOperation => 0x1B,
+            LedUsage::BatteryOk => 0x1C,
+            LedUsage::BatteryLow => 0x1D,
+            LedUsage::Speaker => 0x1E,
+            LedUsage::HeadSet => 0x1F,
+            LedUsage::Hold => 0x20,
+            LedUsage::Microphone => 0x21,
+            LedUsage::Coverage => 0x22,
+            LedUsage::NightMode => 0x23,
+            LedUsage::SendCalls => 0x24,
+            LedUsage::CallPickup => 0x25,
+            LedUsage::Conference => 0x26,
+            LedUsage::StandBy => 0x27,
+            LedUsage::CameraOn => 0x28,
+            LedUsage::CameraOff => 0x29,
+            LedUsage::OnLine => 0x2A,
+            LedUsage::OffLine => 0x2B,
+            LedUsage::Busy => 0x2C,
+            LedUsage::Ready => 0x2D,
+            LedUsage::PaperOut => 0x2E,
+            LedUsage::PaperJam => 0x2F,
+            LedUsage::Remote => 0x30,
+            LedUsage::Forward => 0x31,
+            LedUsage::Reverse => 0x32,
+            LedUsage::Stop => 0x33,
+            LedUsage::Rewind => 0x34,
+            LedUsage::FastForward => 0x35,
+            LedUsage::Play => 0x36,
+            LedUsage::Pause => 0x37,
+            LedUsage::Record => 0x38,
+            LedUsage::Error => 0x39,
+            LedUsage::UsageSelectedIndicator => 0x3A,
+            LedUsage::UsageInUseIndicator => 0x3B,
+            LedUsage::UsageMultiModeIndicator => 0x3C,
+            LedUsage::IndicatorOn => 0x3D,
+            LedUsage::IndicatorFlash => 0x3E,
+            LedUsage::IndicatorSlowBlink => 0x3F,
+            LedUsage::IndicatorFastBlink => 0x40,
+            LedUsage::IndicatorOff => 0x41,
+            LedUsage::FlashOnTime => 0x42,
+            LedUsage::SlowBlinkOnTime => 0x43,
+            LedUsage::SlowBlinkOffTime => 0x44,
+            LedUsage::FastBlinkOnTime => 0x45,
+            LedUsage::FastBlinkOffTime => 0x46,
+            LedUsage::UsageIndicatorColor => 0x47,
+            LedUsage::IndicatorRed => 0x48,
+            LedUsage::IndicatorGreen => 0x49,
+            LedUsage::IndicatorAmber => 0x4A,
+            LedUsage::GenericIndicator => 0x4B,
+            LedUsage::SystemSuspend => 0x4C,
+            LedUsage::ExternalPowerConnected => 0x4D,
+            LedUsage::Reserved(v) => v,
+        }
+    }
+}
@@ -1,6 +1,20 @@
 //! HID Local State
+mod button_usage;
+mod consumer_usage;
+mod digitizer_usage;
+mod game_usage;
+mod keyboard_usage;
+mod led_usage;
+mod simulation_usage;
 mod usage;
 
+pub use button_usage::*;
+pub use consumer_usage::*;
+pub use digitizer_usage::*;
+pub use game_usage::*;
+pub use keyboard_usage::*;
+pub use led_usage::*;
+pub use simulation_usage::*;
 pub use usage::*;
 
 /// Local parse state table
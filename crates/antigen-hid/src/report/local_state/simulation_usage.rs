@@ -0,0 +1,237 @@
+/// Controls found on flight, driving and other simulation peripherals
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SimulationUsage {
+    Undefined,
+    FlightSimulationDevice,
+    AutomobileSimulationDevice,
+    TankSimulationDevice,
+    SpaceshipSimulationDevice,
+    SubmarineSimulationDevice,
+    SailingSimulationDevice,
+    MotorcycleSimulationDevice,
+    SportsSimulationDevice,
+    AirplaneSimulationDevice,
+    HelicopterSimulationDevice,
+    MagicCarpetSimulationDevice,
+    BicycleSimulationDevice,
+    FlightControlStick,
+    FlightStick,
+    CyclicControl,
+    CyclicTrim,
+    FlightYoke,
+    TrackControl,
+    Aileron,
+    AileronTrim,
+    AntiTorqueControl,
+    AutopilotEnable,
+    ChaffRelease,
+    CollectiveControl,
+    DiveBrake,
+    ElectronicCountermeasures,
+    Elevator,
+    ElevatorTrim,
+    Rudder,
+    Throttle,
+    FlightCommunications,
+    FlareRelease,
+    LandingGear,
+    ToeBrake,
+    Trigger,
+    WeaponsArm,
+    WeaponsSelect,
+    WingFlaps,
+    Accelerator,
+    Brake,
+    Clutch,
+    Shifter,
+    Steering,
+    TurretDirection,
+    BarrelElevation,
+    DivePlane,
+    Ballast,
+    BicycleCrank,
+    HandleBars,
+    FrontBrake,
+    RearBrake,
+    Reserved(u32),
+}
+
+impl std::fmt::Display for SimulationUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SimulationUsage::Undefined => "Undefined",
+            SimulationUsage::FlightSimulationDevice => "FlightSimulationDevice",
+            SimulationUsage::AutomobileSimulationDevice => "AutomobileSimulationDevice",
+            SimulationUsage::TankSimulationDevice => "TankSimulationDevice",
+            SimulationUsage::SpaceshipSimulationDevice => "SpaceshipSimulationDevice",
+            SimulationUsage::SubmarineSimulationDevice => "SubmarineSimulationDevice",
+            SimulationUsage::SailingSimulationDevice => "SailingSimulationDevice",
+            SimulationUsage::MotorcycleSimulationDevice => "MotorcycleSimulationDevice",
+            SimulationUsage::SportsSimulationDevice => "SportsSimulationDevice",
+            SimulationUsage::AirplaneSimulationDevice => "AirplaneSimulationDevice",
+            SimulationUsage::HelicopterSimulationDevice => "HelicopterSimulationDevice",
+            SimulationUsage::MagicCarpetSimulationDevice => "MagicCarpetSimulationDevice",
+            SimulationUsage::BicycleSimulationDevice => "BicycleSimulationDevice",
+            SimulationUsage::FlightControlStick => "FlightControlStick",
+            SimulationUsage::FlightStick => "FlightStick",
+            SimulationUsage::CyclicControl => "CyclicControl",
+            SimulationUsage::CyclicTrim => "CyclicTrim",
+            SimulationUsage::FlightYoke => "FlightYoke",
+            SimulationUsage::TrackControl => "TrackControl",
+            SimulationUsage::Aileron => "Aileron",
+            SimulationUsage::AileronTrim => "AileronTrim",
+            SimulationUsage::AntiTorqueControl => "AntiTorqueControl",
+            SimulationUsage::AutopilotEnable => "AutopilotEnable",
+            SimulationUsage::ChaffRelease => "ChaffRelease",
+            SimulationUsage::CollectiveControl => "CollectiveControl",
+            SimulationUsage::DiveBrake => "DiveBrake",
+            SimulationUsage::ElectronicCountermeasures => "ElectronicCountermeasures",
+            SimulationUsage::Elevator => "Elevator",
+            SimulationUsage::ElevatorTrim => "ElevatorTrim",
+            SimulationUsage::Rudder => "Rudder",
+            SimulationUsage::Throttle => "Throttle",
+            SimulationUsage::FlightCommunications => "FlightCommunications",
+            SimulationUsage::FlareRelease => "FlareRelease",
+            SimulationUsage::LandingGear => "LandingGear",
+            SimulationUsage::ToeBrake => "ToeBrake",
+            SimulationUsage::Trigger => "Trigger",
+            SimulationUsage::WeaponsArm => "WeaponsArm",
+            SimulationUsage::WeaponsSelect => "WeaponsSelect",
+            SimulationUsage::WingFlaps => "WingFlaps",
+            SimulationUsage::Accelerator => "Accelerator",
+            SimulationUsage::Brake => "Brake",
+            SimulationUsage::Clutch => "Clutch",
+            SimulationUsage::Shifter => "Shifter",
+            SimulationUsage::Steering => "Steering",
+            SimulationUsage::TurretDirection => "TurretDirection",
+            SimulationUsage::BarrelElevation => "BarrelElevation",
+            SimulationUsage::DivePlane => "DivePlane",
+            SimulationUsage::Ballast => "Ballast",
+            SimulationUsage::BicycleCrank => "BicycleCrank",
+            SimulationUsage::HandleBars => "HandleBars",
+            SimulationUsage::FrontBrake => "FrontBrake",
+            SimulationUsage::RearBrake => "RearBrake",
+            SimulationUsage::Reserved(v) => return write!(f, "Reserved(0x{:x})", v),
+        })
+    }
+}
+
+impl From<u32> for SimulationUsage {
+    fn from(v: u32) -> Self {
+        match v {
+            0x00 => SimulationUsage::Undefined,
+            0x01 => SimulationUsage::FlightSimulationDevice,
+            0x02 => SimulationUsage::AutomobileSimulationDevice,
+            0x03 => SimulationUsage::TankSimulationDevice,
+            0x04 => SimulationUsage::SpaceshipSimulationDevice,
+            0x05 => SimulationUsage::SubmarineSimulationDevice,
+            0x06 => SimulationUsage::SailingSimulationDevice,
+            0x07 => SimulationUsage::MotorcycleSimulationDevice,
+            0x08 => SimulationUsage::SportsSimulationDevice,
+            0x09 => SimulationUsage::AirplaneSimulationDevice,
+            0x0A => SimulationUsage::HelicopterSimulationDevice,
+            0x0B => SimulationUsage::MagicCarpetSimulationDevice,
+            0x0C => SimulationUsage::BicycleSimulationDevice,
+            0x20 => SimulationUsage::FlightControlStick,
+            0x21 => SimulationUsage::FlightStick,
+            0x22 => SimulationUsage::CyclicControl,
+            0x23 => SimulationUsage::CyclicTrim,
+            0x24 => SimulationUsage::FlightYoke,
+            0x25 => SimulationUsage::TrackControl,
+            0xB0 => SimulationUsage::Aileron,
+            0xB1 => SimulationUsage::AileronTrim,
+            0xB2 => SimulationUsage::AntiTorqueControl,
+            0xB3 => SimulationUsage::AutopilotEnable,
+            0xB4 => SimulationUsage::ChaffRelease,
+            0xB5 => SimulationUsage::CollectiveControl,
+            0xB6 => SimulationUsage::DiveBrake,
+            0xB7 => SimulationUsage::ElectronicCountermeasures,
+            0xB8 => SimulationUsage::Elevator,
+            0xB9 => SimulationUsage::ElevatorTrim,
+            0xBA => SimulationUsage::Rudder,
+            0xBB => SimulationUsage::Throttle,
+            0xBC => SimulationUsage::FlightCommunications,
+            0xBD => SimulationUsage::FlareRelease,
+            0xBE => SimulationUsage::LandingGear,
+            0xBF => SimulationUsage::ToeBrake,
+            0xC0 => SimulationUsage::Trigger,
+            0xC1 => SimulationUsage::WeaponsArm,
+            0xC2 => SimulationUsage::WeaponsSelect,
+            0xC3 => SimulationUsage::WingFlaps,
+            0xC4 => SimulationUsage::Accelerator,
+            0xC5 => SimulationUsage::Brake,
+            0xC6 => SimulationUsage::Clutch,
+            0xC7 => SimulationUsage::Shifter,
+            0xC8 => SimulationUsage::Steering,
+            0xC9 => SimulationUsage::TurretDirection,
+            0xCA => SimulationUsage::BarrelElevation,
+            0xCB => SimulationUsage::DivePlane,
+            0xCC => SimulationUsage::Ballast,
+            0xCD => SimulationUsage::BicycleCrank,
+            0xCE => SimulationUsage::HandleBars,
+            0xCF => SimulationUsage::FrontBrake,
+            0xD0 => SimulationUsage::RearBrake,
+            _ => SimulationUsage::Reserved(v),
+        }
+    }
+}
+
+impl From<SimulationUsage> for u32 {
+    fn from(usage: SimulationUsage) -> Self {
+        match usage {
+            SimulationUsage::Undefined => 0x00,
+            SimulationUsage::FlightSimulationDevice => 0x01,
+            SimulationUsage::AutomobileSimulationDevice => 0x02,
+            SimulationUsage::TankSimulationDevice => 0x03,
+            SimulationUsage::SpaceshipSimulationDevice => 0x04,
+            SimulationUsage::SubmarineSimulationDevice => 0x05,
+            SimulationUsage::SailingSimulationDevice => 0x06,
+            SimulationUsage::MotorcycleSimulationDevice => 0x07,
+            SimulationUsage::SportsSimulationDevice => 0x08,
+            SimulationUsage::AirplaneSimulationDevice => 0x09,
+            SimulationUsage::HelicopterSimulationDevice => 0x0A,
+            SimulationUsage::MagicCarpetSimulationDevice => 0x0B,
+            SimulationUsage::BicycleSimulationDevice => 0x0C,
+            SimulationUsage::FlightControlStick => 0x20,
+            SimulationUsage::FlightStick => 0x21,
+            SimulationUsage::CyclicControl => 0x22,
+            SimulationUsage::CyclicTrim => 0x23,
+            SimulationUsage::FlightYoke => 0x24,
+            SimulationUsage::TrackControl => 0x25,
+            SimulationUsage::Aileron => 0xB0,
+            SimulationUsage::AileronTrim => 0xB1,
+            SimulationUsage::AntiTorqueControl => 0xB2,
+            SimulationUsage::AutopilotEnable => 0xB3,
+            SimulationUsage::ChaffRelease => 0xB4,
+            SimulationUsage::CollectiveControl => 0xB5,
+            SimulationUsage::DiveBrake => 0xB6,
+            SimulationUsage::ElectronicCountermeasures => 0xB7,
+            SimulationUsage::Elevator => 0xB8,
+            SimulationUsage::ElevatorTrim => 0xB9,
+            SimulationUsage::Rudder => 0xBA,
+            SimulationUsage::Throttle => 0xBB,
+            SimulationUsage::FlightCommunications => 0xBC,
+            SimulationUsage::FlareRelease => 0xBD,
+            SimulationUsage::LandingGear => 0xBE,
+            SimulationUsage::ToeBrake => 0xBF,
+            SimulationUsage::Trigger => 0xC0,
+            SimulationUsage::WeaponsArm => 0xC1,
+            SimulationUsage::WeaponsSelect => 0xC2,
+            SimulationUsage::WingFlaps => 0xC3,
+            SimulationUsage::Accelerator => 0xC4,
+            SimulationUsage::Brake => 0xC5,
+            SimulationUsage::Clutch => 0xC6,
+            SimulationUsage::Shifter => 0xC7,
+            SimulationUsage::Steering => 0xC8,
+            SimulationUsage::TurretDirection => 0xC9,
+            SimulationUsage::BarrelElevation => 0xCA,
+            SimulationUsage::DivePlane => 0xCB,
+            SimulationUsage::Ballast => 0xCC,
+            SimulationUsage::BicycleCrank => 0xCD,
+            SimulationUsage::HandleBars => 0xCE,
+            SimulationUsage::FrontBrake => 0xCF,
+            SimulationUsage::RearBrake => 0xD0,
+            SimulationUsage::Reserved(v) => v,
+        }
+    }
+}
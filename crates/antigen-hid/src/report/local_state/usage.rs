@@ -1,16 +1,70 @@
+use crate::report::{
+    global_state::UsagePage,
+    local_state::{
+        ButtonUsage, ConsumerUsage, DigitizerUsage, GameUsage, KeyboardUsage, LedUsage,
+        SimulationUsage,
+    },
+};
+
 /// Describes the usage of a report descriptor main item with respect to its usage table
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Usage {
     GenericDesktop(GenericDesktopUsage),
+    Simulation(SimulationUsage),
+    Game(GameUsage),
+    Keyboard(KeyboardUsage),
+    Led(LedUsage),
+    Button(ButtonUsage),
+    Consumer(ConsumerUsage),
+    Digitizer(DigitizerUsage),
     Other(u32),
 }
 
+impl Usage {
+    /// Look up a usage ID within the table for its usage page
+    pub fn new(usage_page: UsagePage, usage: u32) -> Self {
+        match usage_page {
+            UsagePage::GenericDesktop => Usage::GenericDesktop(GenericDesktopUsage::from(usage)),
+            UsagePage::SimulationControls => Usage::Simulation(SimulationUsage::from(usage)),
+            UsagePage::GameControls => Usage::Game(GameUsage::from(usage)),
+            UsagePage::KeyboardKeypad => Usage::Keyboard(KeyboardUsage::from(usage)),
+            UsagePage::Led => Usage::Led(LedUsage::from(usage)),
+            UsagePage::Button => Usage::Button(ButtonUsage::from(usage)),
+            UsagePage::Consumer => Usage::Consumer(ConsumerUsage::from(usage)),
+            UsagePage::Digitizers => Usage::Digitizer(DigitizerUsage::from(usage)),
+            _ => Usage::Other(usage),
+        }
+    }
+
+    /// Raw usage ID within its usage page
+    pub fn id(&self) -> u32 {
+        match *self {
+            Usage::GenericDesktop(usage) => usage.into(),
+            Usage::Simulation(usage) => usage.into(),
+            Usage::Game(usage) => usage.into(),
+            Usage::Keyboard(usage) => usage.into(),
+            Usage::Led(usage) => usage.into(),
+            Usage::Button(usage) => usage.into(),
+            Usage::Consumer(usage) => usage.into(),
+            Usage::Digitizer(usage) => usage.into(),
+            Usage::Other(usage) => usage,
+        }
+    }
+}
+
 impl std::fmt::Debug for Usage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Usage::GenericDesktop(usage) => {
                 f.write_fmt(format_args!("GenericDesktop::{:?}", usage))
             }
+            Usage::Simulation(usage) => f.write_fmt(format_args!("Simulation::{:?}", usage)),
+            Usage::Game(usage) => f.write_fmt(format_args!("Game::{:?}", usage)),
+            Usage::Keyboard(usage) => f.write_fmt(format_args!("Keyboard::{:?}", usage)),
+            Usage::Led(usage) => f.write_fmt(format_args!("Led::{:?}", usage)),
+            Usage::Button(usage) => f.write_fmt(format_args!("Button::{:?}", usage)),
+            Usage::Consumer(usage) => f.write_fmt(format_args!("Consumer::{:?}", usage)),
+            Usage::Digitizer(usage) => f.write_fmt(format_args!("Digitizer::{:?}", usage)),
             Usage::Other(usage) => f.write_fmt(format_args!("Other({:?})", usage)),
         }
     }
@@ -19,9 +73,14 @@ impl std::fmt::Debug for Usage {
 impl std::fmt::Display for Usage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Usage::GenericDesktop(usage) => {
-                f.write_fmt(format_args!("{}", usage))
-            }
+            Usage::GenericDesktop(usage) => usage.fmt(f),
+            Usage::Simulation(usage) => usage.fmt(f),
+            Usage::Game(usage) => usage.fmt(f),
+            Usage::Keyboard(usage) => usage.fmt(f),
+            Usage::Led(usage) => usage.fmt(f),
+            Usage::Button(usage) => usage.fmt(f),
+            Usage::Consumer(usage) => usage.fmt(f),
+            Usage::Digitizer(usage) => usage.fmt(f),
             Usage::Other(usage) => f.write_fmt(format_args!("{}", usage)),
         }
     }
@@ -260,3 +319,81 @@ impl From<u32> for GenericDesktopUsage {
         }
     }
 }
+
+impl From<GenericDesktopUsage> for u32 {
+    fn from(usage: GenericDesktopUsage) -> Self {
+        match usage {
+            GenericDesktopUsage::Undefined => 0x00,
+            GenericDesktopUsage::Pointer => 0x01,
+            GenericDesktopUsage::Mouse => 0x02,
+            GenericDesktopUsage::Joystick => 0x04,
+            GenericDesktopUsage::GamePad => 0x05,
+            GenericDesktopUsage::Keyboard => 0x06,
+            GenericDesktopUsage::Keypad => 0x07,
+            GenericDesktopUsage::MultiAxisController => 0x08,
+            GenericDesktopUsage::TabletPcSystemControls => 0x09,
+            GenericDesktopUsage::X => 0x30,
+            GenericDesktopUsage::Y => 0x31,
+            GenericDesktopUsage::Z => 0x32,
+            GenericDesktopUsage::Rx => 0x33,
+            GenericDesktopUsage::Ry => 0x34,
+            GenericDesktopUsage::Rz => 0x35,
+            GenericDesktopUsage::Slider => 0x36,
+            GenericDesktopUsage::Dial => 0x37,
+            GenericDesktopUsage::Wheel => 0x38,
+            GenericDesktopUsage::HatSwitch => 0x39,
+            GenericDesktopUsage::CountedBuffer => 0x3A,
+            GenericDesktopUsage::ByteCount => 0x3B,
+            GenericDesktopUsage::MotionWakeup => 0x3C,
+            GenericDesktopUsage::Start => 0x3D,
+            GenericDesktopUsage::Select => 0x3E,
+            GenericDesktopUsage::Vx => 0x40,
+            GenericDesktopUsage::Vy => 0x41,
+            GenericDesktopUsage::Vz => 0x42,
+            GenericDesktopUsage::Vbrx => 0x43,
+            GenericDesktopUsage::Vbry => 0x44,
+            GenericDesktopUsage::Vbrz => 0x45,
+            GenericDesktopUsage::Vno => 0x46,
+            GenericDesktopUsage::FeatureNotification => 0x47,
+            GenericDesktopUsage::ResolutionMultiplier => 0x48,
+            GenericDesktopUsage::SystemControl => 0x80,
+            GenericDesktopUsage::SystemPowerDown => 0x81,
+            GenericDesktopUsage::SystemSleep => 0x82,
+            GenericDesktopUsage::SystemWakeUp => 0x83,
+            GenericDesktopUsage::SystemContextMenu => 0x84,
+            GenericDesktopUsage::SystemMainMenu => 0x85,
+            GenericDesktopUsage::SystemAppMenu => 0x86,
+            GenericDesktopUsage::SystemMenuHelp => 0x87,
+            GenericDesktopUsage::SystemMenuExit => 0x88,
+            GenericDesktopUsage::SystemMenuSelect => 0x89,
+            GenericDesktopUsage::SystemMenuRight => 0x8A,
+            GenericDesktopUsage::SystemMenuLeft => 0x8B,
+            GenericDesktopUsage::SystemMenuUp => 0x8C,
+            GenericDesktopUsage::SystemMenuDown => 0x8D,
+            GenericDesktopUsage::SystemColdRestart => 0x8E,
+            GenericDesktopUsage::SystemWarmRestart => 0x8F,
+            GenericDesktopUsage::DpadUp => 0x90,
+            GenericDesktopUsage::DpadDown => 0x91,
+            GenericDesktopUsage::DpadRight => 0x92,
+            GenericDesktopUsage::DpadLeft => 0x93,
+            GenericDesktopUsage::SystemDock => 0xA0,
+            GenericDesktopUsage::SystemUndock => 0xA1,
+            GenericDesktopUsage::SystemSetup => 0xA2,
+            GenericDesktopUsage::SystemBreak => 0xA3,
+            GenericDesktopUsage::SystemDebuggerBreak => 0xA4,
+            GenericDesktopUsage::ApplicationBreak => 0xA5,
+            GenericDesktopUsage::ApplicationDebuggerBreak => 0xA6,
+            GenericDesktopUsage::SystemSpeakerMute => 0xA7,
+            GenericDesktopUsage::SystemHibernate => 0xA8,
+            GenericDesktopUsage::SystemDisplayInvert => 0xB0,
+            GenericDesktopUsage::SystemDisplayInternal => 0xB1,
+            GenericDesktopUsage::SystemDisplayExternal => 0xB2,
+            GenericDesktopUsage::SystemDisplayBoth => 0xB3,
+            GenericDesktopUsage::SystemDisplayDual => 0xB4,
+            GenericDesktopUsage::SystemDisplayToggleIntExt => 0xB5,
+            GenericDesktopUsage::SystemDisplaySwapPrimarySecondary => 0xB6,
+            GenericDesktopUsage::SystemDisplayLcdAutoscale => 0xB7,
+            GenericDesktopUsage::Reserved(v) => v,
+        }
+    }
+}
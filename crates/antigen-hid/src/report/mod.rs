@@ -3,4 +3,5 @@ pub mod global_state;
 pub mod local_state;
 pub mod report_descriptor;
 pub mod input_report;
+pub mod output_report;
 
@@ -0,0 +1,182 @@
+//! HID Output and Feature Reports
+mod write_error;
+
+pub use write_error::*;
+
+use rusb::{DeviceHandle, Direction, Recipient, RequestType, UsbContext};
+use std::time::Duration;
+
+use crate::report::{
+    global_state::UsagePage,
+    local_state::Usage,
+    report_descriptor::{item_data::Constness, DataType, ReportDescriptor, ReportDescriptorItem},
+};
+
+/// HID class request for sending a report over the control endpoint
+const SET_REPORT: u8 = 0x09;
+
+/// Builder for output and feature reports
+///
+/// Values are set by usage, then packed into bytes according to the layout of the
+/// report descriptor's items for the given data type and report ID.
+/// Unset fields and constant padding are sent as zero.
+#[derive(Debug, Clone)]
+pub struct OutputReport {
+    data_type: DataType,
+    report_id: Option<u8>,
+    fields: Vec<(ReportDescriptorItem, i64)>,
+}
+
+impl OutputReport {
+    fn new(report_desc: &ReportDescriptor, data_type: DataType, report_id: Option<u8>) -> Self {
+        let fields = report_desc
+            .iter()
+            .filter(|item| item.data_type == data_type && item.global_state.report_id == report_id)
+            .map(|item| (*item, 0))
+            .collect();
+
+        OutputReport {
+            data_type,
+            report_id,
+            fields,
+        }
+    }
+
+    /// Create an output report, ex. for LEDs or rumble motors
+    ///
+    /// `report_id` should be None for descriptors that don't use report IDs.
+    pub fn output(report_desc: &ReportDescriptor, report_id: Option<u8>) -> Self {
+        OutputReport::new(report_desc, DataType::Output, report_id)
+    }
+
+    /// Create a feature report
+    ///
+    /// `report_id` should be None for descriptors that don't use report IDs.
+    pub fn feature(report_desc: &ReportDescriptor, report_id: Option<u8>) -> Self {
+        OutputReport::new(report_desc, DataType::Feature, report_id)
+    }
+
+    pub fn data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    pub fn report_id(&self) -> Option<u8> {
+        self.report_id
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &ReportDescriptorItem> {
+        self.fields.iter().map(|(item, _)| item)
+    }
+
+    /// Set the value of the first field with the given usage
+    pub fn set(
+        &mut self,
+        usage_page: UsagePage,
+        usage: u32,
+        value: i64,
+    ) -> Result<&mut Self, WriteError> {
+        let target = Usage::new(usage_page, usage);
+        let index = self
+            .fields
+            .iter()
+            .position(|(item, _)| {
+                item.global_state.usage_page == Some(usage_page)
+                    && item.local_state.usage == Some(target)
+            })
+            .ok_or(WriteError::UnknownUsage(usage_page, usage))?;
+
+        self.set_index(index, value)
+    }
+
+    /// Set the value of a field by its position in the report,
+    /// for fields without a usage such as vendor-defined data
+    pub fn set_index(&mut self, index: usize, value: i64) -> Result<&mut Self, WriteError> {
+        let (item, field) = self
+            .fields
+            .get_mut(index)
+            .ok_or(WriteError::InvalidIndex(index))?;
+
+        if item.main_data.constness == Constness::Constant {
+            return Err(WriteError::Constant(index));
+        }
+
+        let global_state = &item.global_state;
+        if let (Some(min), Some(max)) = (global_state.logical_minimum, global_state.logical_maximum)
+        {
+            if value < min as i64 || value > max as i64 {
+                return Err(WriteError::OutOfRange {
+                    value,
+                    min: min as i64,
+                    max: max as i64,
+                });
+            }
+        }
+
+        *field = value;
+        Ok(self)
+    }
+
+    /// Pack the report into bytes, prefixed with the report ID if present
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        if let Some(report_id) = self.report_id {
+            bytes.push(report_id);
+        }
+
+        let offset = bytes.len() * 8;
+        let mut bit = 0;
+        for (item, value) in self.fields.iter() {
+            let size = item.global_state.report_size.unwrap_or(0) as usize
+                * item.global_state.report_count.unwrap_or(0) as usize;
+
+            // Values are written as two's complement, least significant bit first
+            for i in 0..size {
+                let set = i < 64 && (*value >> i) & 1 > 0;
+                let index = offset + bit + i;
+                if index / 8 >= bytes.len() {
+                    bytes.push(0);
+                }
+                if set {
+                    bytes[index / 8] |= 1 << (index % 8);
+                }
+            }
+
+            bit += size;
+        }
+
+        bytes
+    }
+
+    /// Send the report over an interrupt OUT endpoint, returning the number of bytes written
+    pub fn send_interrupt<T: UsbContext>(
+        &self,
+        device_handle: &DeviceHandle<T>,
+        endpoint: u8,
+        timeout: Duration,
+    ) -> Result<usize, rusb::Error> {
+        device_handle.write_interrupt(endpoint, &self.to_bytes(), timeout)
+    }
+
+    /// Send the report with a SET_REPORT control transfer, returning the number of bytes written
+    pub fn send_control<T: UsbContext>(
+        &self,
+        device_handle: &DeviceHandle<T>,
+        interface: u8,
+        timeout: Duration,
+    ) -> Result<usize, rusb::Error> {
+        let report_type: u16 = match self.data_type {
+            DataType::Input => 1,
+            DataType::Output => 2,
+            DataType::Feature => 3,
+        };
+
+        device_handle.write_control(
+            rusb::request_type(Direction::Out, RequestType::Class, Recipient::Interface),
+            SET_REPORT,
+            (report_type << 8) | self.report_id.unwrap_or(0) as u16,
+            interface as u16,
+            &self.to_bytes(),
+            timeout,
+        )
+    }
+}
@@ -0,0 +1,27 @@
+use crate::report::global_state::UsagePage;
+
+/// An error setting a value in an [`OutputReport`](super::OutputReport)
+#[derive(Debug, Copy, Clone)]
+pub enum WriteError {
+    UnknownUsage(UsagePage, u32),
+    InvalidIndex(usize),
+    Constant(usize),
+    OutOfRange { value: i64, min: i64, max: i64 },
+}
+
+impl std::fmt::Display for WriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriteError::UnknownUsage(usage_page, usage) => {
+                write!(f, "No field with usage {:?} 0x{:x}", usage_page, usage)
+            }
+            WriteError::InvalidIndex(index) => write!(f, "No field at index {}", index),
+            WriteError::Constant(index) => write!(f, "Field {} is constant", index),
+            WriteError::OutOfRange { value, min, max } => {
+                write!(f, "Value {} outside logical range {}..={}", value, min, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WriteError {}
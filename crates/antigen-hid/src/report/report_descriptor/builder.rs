@@ -0,0 +1,150 @@
+use crate::{
+    report::{
+        global_state::UsagePage,
+        report_descriptor::{
+            item_data::ItemData,
+            tags::{GlobalTag, ItemTag, ItemTags, LocalTag, MainTag},
+            CollectionType, ReportDescriptor,
+        },
+    },
+    HidError,
+};
+
+/// Assembles a report descriptor item by item
+///
+/// Items are emitted in call order, mirroring the layout of a hand-written descriptor:
+///
+/// ```
+/// # use antigen_hid::report::{
+/// #     global_state::UsagePage,
+/// #     report_descriptor::{item_data::ItemData, CollectionType, ReportDescriptorBuilder},
+/// # };
+/// let bytes = ReportDescriptorBuilder::new()
+///     .usage_page(UsagePage::GenericDesktop)
+///     .usage(0x05)
+///     .collection(CollectionType::Application)
+///     .usage(0x30)
+///     .usage(0x31)
+///     .logical_range(-127, 127)
+///     .report_size(8)
+///     .report_count(2)
+///     .input(ItemData::variable())
+///     .end_collection()
+///     .encode();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ReportDescriptorBuilder(Vec<ItemTag>);
+
+impl ReportDescriptorBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn item(mut self, item: ItemTag) -> Self {
+        self.0.push(item);
+        self
+    }
+
+    fn global(self, global: GlobalTag) -> Self {
+        self.item(ItemTag::Global(global))
+    }
+
+    fn local(self, local: LocalTag) -> Self {
+        self.item(ItemTag::Local(local))
+    }
+
+    fn main(self, main: MainTag) -> Self {
+        self.item(ItemTag::Main(main))
+    }
+
+    pub fn usage_page(self, usage_page: UsagePage) -> Self {
+        self.global(GlobalTag::UsagePage(usage_page))
+    }
+
+    pub fn logical_range(self, min: i32, max: i32) -> Self {
+        self.global(GlobalTag::LogicalMinimum(min))
+            .global(GlobalTag::LogicalMaximum(max))
+    }
+
+    pub fn physical_range(self, min: i32, max: i32) -> Self {
+        self.global(GlobalTag::PhysicalMinimum(min))
+            .global(GlobalTag::PhysicalMaximum(max))
+    }
+
+    pub fn unit(self, unit: u32) -> Self {
+        self.global(GlobalTag::Unit(unit))
+    }
+
+    pub fn unit_exponent(self, unit_exponent: u32) -> Self {
+        self.global(GlobalTag::UnitExponent(unit_exponent))
+    }
+
+    pub fn report_size(self, report_size: u32) -> Self {
+        self.global(GlobalTag::ReportSize(report_size))
+    }
+
+    pub fn report_count(self, report_count: u32) -> Self {
+        self.global(GlobalTag::ReportCount(report_count))
+    }
+
+    pub fn report_id(self, report_id: u8) -> Self {
+        self.global(GlobalTag::ReportId(report_id as u32))
+    }
+
+    pub fn push(self) -> Self {
+        self.global(GlobalTag::Push)
+    }
+
+    pub fn pop(self) -> Self {
+        self.global(GlobalTag::Pop)
+    }
+
+    pub fn usage(self, usage: u32) -> Self {
+        self.local(LocalTag::Usage(usage))
+    }
+
+    pub fn usage_range(self, min: u32, max: u32) -> Self {
+        self.local(LocalTag::UsageMinimum(min))
+            .local(LocalTag::UsageMaximum(max))
+    }
+
+    pub fn collection(self, collection: CollectionType) -> Self {
+        self.main(MainTag::Collection(collection))
+    }
+
+    pub fn end_collection(self) -> Self {
+        self.main(MainTag::EndCollection)
+    }
+
+    pub fn input(self, data: ItemData) -> Self {
+        self.main(MainTag::Input(data))
+    }
+
+    pub fn output(self, data: ItemData) -> Self {
+        self.main(MainTag::Output(data))
+    }
+
+    pub fn feature(self, data: ItemData) -> Self {
+        self.main(MainTag::Feature(data))
+    }
+
+    /// Constant input padding of the given number of bits
+    pub fn input_padding(self, bits: u32) -> Self {
+        self.report_size(bits)
+            .report_count(1)
+            .input(ItemData::constant())
+    }
+
+    pub fn tags(&self) -> ItemTags {
+        ItemTags(self.0.clone())
+    }
+
+    /// Encode into report descriptor bytes
+    pub fn encode(&self) -> Vec<u8> {
+        self.tags().encode()
+    }
+
+    pub fn build(&self) -> Result<ReportDescriptor, HidError> {
+        ReportDescriptor::new(self.encode().into_iter())
+    }
+}
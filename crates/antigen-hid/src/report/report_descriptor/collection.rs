@@ -0,0 +1,33 @@
+use crate::report::{local_state::Usage, report_descriptor::CollectionType};
+
+/// Handle to a [`Collection`] in its [`ReportDescriptor`](super::ReportDescriptor)'s collection table
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CollectionId(u16);
+
+impl CollectionId {
+    pub(crate) fn new(index: usize) -> Self {
+        CollectionId(index as u16)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A collection opened in a report descriptor, grouping related items
+/// such as the axes of one stick
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Collection {
+    pub collection_type: CollectionType,
+    pub usage: Option<Usage>,
+    pub parent: Option<CollectionId>,
+}
+
+impl std::fmt::Display for Collection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.usage {
+            Some(usage) => write!(f, "{} ({:?})", usage, self.collection_type),
+            None => write!(f, "{:?}", self.collection_type),
+        }
+    }
+}
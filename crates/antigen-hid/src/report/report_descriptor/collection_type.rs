@@ -28,3 +28,21 @@ impl From<u8> for CollectionType {
     }
 }
 
+/// Raw collection type
+///
+/// Reserved and vendor-defined collections map back to the first value in their range.
+impl From<CollectionType> for u8 {
+    fn from(collection_type: CollectionType) -> Self {
+        match collection_type {
+            CollectionType::Physical => 0x00,
+            CollectionType::Application => 0x01,
+            CollectionType::Logical => 0x02,
+            CollectionType::Report => 0x03,
+            CollectionType::NamedArray => 0x04,
+            CollectionType::UsageSwitch => 0x05,
+            CollectionType::UsageModifier => 0x06,
+            CollectionType::Reserved => 0x07,
+            CollectionType::VendorDefined => 0x80,
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::report::{
-    global_state::GlobalState, local_state::LocalState, report_descriptor::ItemData,
+    global_state::GlobalState,
+    local_state::LocalState,
+    report_descriptor::{CollectionId, ItemData},
 };
 
 /// Report item wrapping global state, local state, and item data
@@ -9,6 +11,8 @@ pub struct ReportDescriptorItem {
     pub global_state: GlobalState,
     pub local_state: LocalState,
     pub main_data: ItemData,
+    /// Innermost enclosing collection, if any
+    pub collection: Option<CollectionId>,
 }
 
 /// Data item variant
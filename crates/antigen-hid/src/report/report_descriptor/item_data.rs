@@ -15,11 +15,55 @@ pub struct ItemData {
 }
 
 impl ItemData {
+    /// Data, Variable, Absolute; the usual flags for buttons and axes
+    pub fn variable() -> Self {
+        ItemData::from_bits(0x02)
+    }
+
+    /// Data, Variable, Relative; ex. mouse movement
+    pub fn relative() -> Self {
+        ItemData::from_bits(0x06)
+    }
+
+    /// Data, Array, Absolute; ex. keyboard key codes
+    pub fn array() -> Self {
+        ItemData::from_bits(0x00)
+    }
+
+    /// Constant padding
+    pub fn constant() -> Self {
+        ItemData::from_bits(0x01)
+    }
+
+    pub fn from_bits(bits: u16) -> Self {
+        ItemData::new(&bits.to_le_bytes())
+    }
+
+    /// Encode as the flags of an Input / Output / Feature item
+    pub fn bits(&self) -> u16 {
+        let flags = [
+            self.constness == Constness::Constant,
+            self.dimensionality == Dimensionality::Variable,
+            self.absolute_relative == AbsoluteRelative::Relative,
+            self.wrapping == Wrapping::Wrap,
+            self.linearity == Linearity::NonLinear,
+            self.state_preference == StatePreference::NoPreferred,
+            self.nullability == Nullability::NullState,
+            self.volatility == Volatility::Volatile,
+            self.byte_stream == ByteStream::BufferedBytes,
+        ];
+
+        flags
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, flag)| acc | ((*flag as u16) << i))
+    }
+
     pub fn new(data: &[u8]) -> Self {
-        let v = if data.len() == 1 {
-            data[0] as u16
-        } else {
-            u16::from_le_bytes([data[0], data[1]])
+        let v = match data {
+            [] => 0,
+            [v] => *v as u16,
+            [lo, hi, ..] => u16::from_le_bytes([*lo, *hi]),
         };
 
         let constness = if v & 1 == 0 {
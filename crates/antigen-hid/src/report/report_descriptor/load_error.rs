@@ -0,0 +1,59 @@
+use std::path::{Path, PathBuf};
+
+use crate::HidError;
+
+/// An error loading a report descriptor file with [`ReportDescriptorData::load_dir`](super::ReportDescriptorData::load_dir)
+#[derive(Debug)]
+pub enum LoadError {
+    Io(PathBuf, std::io::Error),
+    /// File name doesn't match `VID_xxxx_PID_xxxx`
+    InvalidFileName(PathBuf),
+    /// Hex text file contains something other than hex bytes, at the given line
+    InvalidHex(PathBuf, usize),
+    /// File contents are malformed
+    Hid(PathBuf, HidError),
+    /// File contents don't describe any report items
+    InvalidReportDescriptor(PathBuf),
+}
+
+impl LoadError {
+    pub fn path(&self) -> &Path {
+        match self {
+            LoadError::Io(path, _)
+            | LoadError::InvalidFileName(path)
+            | LoadError::InvalidHex(path, _)
+            | LoadError::Hid(path, _)
+            | LoadError::InvalidReportDescriptor(path) => path,
+        }
+    }
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            LoadError::InvalidFileName(path) => write!(
+                f,
+                "{}: Expected a file name of the form VID_xxxx_PID_xxxx",
+                path.display()
+            ),
+            LoadError::InvalidHex(path, line) => {
+                write!(f, "{}:{}: Invalid hex byte", path.display(), line)
+            }
+            LoadError::Hid(path, e) => write!(f, "{}: {}", path.display(), e),
+            LoadError::InvalidReportDescriptor(path) => {
+                write!(f, "{}: Invalid report descriptor", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(_, e) => Some(e),
+            LoadError::Hid(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -2,24 +2,32 @@
 pub mod item_data;
 pub mod tags;
 
+mod builder;
+mod collection;
 mod collection_type;
 mod item;
+mod load_error;
 mod report_descriptor_data;
+mod report_layout;
 
+pub use builder::*;
+pub use collection::*;
 pub use collection_type::*;
 pub use item::*;
+pub use load_error::*;
 pub use report_descriptor_data::*;
+pub use report_layout::*;
 
 use item_data::*;
 use tags::*;
 
 use rusb::{DeviceHandle, Direction, Recipient, RequestType};
 
-use crate::HidDescriptor;
+use crate::{HidDescriptor, HidError, HidErrorKind};
 
 use super::{
-    global_state::{GlobalState, UsagePage},
-    local_state::{GenericDesktopUsage, LocalState, Usage},
+    global_state::GlobalState,
+    local_state::{LocalState, Usage},
 };
 
 use std::time::Duration;
@@ -36,13 +44,24 @@ use std::time::Duration;
 ///
 /// Thus, report descriptors must be dumped from a device and provided to
 /// the program externally in order to parse a device's output under Windows.
+///
+/// Each item records the innermost collection it belongs to, as a handle into the
+/// descriptor's collection table.
 #[derive(Debug, Clone)]
-pub struct ReportDescriptor(Vec<ReportDescriptorItem>);
+pub struct ReportDescriptor {
+    items: Vec<ReportDescriptorItem>,
+    collections: Vec<Collection>,
+}
+
+/// Upper bound on Report Count, to keep malformed descriptors from exhausting memory
+pub const MAX_REPORT_COUNT: u32 = u16::MAX as u32;
 
 impl ReportDescriptor {
-    pub fn new(report_desc_bytes: impl Iterator<Item = u8>) -> Self {
-        let ItemTags(items) = ItemTags::parse(report_desc_bytes);
-        let mut collection_stack: Vec<CollectionType> = Default::default();
+    pub fn new(report_desc_bytes: impl Iterator<Item = u8>) -> Result<Self, HidError> {
+        let bytes = report_desc_bytes.collect::<Vec<_>>();
+        let items = parse_items(&bytes)?;
+        let mut collections: Vec<Collection> = vec![];
+        let mut collection_stack: Vec<CollectionId> = Default::default();
 
         // Global state
         let mut global_state_stack = vec![GlobalState::default()];
@@ -51,7 +70,14 @@ impl ReportDescriptor {
 
         let mut data: Vec<ReportDescriptorItem> = vec![];
 
-        for item in items {
+        for ParsedItem {
+            item,
+            prefix,
+            offset,
+        } in items
+        {
+            let error = |kind| HidError::new(offset, kind).with_tag(prefix);
+
             match item {
                 ItemTag::Main(main) => {
                     match main {
@@ -59,11 +85,37 @@ impl ReportDescriptor {
                         | MainTag::Output(main_data)
                         | MainTag::Feature(main_data) => {
                             let gs = *global_state_stack.last().unwrap();
-                            let report_count = gs.report_count.unwrap();
+                            if gs.report_size.is_none() {
+                                return Err(error(HidErrorKind::MissingReportSize));
+                            }
+                            let report_count = gs
+                                .report_count
+                                .ok_or_else(|| error(HidErrorKind::MissingReportCount))?;
+                            if report_count > MAX_REPORT_COUNT {
+                                return Err(error(HidErrorKind::ReportCountTooLarge(report_count)));
+                            }
 
-                            for _ in 0..report_count {
+                            for i in 0..report_count {
                                 let usage = match usage_stack.len() {
-                                    0 => None,
+                                    // Variable items spread a usage range across their fields
+                                    0 => match (
+                                        main_data.dimensionality,
+                                        gs.usage_page,
+                                        local_state.usage_minimum,
+                                    ) {
+                                        (
+                                            Dimensionality::Variable,
+                                            Some(usage_page),
+                                            Some(usage_minimum),
+                                        ) => {
+                                            let mut usage = usage_minimum.saturating_add(i);
+                                            if let Some(usage_maximum) = local_state.usage_maximum {
+                                                usage = usage.min(usage_maximum);
+                                            }
+                                            Some(Usage::new(usage_page, usage))
+                                        }
+                                        _ => None,
+                                    },
                                     1 => Some(usage_stack[0]),
                                     _ => Some(usage_stack.remove(0)),
                                 };
@@ -86,14 +138,26 @@ impl ReportDescriptor {
                                     global_state: gs,
                                     local_state: ls,
                                     main_data,
+                                    collection: collection_stack.last().copied(),
                                 })
                             }
                         }
-                        MainTag::Collection(collection) => collection_stack.push(collection),
+                        MainTag::Collection(collection_type) => {
+                            if collections.len() > u16::MAX as usize {
+                                return Err(error(HidErrorKind::TooManyCollections));
+                            }
+                            let id = CollectionId::new(collections.len());
+                            collections.push(Collection {
+                                collection_type,
+                                usage: usage_stack.first().copied(),
+                                parent: collection_stack.last().copied(),
+                            });
+                            collection_stack.push(id);
+                        }
                         MainTag::EndCollection => {
                             collection_stack.pop();
                         }
-                        MainTag::Reserved => (),
+                        MainTag::Reserved(..) => (),
                     }
 
                     local_state = Default::default();
@@ -134,20 +198,17 @@ impl ReportDescriptor {
                         global_state_stack.push(*global_state_stack.last().unwrap());
                     }
                     GlobalTag::Pop => {
+                        if global_state_stack.len() <= 1 {
+                            return Err(error(HidErrorKind::PopWithoutPush));
+                        }
                         global_state_stack.pop();
                     }
-                    GlobalTag::Reserved => (),
+                    GlobalTag::Reserved(..) => (),
                 },
                 ItemTag::Local(local) => match local {
                     LocalTag::Usage(usage) => {
                         if let Some(usage_page) = global_state_stack.last().unwrap().usage_page {
-                            let usage = match usage_page {
-                                UsagePage::GenericDesktop => {
-                                    Usage::GenericDesktop(GenericDesktopUsage::from(usage))
-                                }
-                                _ => Usage::Other(usage),
-                            };
-                            usage_stack.push(usage);
+                            usage_stack.push(Usage::new(usage_page, usage));
                         }
                     }
                     LocalTag::UsageMinimum(usage_min) => {
@@ -175,17 +236,111 @@ impl ReportDescriptor {
                         local_state.string_maximum = Some(string_max)
                     }
                     LocalTag::Delimiter(delimiter) => local_state.delimiter = Some(delimiter),
-                    LocalTag::Reserved(_) => (),
+                    LocalTag::Reserved(..) => (),
                 },
                 ItemTag::Reserved => (),
             }
         }
 
-        ReportDescriptor(data)
+        Ok(ReportDescriptor {
+            items: data,
+            collections,
+        })
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &ReportDescriptorItem> {
-        self.0.iter()
+        self.items.iter()
+    }
+
+    /// Group items into the reports they belong to
+    pub fn layout(&self) -> ReportLayout {
+        ReportLayout::new(self)
+    }
+
+    pub fn collection(&self, id: CollectionId) -> &Collection {
+        &self.collections[id.index()]
+    }
+
+    pub fn collections(&self) -> impl Iterator<Item = (CollectionId, &Collection)> {
+        self.collections
+            .iter()
+            .enumerate()
+            .map(|(i, collection)| (CollectionId::new(i), collection))
+    }
+
+    /// Collections enclosing `id`, from the outermost down to `id` itself
+    pub fn collection_path(&self, id: CollectionId) -> Vec<CollectionId> {
+        let mut path = vec![id];
+        while let Some(parent) = self.collection(*path.last().unwrap()).parent {
+            path.push(parent);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Usages of the collections enclosing `id`, from the outermost down
+    pub fn collection_usages(&self, id: CollectionId) -> Vec<Option<Usage>> {
+        self.collection_path(id)
+            .into_iter()
+            .map(|id| self.collection(id).usage)
+            .collect()
+    }
+
+    /// Whether `id` is `ancestor` or nested anywhere inside it
+    pub fn is_within(&self, id: CollectionId, ancestor: CollectionId) -> bool {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            if id == ancestor {
+                return true;
+            }
+            current = self.collection(id).parent;
+        }
+        false
+    }
+
+    /// Collections whose usage path from the outermost collection matches `usages` exactly
+    ///
+    /// ex. `[Joystick, Pointer]` finds Pointer collections directly inside a top-level Joystick.
+    pub fn find_collections<'a>(
+        &'a self,
+        usages: &'a [Usage],
+    ) -> impl Iterator<Item = CollectionId> + 'a {
+        self.collections().filter_map(move |(id, _)| {
+            let path = self.collection_usages(id);
+            if path.len() == usages.len()
+                && path
+                    .iter()
+                    .zip(usages.iter())
+                    .all(|(usage, target)| *usage == Some(*target))
+            {
+                Some(id)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Items inside a collection, including those in nested collections
+    pub fn items_in(&self, id: CollectionId) -> impl Iterator<Item = &ReportDescriptorItem> {
+        self.items.iter().filter(move |item| match item.collection {
+            Some(collection) => self.is_within(collection, id),
+            None => false,
+        })
+    }
+
+    /// Items inside any collection matching the usage path `usages`,
+    /// including those in nested collections
+    pub fn items_in_path<'a>(
+        &'a self,
+        usages: &'a [Usage],
+    ) -> impl Iterator<Item = &'a ReportDescriptorItem> + 'a {
+        let collections = self.find_collections(usages).collect::<Vec<_>>();
+        self.items.iter().filter(move |item| match item.collection {
+            Some(collection) => collections
+                .iter()
+                .any(|ancestor| self.is_within(collection, *ancestor)),
+            None => false,
+        })
     }
 
     /// Read a [`ReportDescriptor`] from a [`DeviceHandle`]
@@ -206,7 +361,7 @@ impl ReportDescriptor {
         )?;
 
         if len as u16 == hid_desc.wDescriptorLength {
-            Ok(ReportDescriptor::new(buf.iter().copied()))
+            Ok(ReportDescriptor::new(buf.iter().copied())?)
         } else {
             Err(ReadError::InvalidReportDescriptor)
         }
@@ -217,6 +372,7 @@ impl ReportDescriptor {
 #[derive(Debug, Copy, Clone)]
 pub enum ReadError {
     InvalidReportDescriptor,
+    Hid(HidError),
     Rusb(rusb::Error),
 }
 
@@ -224,6 +380,7 @@ impl std::fmt::Display for ReadError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ReadError::InvalidReportDescriptor => f.write_str("Invalid report descriptor"),
+            ReadError::Hid(e) => e.fmt(f),
             ReadError::Rusb(e) => e.fmt(f),
         }
     }
@@ -233,6 +390,7 @@ impl std::error::Error for ReadError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             ReadError::InvalidReportDescriptor => None,
+            ReadError::Hid(e) => Some(e),
             ReadError::Rusb(e) => Some(e),
         }
     }
@@ -243,3 +401,9 @@ impl From<rusb::Error> for ReadError {
         ReadError::Rusb(e)
     }
 }
+
+impl From<HidError> for ReadError {
+    fn from(e: HidError) -> Self {
+        ReadError::Hid(e)
+    }
+}
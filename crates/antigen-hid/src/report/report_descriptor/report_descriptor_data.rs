@@ -1,5 +1,13 @@
 use std::{collections::BTreeMap, path::Path};
 
+use super::{LoadError, ReportDescriptor};
+
+/// Extension of raw report descriptor files, as written by [`ReportDescriptorData::dump`]
+pub const REPORT_DESCRIPTOR_EXTENSION: &str = "hrd";
+
+/// Extension of report descriptor files written as hex text
+pub const REPORT_DESCRIPTOR_HEX_EXTENSION: &str = "hex";
+
 /// A map of VID/PID to Report Descriptor bytes
 ///
 /// Used to provide a report descriptor for known devices
@@ -8,21 +16,134 @@ pub struct ReportDescriptorData(BTreeMap<(u16, u16), Vec<u8>>);
 
 impl ReportDescriptorData {
     pub fn insert(&mut self, vid: u16, pid: u16, report_desc: &[u8]) -> Option<Vec<u8>> {
-        self.0
-            .insert((vid, pid), report_desc.iter().copied().collect())
+        self.0.insert((vid, pid), report_desc.to_vec())
     }
 
     pub fn get(&self, vid: u16, pid: u16) -> Option<&Vec<u8>> {
         self.0.get(&(vid, pid))
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&(u16, u16), &Vec<u8>)> {
+        self.0.iter()
+    }
+
+    /// Add all entries from `other`, replacing existing entries for the same device
+    pub fn merge(&mut self, other: ReportDescriptorData) {
+        self.0.extend(other.0);
+    }
+
     pub fn dump(&self, path: &Path) -> std::io::Result<()> {
         for ((vid, pid), report) in &self.0 {
             std::fs::write(
-                path.join(Path::new(&format!("VID_{:04x}_PID_{:04x}.hrd", vid, pid))),
+                path.join(Path::new(&format!(
+                    "VID_{:04x}_PID_{:04x}.{}",
+                    vid, pid, REPORT_DESCRIPTOR_EXTENSION
+                ))),
                 report,
             )?;
         }
         Ok(())
     }
+
+    /// Load report descriptors from the `VID_xxxx_PID_xxxx` files in a directory
+    ///
+    /// `.hrd` files hold raw descriptor bytes, and `.hex` files hold whitespace or comma-separated
+    /// hex bytes with optional `0x` prefixes and `//` comments. Other files are ignored.
+    ///
+    /// Valid files replace existing entries for the same device, and malformed files are
+    /// returned as errors without affecting the rest. Failing to read the directory itself is
+    /// returned as the outer error.
+    pub fn load_dir(&mut self, path: &Path) -> std::io::Result<Vec<LoadError>> {
+        let mut entries = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort();
+
+        let mut errors = vec![];
+        for path in entries {
+            let hex = match path.extension().and_then(|extension| extension.to_str()) {
+                Some(REPORT_DESCRIPTOR_EXTENSION) => false,
+                Some(REPORT_DESCRIPTOR_HEX_EXTENSION) => true,
+                _ => continue,
+            };
+
+            match load_file(&path, hex) {
+                Ok((vid, pid, report_desc)) => {
+                    self.0.insert((vid, pid), report_desc);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        Ok(errors)
+    }
+}
+
+fn load_file(path: &Path, hex: bool) -> Result<(u16, u16, Vec<u8>), LoadError> {
+    let (vid, pid) = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(parse_file_stem)
+        .ok_or_else(|| LoadError::InvalidFileName(path.to_owned()))?;
+
+    let bytes = std::fs::read(path).map_err(|e| LoadError::Io(path.to_owned(), e))?;
+    let report_desc = if hex {
+        let text = String::from_utf8_lossy(&bytes);
+        parse_hex(&text).map_err(|line| LoadError::InvalidHex(path.to_owned(), line))?
+    } else {
+        bytes
+    };
+
+    let parsed = ReportDescriptor::new(report_desc.iter().copied())
+        .map_err(|e| LoadError::Hid(path.to_owned(), e))?;
+    if parsed.iter().next().is_none() {
+        return Err(LoadError::InvalidReportDescriptor(path.to_owned()));
+    }
+
+    Ok((vid, pid, report_desc))
+}
+
+/// Parse `VID_xxxx_PID_xxxx` into a VID / PID pair
+fn parse_file_stem(stem: &str) -> Option<(u16, u16)> {
+    let mut parts = stem.split('_');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(vid_prefix), Some(vid), Some(pid_prefix), Some(pid))
+            if vid_prefix.eq_ignore_ascii_case("VID") && pid_prefix.eq_ignore_ascii_case("PID") =>
+        {
+            if parts.next().is_some() {
+                return None;
+            }
+            let vid = u16::from_str_radix(vid, 16).ok()?;
+            let pid = u16::from_str_radix(pid, 16).ok()?;
+            Some((vid, pid))
+        }
+        _ => None,
+    }
+}
+
+/// Parse hex text into bytes, returning the 1-based line number of the first invalid token
+fn parse_hex(text: &str) -> Result<Vec<u8>, usize> {
+    let mut bytes = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line = match line.find("//") {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+
+        for token in line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+        {
+            let digits = token
+                .strip_prefix("0x")
+                .or_else(|| token.strip_prefix("0X"))
+                .unwrap_or(token);
+
+            if digits.len() > 2 {
+                return Err(i + 1);
+            }
+            bytes.push(u8::from_str_radix(digits, 16).map_err(|_| i + 1)?);
+        }
+    }
+    Ok(bytes)
 }
@@ -0,0 +1,119 @@
+use super::{DataType, ReportDescriptor, ReportDescriptorItem};
+
+/// The items and size of a single report, identified by data type and report ID
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportFormat {
+    data_type: DataType,
+    report_id: Option<u8>,
+    bits: usize,
+    items: Vec<ReportDescriptorItem>,
+}
+
+impl ReportFormat {
+    pub fn data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    /// Report ID, or None for descriptors that don't use report IDs
+    pub fn report_id(&self) -> Option<u8> {
+        self.report_id
+    }
+
+    /// Number of data bits in the report, excluding the report ID
+    pub fn bit_len(&self) -> usize {
+        self.bits
+    }
+
+    /// Number of bytes in the report, including the report ID byte if present
+    pub fn len(&self) -> usize {
+        let id_len = if self.report_id.is_some() { 1 } else { 0 };
+        id_len + (self.bits + 7) / 8
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Items in the order their fields appear in the report
+    pub fn items(&self) -> impl Iterator<Item = &ReportDescriptorItem> {
+        self.items.iter()
+    }
+}
+
+/// The reports declared by a report descriptor, grouped by data type and report ID
+///
+/// Devices that use report IDs prefix each report with a one-byte ID,
+/// and only the items declared under that ID are present in it.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReportLayout {
+    uses_report_ids: bool,
+    reports: Vec<ReportFormat>,
+}
+
+impl ReportLayout {
+    pub fn new(report_desc: &ReportDescriptor) -> Self {
+        let mut reports: Vec<ReportFormat> = vec![];
+
+        for item in report_desc.iter() {
+            let data_type = item.data_type;
+            let report_id = item.global_state.report_id;
+            let size = (item.global_state.report_size.unwrap_or(0) as usize)
+                .saturating_mul(item.global_state.report_count.unwrap_or(0) as usize);
+
+            match reports
+                .iter_mut()
+                .find(|report| report.data_type == data_type && report.report_id == report_id)
+            {
+                Some(report) => {
+                    report.bits = report.bits.saturating_add(size);
+                    report.items.push(*item);
+                }
+                None => reports.push(ReportFormat {
+                    data_type,
+                    report_id,
+                    bits: size,
+                    items: vec![*item],
+                }),
+            }
+        }
+
+        reports.sort_by_key(|report| (report.data_type, report.report_id));
+
+        ReportLayout {
+            uses_report_ids: reports.iter().any(|report| report.report_id.is_some()),
+            reports,
+        }
+    }
+
+    /// Whether reports are prefixed with a report ID byte
+    pub fn uses_report_ids(&self) -> bool {
+        self.uses_report_ids
+    }
+
+    /// All reports, ordered by data type and report ID
+    pub fn reports(&self) -> impl Iterator<Item = &ReportFormat> {
+        self.reports.iter()
+    }
+
+    /// Reports of the given data type, ordered by report ID
+    pub fn reports_of(&self, data_type: DataType) -> impl Iterator<Item = &ReportFormat> {
+        self.reports
+            .iter()
+            .filter(move |report| report.data_type == data_type)
+    }
+
+    pub fn report(&self, data_type: DataType, report_id: Option<u8>) -> Option<&ReportFormat> {
+        self.reports
+            .iter()
+            .find(|report| report.data_type == data_type && report.report_id == report_id)
+    }
+
+    /// Length in bytes of the largest report of the given data type,
+    /// i.e. the buffer size needed to receive any of them
+    pub fn max_len(&self, data_type: DataType) -> usize {
+        self.reports_of(data_type)
+            .map(ReportFormat::len)
+            .max()
+            .unwrap_or(0)
+    }
+}
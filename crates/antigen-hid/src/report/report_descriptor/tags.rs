@@ -1,19 +1,23 @@
 //! Intermediate representation for parsed HID reports
 //! Used to parse byte arrays into rust types, which are then parsed into a [`ReportDescriptor`]
 
-use crate::report::{
-    global_state::UsagePage,
-    report_descriptor::{CollectionType, ItemData},
+use crate::{
+    report::{
+        global_state::UsagePage,
+        report_descriptor::{CollectionType, ItemData},
+    },
+    HidError, HidErrorKind,
 };
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MainTag {
     Input(ItemData),
     Output(ItemData),
     Feature(ItemData),
     Collection(CollectionType),
     EndCollection,
-    Reserved,
+    /// Reserved tag, holding its tag number and data
+    Reserved(u8, u32),
 }
 
 impl MainTag {
@@ -21,16 +25,39 @@ impl MainTag {
         match v {
             8 => MainTag::Input(ItemData::new(data)),
             9 => MainTag::Output(ItemData::new(data)),
-            10 => MainTag::Collection(CollectionType::from(data[0])),
+            10 => MainTag::Collection(CollectionType::from(unsigned_data(data) as u8)),
             11 => MainTag::Feature(ItemData::new(data)),
             12 => MainTag::EndCollection,
-            13..=16 => MainTag::Reserved,
-            _ => panic!("Can't create MainTag from a >4bit &value"),
+            _ => MainTag::Reserved(v, unsigned_data(data)),
+        }
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        let item_data = |item_data: &ItemData| {
+            let bits = item_data.bits();
+            if bits <= u8::MAX as u16 {
+                vec![bits as u8]
+            } else {
+                bits.to_le_bytes().to_vec()
+            }
+        };
+
+        match self {
+            MainTag::Input(data) => encode_item(out, ItemType::Main, 8, &item_data(data)),
+            MainTag::Output(data) => encode_item(out, ItemType::Main, 9, &item_data(data)),
+            MainTag::Collection(collection) => {
+                encode_item(out, ItemType::Main, 10, &[u8::from(*collection)])
+            }
+            MainTag::Feature(data) => encode_item(out, ItemType::Main, 11, &item_data(data)),
+            MainTag::EndCollection => encode_item(out, ItemType::Main, 12, &[]),
+            MainTag::Reserved(tag, data) => {
+                encode_item(out, ItemType::Main, *tag, &encode_unsigned(*data))
+            }
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum GlobalTag {
     UsagePage(UsagePage),
     LogicalMinimum(i32),
@@ -44,24 +71,14 @@ pub enum GlobalTag {
     ReportCount(u32),
     Push,
     Pop,
-    Reserved,
+    /// Reserved tag, holding its tag number and data
+    Reserved(u8, u32),
 }
 
 impl GlobalTag {
     pub fn new(v: u8, data: &[u8]) -> Self {
-        let data_as_u32 = || match data.len() {
-            1 => u8::from_le_bytes([data[0]]) as u32,
-            2 => u16::from_le_bytes([data[0], data[1]]) as u32,
-            4 => u32::from_le_bytes([data[0], data[1], data[2], data[3]]),
-            _ => panic!("Invalid usage page"),
-        };
-
-        let data_as_i32 = || match data.len() {
-            1 => i8::from_le_bytes([data[0]]) as i32,
-            2 => i16::from_le_bytes([data[0], data[1]]) as i32,
-            4 => i32::from_le_bytes([data[0], data[1], data[2], data[3]]),
-            _ => panic!("Invalid usage page"),
-        };
+        let data_as_u32 = || unsigned_data(data);
+        let data_as_i32 = || signed_data(data);
 
         match v {
             0 => GlobalTag::UsagePage(UsagePage::from(data_as_u32())),
@@ -76,13 +93,31 @@ impl GlobalTag {
             9 => GlobalTag::ReportCount(data_as_u32()),
             10 => GlobalTag::Push,
             11 => GlobalTag::Pop,
-            12..=16 => GlobalTag::Reserved,
-            _ => panic!("Can't create GlobalTag from a >4bit value"),
+            _ => GlobalTag::Reserved(v, data_as_u32()),
         }
     }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        let (tag, data) = match *self {
+            GlobalTag::UsagePage(v) => (0, encode_unsigned(v.into())),
+            GlobalTag::LogicalMinimum(v) => (1, encode_signed(v)),
+            GlobalTag::LogicalMaximum(v) => (2, encode_signed(v)),
+            GlobalTag::PhysicalMinimum(v) => (3, encode_signed(v)),
+            GlobalTag::PhysicalMaximum(v) => (4, encode_signed(v)),
+            GlobalTag::UnitExponent(v) => (5, encode_unsigned(v)),
+            GlobalTag::Unit(v) => (6, encode_unsigned(v)),
+            GlobalTag::ReportSize(v) => (7, encode_unsigned(v)),
+            GlobalTag::ReportId(v) => (8, encode_unsigned(v)),
+            GlobalTag::ReportCount(v) => (9, encode_unsigned(v)),
+            GlobalTag::Push => (10, vec![]),
+            GlobalTag::Pop => (11, vec![]),
+            GlobalTag::Reserved(tag, v) => (tag, encode_unsigned(v)),
+        };
+        encode_item(out, ItemType::Global, tag, &data)
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum LocalTag {
     Usage(u32),
     UsageMinimum(u32),
@@ -94,83 +129,221 @@ pub enum LocalTag {
     StringMinimum(u32),
     StringMaximum(u32),
     Delimiter(u32),
-    Reserved(u32),
+    /// Reserved tag, holding its tag number and data
+    Reserved(u8, u32),
 }
 
 impl LocalTag {
     pub fn new(v: u8, data: &[u8]) -> Self {
+        let data = unsigned_data(data);
         match v {
-            0 => LocalTag::Usage(data[0] as u32),
-            1 => LocalTag::UsageMinimum(data[0] as u32),
-            2 => LocalTag::UsageMaximum(data[0] as u32),
-            3 => LocalTag::DesignatorIndex(data[0] as u32),
-            4 => LocalTag::DesignatorMinimum(data[0] as u32),
-            5 => LocalTag::DesignatorMaximum(data[0] as u32),
-            6 => LocalTag::StringIndex(data[0] as u32),
-            7 => LocalTag::StringMinimum(data[0] as u32),
-            8 => LocalTag::StringMaximum(data[0] as u32),
-            9 => LocalTag::Delimiter(data[0] as u32),
-            10..=16 => LocalTag::Reserved(v as u32),
-            _ => panic!("Can't create LocalTag from a >4bit value"),
+            0 => LocalTag::Usage(data),
+            1 => LocalTag::UsageMinimum(data),
+            2 => LocalTag::UsageMaximum(data),
+            3 => LocalTag::DesignatorIndex(data),
+            4 => LocalTag::DesignatorMinimum(data),
+            5 => LocalTag::DesignatorMaximum(data),
+            6 => LocalTag::StringIndex(data),
+            7 => LocalTag::StringMinimum(data),
+            8 => LocalTag::StringMaximum(data),
+            9 => LocalTag::Delimiter(data),
+            _ => LocalTag::Reserved(v, data),
         }
     }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        let (tag, data) = match *self {
+            LocalTag::Usage(v) => (0, v),
+            LocalTag::UsageMinimum(v) => (1, v),
+            LocalTag::UsageMaximum(v) => (2, v),
+            LocalTag::DesignatorIndex(v) => (3, v),
+            LocalTag::DesignatorMinimum(v) => (4, v),
+            LocalTag::DesignatorMaximum(v) => (5, v),
+            LocalTag::StringIndex(v) => (6, v),
+            LocalTag::StringMinimum(v) => (7, v),
+            LocalTag::StringMaximum(v) => (8, v),
+            LocalTag::Delimiter(v) => (9, v),
+            LocalTag::Reserved(tag, v) => (tag, v),
+        };
+        encode_item(out, ItemType::Local, tag, &encode_unsigned(data))
+    }
+}
+
+/// Little-endian unsigned item data of up to 4 bytes, with empty data as 0
+fn unsigned_data(data: &[u8]) -> u32 {
+    data.iter()
+        .take(4)
+        .enumerate()
+        .fold(0, |acc, (i, byte)| acc | (*byte as u32) << (i * 8))
+}
+
+/// Little-endian two's complement item data of up to 4 bytes, with empty data as 0
+fn signed_data(data: &[u8]) -> i32 {
+    match data.len() {
+        0 => 0,
+        1 => data[0] as i8 as i32,
+        2 => i16::from_le_bytes([data[0], data[1]]) as i32,
+        _ => unsigned_data(data) as i32,
+    }
+}
+
+/// Smallest item data representation of an unsigned value
+///
+/// Always at least one byte, even though the parser reads empty data as 0.
+fn encode_unsigned(v: u32) -> Vec<u8> {
+    if v <= u8::MAX as u32 {
+        vec![v as u8]
+    } else if v <= u16::MAX as u32 {
+        (v as u16).to_le_bytes().to_vec()
+    } else {
+        v.to_le_bytes().to_vec()
+    }
+}
+
+/// Smallest item data representation of a signed value
+fn encode_signed(v: i32) -> Vec<u8> {
+    if v >= i8::MIN as i32 && v <= i8::MAX as i32 {
+        vec![v as u8]
+    } else if v >= i16::MIN as i32 && v <= i16::MAX as i32 {
+        (v as i16).to_le_bytes().to_vec()
+    } else {
+        v.to_le_bytes().to_vec()
+    }
+}
+
+/// Write a short item prefix followed by its data
+fn encode_item(out: &mut Vec<u8>, item_type: ItemType, tag: u8, data: &[u8]) {
+    let size = match data.len() {
+        0 => 0,
+        1 => 1,
+        2 => 2,
+        4 => 3,
+        _ => panic!("Invalid item data length"),
+    };
+
+    out.push((tag << 4) | (u8::from(item_type) << 2) | size);
+    out.extend_from_slice(data);
 }
 
 /// An item parsed from a report descriptor
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ItemTag {
     Main(MainTag),
     Global(GlobalTag),
     Local(LocalTag),
+    /// Long item or short item of the reserved type, whose tag and data aren't kept
     Reserved,
 }
 
+/// Prefix byte introducing a long item, whose data is skipped
+const LONG_ITEM_PREFIX: u8 = 0xFE;
+
 impl ItemTag {
-    pub fn parse(mut report: impl Iterator<Item = u8>) -> Option<ItemTag> {
-        let byte = report.next()?;
+    /// Parse a single item from the start of `bytes`,
+    /// returning it with its prefix byte and encoded length
+    ///
+    /// Long items aren't interpreted, and parse as [`ItemTag::Reserved`].
+    pub fn parse(bytes: &[u8]) -> Result<Option<(ItemTag, u8, usize)>, HidErrorKind> {
+        let byte = match bytes.first() {
+            Some(byte) => *byte,
+            None => return Ok(None),
+        };
+
+        if byte == LONG_ITEM_PREFIX {
+            let data_size = *bytes.get(1).ok_or(HidErrorKind::UnexpectedEnd)? as usize;
+            let len = 3 + data_size;
+            if bytes.len() < len {
+                return Err(HidErrorKind::UnexpectedEnd);
+            }
+            return Ok(Some((ItemTag::Reserved, byte, len)));
+        }
 
         let size = byte & 0b00000011;
         let item_type = ItemType::from((byte >> 2) & 0b00000011);
         let tag = (byte >> 4) & 0b00001111;
 
-        let bytes = match size {
+        let data_len = match size {
             0 => 0,
             1 => 1,
             2 => 2,
-            3 => 4,
-            _ => panic!("Size value exceeds 2bits"),
+            _ => 4,
         };
 
-        let mut data = vec![];
-        for _ in 0..bytes {
-            data.push(report.next().expect("Missing data bytes"));
-        }
+        let data = bytes
+            .get(1..1 + data_len)
+            .ok_or(HidErrorKind::UnexpectedEnd)?;
 
         let item_tag = match item_type {
-            ItemType::Main => ItemTag::Main(MainTag::new(tag, &data)),
-            ItemType::Global => ItemTag::Global(GlobalTag::new(tag, &data)),
-            ItemType::Local => ItemTag::Local(LocalTag::new(tag, &data)),
+            ItemType::Main => ItemTag::Main(MainTag::new(tag, data)),
+            ItemType::Global => ItemTag::Global(GlobalTag::new(tag, data)),
+            ItemType::Local => ItemTag::Local(LocalTag::new(tag, data)),
             ItemType::Reserved => ItemTag::Reserved,
         };
 
-        Some(item_tag)
+        Ok(Some((item_tag, byte, 1 + data_len)))
+    }
+
+    /// Encode as a short item, appending the bytes to `out`
+    ///
+    /// Data is written using the smallest size that holds it,
+    /// so encoding a parsed item may not reproduce the original bytes exactly.
+    /// [`ItemTag::Reserved`] encodes as an empty reserved-type item, dropping the original data.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            ItemTag::Main(main) => main.encode(out),
+            ItemTag::Global(global) => global.encode(out),
+            ItemTag::Local(local) => local.encode(out),
+            ItemTag::Reserved => encode_item(out, ItemType::Reserved, 0, &[]),
+        }
     }
 }
 
 /// A set of item tags parsed from a byte array representing a report descriptor
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct ItemTags(pub Vec<ItemTag>);
 
 impl ItemTags {
-    pub fn parse(mut report: impl Iterator<Item = u8>) -> ItemTags {
-        let mut items = vec![];
-        while let Some(item) = ItemTag::parse(&mut report) {
-            items.push(item);
+    pub fn parse(report: impl Iterator<Item = u8>) -> Result<ItemTags, HidError> {
+        let bytes = report.collect::<Vec<_>>();
+        let items = parse_items(&bytes)?;
+        Ok(ItemTags(items.into_iter().map(|item| item.item).collect()))
+    }
+
+    /// Encode into report descriptor bytes
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = vec![];
+        for item in self.0.iter() {
+            item.encode(&mut out);
         }
-        ItemTags(items)
+        out
     }
 }
 
+/// An item along with its location in the report descriptor
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct ParsedItem {
+    pub item: ItemTag,
+    pub prefix: u8,
+    pub offset: usize,
+}
+
+/// Parse every item in a report descriptor, recording the offset and prefix byte of each
+pub(crate) fn parse_items(bytes: &[u8]) -> Result<Vec<ParsedItem>, HidError> {
+    let mut items = vec![];
+    let mut offset = 0;
+    while let Some((item, prefix, len)) = ItemTag::parse(&bytes[offset..])
+        .map_err(|kind| HidError::new(offset, kind).with_tag(bytes[offset]))?
+    {
+        items.push(ParsedItem {
+            item,
+            prefix,
+            offset,
+        });
+        offset += len;
+    }
+    Ok(items)
+}
+
 /// ItemTag variants
 #[derive(Debug, Copy, Clone)]
 pub enum ItemType {
@@ -191,3 +364,14 @@ impl From<u8> for ItemType {
         }
     }
 }
+
+impl From<ItemType> for u8 {
+    fn from(item_type: ItemType) -> Self {
+        match item_type {
+            ItemType::Main => 0,
+            ItemType::Global => 1,
+            ItemType::Local => 2,
+            ItemType::Reserved => 3,
+        }
+    }
+}
@@ -0,0 +1,1030 @@
+//! Fuzz-style corpus tests: malformed input must produce errors, never panics,
+//! round trip tests over real device descriptors, value decoding tests,
+//! and change tracking tests over parsed reports
+
+use crate::{
+    devices::parse_input_report,
+    fallbacks::{
+        DUALSHOCK_4_REPORT, SATURN_PAD_DINPUT_REPORT, SATURN_PAD_XINPUT_REPORT, T16000M_REPORT,
+        TFRP_REPORT, TWCS_REPORT,
+    },
+    polling::{ChangeTracker, InputEvent},
+    report::{
+        global_state::UsagePage,
+        input_report::{HatDirection, InputValue, ReportValue},
+        local_state::{GenericDesktopUsage, Usage},
+        output_report::{OutputReport, WriteError},
+        report_descriptor::{
+            item_data::ItemData,
+            tags::{GlobalTag, ItemTag, ItemTags, LocalTag, MainTag},
+            CollectionType, DataType, LoadError, ReportDescriptor, ReportDescriptorBuilder,
+            ReportDescriptorData, ReportDescriptorItem,
+        },
+    },
+    HidDescriptor, HidErrorKind,
+};
+
+/// Deterministic xorshift generator, so failures are reproducible
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
+
+fn gamepad() -> Vec<u8> {
+    ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x05)
+        .collection(CollectionType::Application)
+        .report_id(1)
+        .usage_page(UsagePage::Button)
+        .usage_range(1, 12)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(12)
+        .input(ItemData::variable())
+        .input_padding(4)
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x39)
+        .logical_range(0, 7)
+        .physical_range(0, 315)
+        .unit(0x14)
+        .report_size(4)
+        .report_count(1)
+        .input(ItemData::variable())
+        .input_padding(4)
+        .push()
+        .usage(0x30)
+        .usage(0x31)
+        .logical_range(-32768, 32767)
+        .report_size(16)
+        .report_count(2)
+        .input(ItemData::variable())
+        .pop()
+        .usage_page(UsagePage::Button)
+        .usage_range(1, 8)
+        .logical_range(0, 8)
+        .report_size(8)
+        .report_count(2)
+        .input(ItemData::array())
+        .report_id(2)
+        .usage_page(UsagePage::Led)
+        .usage_range(1, 5)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(5)
+        .output(ItemData::variable())
+        .end_collection()
+        .encode()
+}
+
+/// Run every parser over the given descriptor bytes
+fn exercise(rng: &mut Rng, bytes: &[u8]) {
+    let _ = HidDescriptor::parse(bytes.iter().copied());
+
+    if let Ok(tags) = ItemTags::parse(bytes.iter().copied()) {
+        let encoded = tags.encode();
+        assert_eq!(ItemTags::parse(encoded.iter().copied()).as_ref(), Ok(&tags));
+    }
+
+    if let Ok(report_desc) = ReportDescriptor::new(bytes.iter().copied()) {
+        for len in 0..12 {
+            let input = rng.bytes(len);
+            let _ = parse_input_report(&report_desc, input.iter().copied());
+        }
+    }
+}
+
+#[test]
+fn test_malformed_corpus() {
+    let corpus: &[(&[u8], Option<HidErrorKind>)] = &[
+        (&[], None),
+        // Item data cut short
+        (&[0x06, 0x00], Some(HidErrorKind::UnexpectedEnd)),
+        (&[0x27, 0xff, 0xff], Some(HidErrorKind::UnexpectedEnd)),
+        // Long item header with no data
+        (&[0xfe], Some(HidErrorKind::UnexpectedEnd)),
+        // Main items without a report size or count
+        (&[0x81, 0x02], Some(HidErrorKind::MissingReportSize)),
+        (
+            &[0x75, 0x08, 0x81, 0x02],
+            Some(HidErrorKind::MissingReportCount),
+        ),
+        // Pop without push
+        (&[0xb4], Some(HidErrorKind::PopWithoutPush)),
+        (&[0xa4, 0xb4, 0xb4], Some(HidErrorKind::PopWithoutPush)),
+        // Report count of u32::MAX
+        (
+            &[0x97, 0xff, 0xff, 0xff, 0xff, 0x75, 0x08, 0x81, 0x02],
+            Some(HidErrorKind::ReportCountTooLarge(u32::MAX)),
+        ),
+        // Reserved main, global and local tags
+        (&[0x01, 0xf1, 0xc5, 0x00, 0xf9, 0x00], None),
+        // Unbalanced collections
+        (&[0xc0, 0xc0, 0xa1, 0x01], None),
+        // Usage range with maximum below minimum, and minimum at u32::MAX
+        (
+            &[
+                0x1b, 0xff, 0xff, 0xff, 0xff, 0x29, 0x01, 0x75, 0x01, 0x95, 0x04, 0x81, 0x02,
+            ],
+            None,
+        ),
+    ];
+
+    for (bytes, expected) in corpus {
+        let result = ReportDescriptor::new(bytes.iter().copied());
+        assert_eq!(
+            result.err().map(|e| e.kind()),
+            *expected,
+            "Unexpected result for {:02x?}",
+            bytes
+        );
+    }
+
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for (bytes, _) in corpus {
+        exercise(&mut rng, bytes);
+    }
+}
+
+#[test]
+fn test_reserved_tags() {
+    // Reserved main, global and local tags keep their tag numbers and data
+    let bytes = [0xd1, 0x07, 0xf5, 0x12, 0xc9, 0x2a, 0xfa, 0x34, 0x12];
+    let tags = ItemTags::parse(bytes.iter().copied()).unwrap();
+    assert_eq!(
+        tags.0,
+        [
+            ItemTag::Main(MainTag::Reserved(13, 0x07)),
+            ItemTag::Global(GlobalTag::Reserved(15, 0x12)),
+            ItemTag::Local(LocalTag::Reserved(12, 0x2a)),
+            ItemTag::Local(LocalTag::Reserved(15, 0x1234)),
+        ]
+    );
+    assert_eq!(tags.encode(), bytes);
+
+    // Long items are skipped over, and only encode as an empty reserved item
+    let bytes = [0xfe, 0x02, 0x10, 0xaa, 0xbb, 0x05, 0x01];
+    let tags = ItemTags::parse(bytes.iter().copied()).unwrap();
+    assert_eq!(tags.0[0], ItemTag::Reserved);
+    assert_eq!(tags.encode(), [0x0c, 0x05, 0x01]);
+}
+
+#[test]
+fn test_error_location() {
+    let error = ReportDescriptor::new([0x05, 0x01, 0xa4, 0xb4, 0xb4].iter().copied()).unwrap_err();
+    assert_eq!(error.kind(), HidErrorKind::PopWithoutPush);
+    assert_eq!(error.offset(), 4);
+    assert_eq!(error.tag(), Some(0xb4));
+}
+
+#[test]
+fn test_truncated_descriptors() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    let bytes = gamepad();
+    assert!(ReportDescriptor::new(bytes.iter().copied()).is_ok());
+
+    for len in 0..bytes.len() {
+        exercise(&mut rng, &bytes[..len]);
+    }
+}
+
+#[test]
+fn test_mutated_descriptors() {
+    let mut rng = Rng(0xd1b5_4a32_d192_ed03);
+    let bytes = gamepad();
+
+    for _ in 0..5000 {
+        let mut mutated = bytes.clone();
+        for _ in 0..1 + rng.below(4) {
+            let i = rng.below(mutated.len());
+            match rng.below(3) {
+                0 => mutated[i] ^= 1 << rng.below(8),
+                1 => mutated[i] = rng.next() as u8,
+                _ => {
+                    mutated.remove(i);
+                }
+            }
+        }
+        exercise(&mut rng, &mutated);
+    }
+}
+
+#[test]
+fn test_random_descriptors() {
+    let mut rng = Rng(0x8cb9_2ba7_2f3d_8dd7);
+
+    for _ in 0..5000 {
+        let len = rng.below(64);
+        let bytes = rng.bytes(len);
+        exercise(&mut rng, &bytes);
+    }
+}
+
+/// Encoding the parsed item tags and parsing them again should reproduce the same items
+#[test]
+fn test_round_trip() {
+    let reports: [&[u8]; 6] = [
+        &T16000M_REPORT,
+        &TWCS_REPORT,
+        &TFRP_REPORT,
+        &DUALSHOCK_4_REPORT,
+        &SATURN_PAD_DINPUT_REPORT,
+        &SATURN_PAD_XINPUT_REPORT,
+    ];
+
+    for report in reports.iter() {
+        let tags = ItemTags::parse(report.iter().copied()).unwrap();
+        let encoded = tags.encode();
+        assert_eq!(ItemTags::parse(encoded.iter().copied()).unwrap(), tags);
+
+        let report_desc = ReportDescriptor::new(report.iter().copied()).unwrap();
+        let encoded_desc = ReportDescriptor::new(encoded.iter().copied()).unwrap();
+        assert!(report_desc.iter().eq(encoded_desc.iter()));
+    }
+}
+
+#[test]
+fn test_builder_t16000m_buttons() {
+    // First items of the T.16000M descriptor, which use minimal data sizes throughout
+    let encoded = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x04)
+        .collection(CollectionType::Application)
+        .usage_page(UsagePage::Button)
+        .usage_range(0x01, 0x10)
+        .logical_range(0, 1)
+        .physical_range(0, 1)
+        .report_size(1)
+        .report_count(16)
+        .input(ItemData::variable())
+        .encode();
+
+    assert_eq!(encoded[..], T16000M_REPORT[..encoded.len()]);
+}
+
+#[test]
+fn test_builder_virtual_mouse() {
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x02)
+        .collection(CollectionType::Application)
+        .usage(0x01)
+        .collection(CollectionType::Physical)
+        .usage_page(UsagePage::Button)
+        .usage_range(1, 3)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(3)
+        .input(ItemData::variable())
+        .input_padding(5)
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x30)
+        .usage(0x31)
+        .logical_range(-127, 127)
+        .report_size(8)
+        .report_count(2)
+        .input(ItemData::relative())
+        .end_collection()
+        .end_collection()
+        .build()
+        .unwrap();
+
+    let input = [0b0000_0101, 0xFE, 0x03];
+    let device_report = parse_input_report(&report_desc, input.iter().copied()).unwrap();
+
+    let buttons = device_report
+        .iter()
+        .filter(|value| value.data_item().global_state.usage_page == Some(UsagePage::Button))
+        .map(|value| value.report_value().as_i64())
+        .collect::<Vec<_>>();
+    assert_eq!(buttons, [1, 0, 1]);
+
+    let axis = |usage| {
+        device_report
+            .iter()
+            .find(|value| value.data_item().local_state.usage == Some(Usage::GenericDesktop(usage)))
+            .map(|value| *value.report_value())
+            .expect("Missing axis")
+    };
+
+    assert!(matches!(axis(GenericDesktopUsage::X), ReportValue::I8(-2)));
+    assert!(matches!(axis(GenericDesktopUsage::Y), ReportValue::I8(3)));
+}
+
+#[test]
+fn test_short_input_report() {
+    let report_desc = ReportDescriptor::new(T16000M_REPORT.iter().copied()).unwrap();
+    let input = [0u8; 2];
+    let error = parse_input_report(&report_desc, input.iter().copied()).unwrap_err();
+    assert_eq!(error.kind(), HidErrorKind::UnexpectedEnd);
+    assert_eq!(error.offset(), input.len());
+}
+
+#[test]
+fn test_layout_dualshock_4() {
+    let report_desc = ReportDescriptor::new(DUALSHOCK_4_REPORT.iter().copied()).unwrap();
+    let layout = report_desc.layout();
+
+    assert!(layout.uses_report_ids());
+    assert_eq!(layout.max_len(DataType::Input), 64);
+    assert_eq!(
+        layout.report(DataType::Output, Some(0x02)).unwrap().len(),
+        48
+    );
+    assert_eq!(
+        layout.report(DataType::Feature, Some(0xA0)).unwrap().len(),
+        2
+    );
+    assert!(layout.report(DataType::Input, Some(0x02)).is_none());
+
+    let layout = ReportDescriptor::new(T16000M_REPORT.iter().copied())
+        .unwrap()
+        .layout();
+    assert!(!layout.uses_report_ids());
+    assert_eq!(layout.max_len(DataType::Input), 9);
+    assert_eq!(layout.max_len(DataType::Feature), 4);
+}
+
+#[test]
+fn test_report_id_dispatch() {
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x05)
+        .collection(CollectionType::Application)
+        .report_id(1)
+        .usage(0x30)
+        .logical_range(0, 255)
+        .report_size(8)
+        .report_count(1)
+        .input(ItemData::variable())
+        .report_id(2)
+        .usage_page(UsagePage::Button)
+        .usage_range(1, 3)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(3)
+        .input(ItemData::variable())
+        .input_padding(5)
+        .end_collection()
+        .build()
+        .unwrap();
+
+    let parse = |input: &[u8]| parse_input_report(&report_desc, input.iter().copied());
+
+    let axis = parse(&[0x01, 0x80]).unwrap();
+    assert_eq!(axis.iter().count(), 1);
+    assert_eq!(axis.iter().next().unwrap().report_value().as_i64(), 0x80);
+
+    let buttons = parse(&[0x02, 0b0000_0110]).unwrap();
+    let buttons = buttons
+        .iter()
+        .map(|value| value.report_value().as_i64())
+        .collect::<Vec<_>>();
+    assert_eq!(buttons, [0, 1, 1]);
+
+    assert_eq!(
+        parse(&[0x03, 0x00]).unwrap_err().kind(),
+        HidErrorKind::UnknownReportId(3)
+    );
+}
+
+/// Report 1: three buttons, a hat switch and an X axis. Report 2: a two-key array
+fn tracked_gamepad() -> ReportDescriptor {
+    ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x05)
+        .collection(CollectionType::Application)
+        .report_id(1)
+        .usage_page(UsagePage::Button)
+        .usage_range(1, 3)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(3)
+        .input(ItemData::variable())
+        .input_padding(1)
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x39)
+        .logical_range(1, 8)
+        .report_size(4)
+        .report_count(1)
+        .input(ItemData::variable())
+        .usage(0x30)
+        .logical_range(0, 100)
+        .report_size(8)
+        .input(ItemData::variable())
+        .report_id(2)
+        .usage_page(UsagePage::Button)
+        .usage_range(1, 8)
+        .logical_range(1, 8)
+        .report_size(8)
+        .report_count(2)
+        .input(ItemData::array())
+        .end_collection()
+        .build()
+        .unwrap()
+}
+
+fn track(tracker: &mut ChangeTracker, report_desc: &ReportDescriptor, input: &[u8]) -> Vec<String> {
+    let report = parse_input_report(report_desc, input.iter().copied()).unwrap();
+    tracker
+        .update(Some(input[0]), report)
+        .iter()
+        .map(|event| match event {
+            InputEvent::AxisMoved { value, .. } => format!("{} moved", value.report_value()),
+            event => event.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_change_tracker() {
+    let report_desc = tracked_gamepad();
+    let mut tracker = ChangeTracker::new(0.05);
+
+    // Held buttons and hat are reported, the axis takes its baseline
+    assert_eq!(
+        track(&mut tracker, &report_desc, &[0x01, 0b0000_0001, 50]),
+        ["Button 1 pressed"]
+    );
+
+    assert_eq!(
+        track(&mut tracker, &report_desc, &[0x01, 0b0010_0110, 53]),
+        [
+            "Button 1 released",
+            "Button 2 pressed",
+            "Button 3 pressed",
+            "HatSwitch changed from Centered to UpRight"
+        ]
+    );
+
+    // Below the deadzone, then beyond it relative to the last reported value
+    assert!(track(&mut tracker, &report_desc, &[0x01, 0b0010_0110, 54]).is_empty());
+    assert_eq!(
+        track(&mut tracker, &report_desc, &[0x01, 0b0010_0110, 56]),
+        ["56 moved"]
+    );
+
+    // Other report IDs don't affect the state of report 1
+    assert_eq!(
+        track(&mut tracker, &report_desc, &[0x02, 0x04, 0x00]),
+        ["Button 4 pressed"]
+    );
+    assert_eq!(
+        track(&mut tracker, &report_desc, &[0x02, 0x00, 0x00]),
+        ["Button 4 released"]
+    );
+
+    let state = tracker.state();
+    assert_eq!(state.reports().count(), 2);
+    assert_eq!(state.report(Some(1)).unwrap().iter().count(), 5);
+    assert_eq!(
+        state
+            .values()
+            .filter_map(|value| value.hat_switch())
+            .collect::<Vec<_>>(),
+        [HatDirection::UpRight]
+    );
+}
+
+#[test]
+fn test_change_tracker_overlapping_buttons() {
+    // Eight buttons packed into a single byte by the descriptor
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x05)
+        .collection(CollectionType::Application)
+        .usage_page(UsagePage::Button)
+        .usage_range(1, 8)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(8)
+        .input(ItemData::variable())
+        .end_collection()
+        .build()
+        .unwrap();
+
+    let mut tracker = ChangeTracker::new(0.05);
+    let mut track = |input: u8| {
+        let report = parse_input_report(&report_desc, std::iter::once(input)).unwrap();
+        tracker
+            .update(None, report)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    // Each button is pressed and released while the other is held
+    assert_eq!(track(0b0000_0001), ["Button 1 pressed"]);
+    assert_eq!(track(0b1000_0001), ["Button 8 pressed"]);
+    assert_eq!(track(0b1000_0000), ["Button 1 released"]);
+    assert_eq!(track(0b0000_0000), ["Button 8 released"]);
+}
+
+/// Parse a single input report, returning its values in order
+fn input_values(report_desc: &ReportDescriptor, input: &[u8]) -> Vec<InputValue> {
+    parse_input_report(report_desc, input.iter().copied())
+        .unwrap()
+        .iter()
+        .copied()
+        .collect()
+}
+
+fn assert_approx(value: Option<f64>, expected: f64) {
+    let value = value.expect("Missing value");
+    assert!((value - expected).abs() < 1e-9, "{} != {}", value, expected);
+}
+
+#[test]
+fn test_physical_scaling() {
+    // Y without a physical range, then X scaled into hundredths
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x04)
+        .collection(CollectionType::Application)
+        .usage(0x31)
+        .logical_range(-100, 100)
+        .report_size(8)
+        .report_count(1)
+        .input(ItemData::variable())
+        .usage(0x30)
+        .logical_range(0, 250)
+        .physical_range(0, 1000)
+        .unit_exponent(0x0E)
+        .input(ItemData::variable())
+        .end_collection()
+        .build()
+        .unwrap();
+
+    let values = input_values(&report_desc, &[-50i8 as u8, 50]);
+    let physical = |i: usize| values[i].physical().map(|physical| physical.value);
+
+    // A missing physical range falls back to the logical range
+    assert_approx(physical(0), -50.0);
+    assert_approx(values[0].normalized(), 0.25);
+    assert_approx(values[0].normalized_signed(), -0.5);
+
+    // 50 / 250 of 1000, with an exponent of -2
+    assert_approx(physical(1), 2.0);
+    assert_approx(values[1].normalized(), 0.2);
+
+    let values = input_values(&report_desc, &[100, 250]);
+    assert_approx(values[0].physical().map(|physical| physical.value), 100.0);
+    assert_approx(values[1].physical().map(|physical| physical.value), 10.0);
+    assert_approx(values[1].normalized_signed(), 1.0);
+
+    // Outside the logical range is the null state, which normalizes to the nearest bound
+    let values = input_values(&report_desc, &[-128i8 as u8, 0]);
+    assert!(values[0].physical().is_none());
+    assert_approx(values[0].normalized(), 0.0);
+    assert!(values[0].hat_switch().is_none());
+}
+
+#[test]
+fn test_hat_switches() {
+    // An eight-position hat from 1 to 8, then a four-position hat from 1 to 4
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x05)
+        .collection(CollectionType::Application)
+        .usage(0x39)
+        .logical_range(1, 8)
+        .report_size(4)
+        .report_count(1)
+        .input(ItemData::variable())
+        .usage(0x39)
+        .logical_range(1, 4)
+        .input(ItemData::variable())
+        .end_collection()
+        .build()
+        .unwrap();
+
+    let hats = |input: u8| {
+        let values = input_values(&report_desc, &[input]);
+        (
+            values[0].hat_switch().unwrap(),
+            values[1].hat_switch().unwrap(),
+        )
+    };
+
+    assert_eq!(hats(0x11), (HatDirection::Up, HatDirection::Up));
+    assert_eq!(hats(0x22), (HatDirection::UpRight, HatDirection::Right));
+    assert_eq!(hats(0x33), (HatDirection::Right, HatDirection::Down));
+    assert_eq!(hats(0x48), (HatDirection::UpLeft, HatDirection::Left));
+
+    // Values outside the logical range are the null state
+    assert_eq!(hats(0x50), (HatDirection::Centered, HatDirection::Centered));
+    assert_eq!(hats(0x09), (HatDirection::Centered, HatDirection::Centered));
+
+    // Unsupported position counts decode as centered
+    assert_eq!(HatDirection::from_logical(2, 0, 5), HatDirection::Centered);
+    assert_eq!(HatDirection::from_logical(-3, -4, -1), HatDirection::Right);
+}
+
+#[test]
+fn test_report_value_from_bits() {
+    // Odd widths sign-extend from their top bit
+    assert!(matches!(
+        ReportValue::from_bits(0xFFF, 12, true),
+        ReportValue::I16(-1)
+    ));
+    assert!(matches!(
+        ReportValue::from_bits(0x800, 12, true),
+        ReportValue::I16(-2048)
+    ));
+    assert!(matches!(
+        ReportValue::from_bits(0x7FF, 12, true),
+        ReportValue::I16(2047)
+    ));
+    assert!(matches!(
+        ReportValue::from_bits(0x1F, 5, true),
+        ReportValue::I8(-1)
+    ));
+    assert!(matches!(
+        ReportValue::from_bits(0x3, 1, false),
+        ReportValue::Bool(true)
+    ));
+    assert!(matches!(
+        ReportValue::from_bits(0xFFF, 12, false),
+        ReportValue::U16(0xFFF)
+    ));
+
+    // Widths beyond 32 bits use the 64-bit variants
+    let signed = ReportValue::from_bits(1 << 32, 33, true);
+    assert!(matches!(signed, ReportValue::I64(v) if v == -(1 << 32)));
+    assert!(matches!(
+        ReportValue::from_bits(u64::MAX, 64, true),
+        ReportValue::I64(-1)
+    ));
+
+    let unsigned = ReportValue::from_bits(u64::MAX, 64, false);
+    assert!(matches!(unsigned, ReportValue::U64(u64::MAX)));
+    assert!(!unsigned.is_signed());
+    assert_eq!(unsigned.as_i64(), -1);
+    assert!(unsigned.as_f64() > 1.8e19);
+}
+
+#[test]
+fn test_value_across_byte_boundary() {
+    // A 12-bit signed axis starting halfway through the first byte
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x04)
+        .collection(CollectionType::Application)
+        .input_padding(4)
+        .usage(0x30)
+        .logical_range(-2048, 2047)
+        .report_size(12)
+        .report_count(1)
+        .input(ItemData::variable())
+        .end_collection()
+        .build()
+        .unwrap();
+
+    let value = |input: [u8; 2]| {
+        input_values(&report_desc, &input)[0]
+            .report_value()
+            .as_i64()
+    };
+    assert_eq!(value([0xF0, 0xFF]), -1);
+    assert_eq!(value([0x00, 0x80]), -2048);
+    assert_eq!(value([0xF5, 0x7F]), 2047);
+    assert_eq!(value([0x30, 0x12]), 0x123);
+}
+
+#[test]
+fn test_array_selection() {
+    // Two button selectors from 1 to 8, then a key selector where 0 means no key
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x06)
+        .collection(CollectionType::Application)
+        .usage_page(UsagePage::Button)
+        .usage_range(1, 8)
+        .logical_range(1, 8)
+        .report_size(8)
+        .report_count(2)
+        .input(ItemData::array())
+        .usage_page(UsagePage::KeyboardKeypad)
+        .usage_range(0, 0x65)
+        .logical_range(0, 0x65)
+        .report_count(1)
+        .input(ItemData::array())
+        .end_collection()
+        .build()
+        .unwrap();
+
+    let usages = |input: [u8; 3]| {
+        input_values(&report_desc, &input)
+            .iter()
+            .map(|value| value.data_item().local_state.usage.unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        usages([3, 8, 4]),
+        [
+            Usage::new(UsagePage::Button, 3),
+            Usage::new(UsagePage::Button, 8),
+            Usage::new(UsagePage::KeyboardKeypad, 4),
+        ]
+    );
+
+    // Selectors outside the logical range, and the undefined usage 0, select nothing
+    assert_eq!(usages([0, 9, 0]), []);
+    assert_eq!(usages([2, 0, 0x66]), [Usage::new(UsagePage::Button, 2)]);
+}
+
+#[test]
+fn test_output_report() {
+    // Five LEDs, three bits of constant padding, then a 12-bit signed value
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x05)
+        .collection(CollectionType::Application)
+        .report_id(3)
+        .usage_page(UsagePage::Led)
+        .usage_range(1, 5)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(5)
+        .output(ItemData::variable())
+        .report_size(3)
+        .report_count(1)
+        .output(ItemData::constant())
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x30)
+        .logical_range(-2048, 2047)
+        .report_size(12)
+        .output(ItemData::variable())
+        .end_collection()
+        .build()
+        .unwrap();
+
+    let mut report = OutputReport::output(&report_desc, Some(3));
+    assert_eq!(report.items().count(), 7);
+    assert_eq!(report.to_bytes(), [0x03, 0x00, 0x00, 0x00]);
+
+    report
+        .set(UsagePage::Led, 1, 1)
+        .unwrap()
+        .set(UsagePage::Led, 5, 1)
+        .unwrap()
+        .set_index(6, -2)
+        .unwrap();
+
+    // LEDs 1 and 5, zeroed padding, then -2 spanning the last two bytes
+    assert_eq!(report.to_bytes(), [0x03, 0x11, 0xFE, 0x0F]);
+
+    assert!(matches!(
+        report.set(UsagePage::Led, 1, 2),
+        Err(WriteError::OutOfRange {
+            value: 2,
+            min: 0,
+            max: 1
+        })
+    ));
+    assert!(matches!(
+        report.set_index(6, 2048),
+        Err(WriteError::OutOfRange { .. })
+    ));
+    assert!(matches!(
+        report.set_index(5, 1),
+        Err(WriteError::Constant(5))
+    ));
+    assert!(matches!(
+        report.set_index(7, 0),
+        Err(WriteError::InvalidIndex(7))
+    ));
+    assert!(matches!(
+        report.set(UsagePage::Led, 6, 1),
+        Err(WriteError::UnknownUsage(UsagePage::Led, 6))
+    ));
+
+    // Failed writes leave the report unchanged
+    assert_eq!(report.to_bytes(), [0x03, 0x11, 0xFE, 0x0F]);
+
+    // Report IDs without fields of this type only contain the prefix
+    assert_eq!(
+        OutputReport::feature(&report_desc, Some(3)).to_bytes(),
+        [0x03]
+    );
+}
+
+#[test]
+fn test_output_report_without_id() {
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::Led)
+        .usage_range(1, 3)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(3)
+        .output(ItemData::variable())
+        .build()
+        .unwrap();
+
+    let mut report = OutputReport::output(&report_desc, None);
+    report.set(UsagePage::Led, 2, 1).unwrap();
+    assert_eq!(report.to_bytes(), [0b0000_0010]);
+}
+
+#[test]
+fn test_load_dir() {
+    let dir = std::env::temp_dir().join(format!("antigen-hid-load-dir-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let write = |name: &str, contents: &[u8]| std::fs::write(dir.join(name), contents).unwrap();
+    write("VID_044f_PID_b679.hrd", &TFRP_REPORT);
+    write(
+        "VID_1234_PID_ABCD.hex",
+        b"// Vendor gamepad\n0x05, 0x01 0x09 0x05\r\n\n  a1 01 // Application\n0x75 0x08 0x95 0x01 0x81 0x02 0xC0\n",
+    );
+    write("VID_0001_PID_0002.hex", b"05 01\n09 0x105\n");
+    write("VID_0001.hrd", &TFRP_REPORT);
+    write("notes.txt", b"Not a descriptor");
+
+    let mut report_descriptors = ReportDescriptorData::default();
+    let errors = report_descriptors.load_dir(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let errors = errors.unwrap();
+
+    // Malformed files are reported without stopping the others from loading
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().any(|error| matches!(
+        error,
+        LoadError::InvalidHex(path, 2) if path.ends_with("VID_0001_PID_0002.hex")
+    )));
+    assert!(errors.iter().any(|error| matches!(
+        error,
+        LoadError::InvalidFileName(path) if path.ends_with("VID_0001.hrd")
+    )));
+
+    assert_eq!(report_descriptors.iter().count(), 2);
+    assert_eq!(
+        report_descriptors.get(0x044f, 0xb679).unwrap()[..],
+        TFRP_REPORT[..]
+    );
+    assert_eq!(
+        report_descriptors.get(0x1234, 0xabcd).unwrap()[..],
+        [0x05, 0x01, 0x09, 0x05, 0xa1, 0x01, 0x75, 0x08, 0x95, 0x01, 0x81, 0x02, 0xc0][..]
+    );
+    assert!(report_descriptors.get(0x0001, 0x0002).is_none());
+}
+
+#[test]
+fn test_collection_queries() {
+    let joystick = Usage::GenericDesktop(GenericDesktopUsage::Joystick);
+    let pointer = Usage::GenericDesktop(GenericDesktopUsage::Pointer);
+    let usages = |items: Vec<&ReportDescriptorItem>| {
+        items
+            .into_iter()
+            .map(|item| item.local_state.usage)
+            .collect::<Vec<_>>()
+    };
+
+    // The stick axes sit in a Physical(Pointer) collection next to a hat switch
+    let report_desc = ReportDescriptorBuilder::new()
+        .usage_page(UsagePage::GenericDesktop)
+        .usage(0x04)
+        .collection(CollectionType::Application)
+        .usage(0x01)
+        .collection(CollectionType::Physical)
+        .usage(0x30)
+        .usage(0x31)
+        .logical_range(0, 255)
+        .report_size(8)
+        .report_count(2)
+        .input(ItemData::variable())
+        .end_collection()
+        .usage(0x39)
+        .logical_range(0, 7)
+        .report_size(4)
+        .report_count(1)
+        .input(ItemData::variable())
+        .input_padding(4)
+        .end_collection()
+        .build()
+        .unwrap();
+
+    let sticks = report_desc
+        .find_collections(&[joystick, pointer])
+        .collect::<Vec<_>>();
+    assert_eq!(sticks.len(), 1);
+    let stick = sticks[0];
+    let application = report_desc.collection(stick).parent.unwrap();
+
+    assert_eq!(report_desc.collection_path(stick), [application, stick]);
+    assert_eq!(
+        report_desc.collection_usages(stick),
+        [Some(joystick), Some(pointer)]
+    );
+    assert!(report_desc.is_within(stick, application));
+    assert!(!report_desc.is_within(application, stick));
+
+    // Usage paths match from the outermost collection
+    assert_eq!(report_desc.find_collections(&[pointer]).count(), 0);
+
+    assert_eq!(
+        usages(report_desc.items_in_path(&[joystick, pointer]).collect()),
+        [
+            Some(Usage::GenericDesktop(GenericDesktopUsage::X)),
+            Some(Usage::GenericDesktop(GenericDesktopUsage::Y)),
+        ]
+    );
+    assert_eq!(
+        usages(report_desc.items_in(application).collect()),
+        [
+            Some(Usage::GenericDesktop(GenericDesktopUsage::X)),
+            Some(Usage::GenericDesktop(GenericDesktopUsage::Y)),
+            Some(Usage::GenericDesktop(GenericDesktopUsage::HatSwitch)),
+            None,
+        ]
+    );
+
+    // The T.16000M keeps every item directly inside its Joystick application collection
+    let report_desc = ReportDescriptor::new(T16000M_REPORT.iter().copied()).unwrap();
+    assert_eq!(
+        report_desc.find_collections(&[joystick, pointer]).count(),
+        0
+    );
+    assert_eq!(
+        report_desc.items_in_path(&[joystick]).count(),
+        report_desc.iter().count()
+    );
+}
+
+#[test]
+fn test_usage_round_trip() {
+    let usage_pages = [
+        UsagePage::GenericDesktop,
+        UsagePage::SimulationControls,
+        UsagePage::GameControls,
+        UsagePage::KeyboardKeypad,
+        UsagePage::Led,
+        UsagePage::Button,
+        UsagePage::Consumer,
+        UsagePage::Digitizers,
+        UsagePage::VendorDefined,
+        UsagePage::Reserved(0x77),
+    ];
+
+    // Every ID in each table maps back to itself, including reserved ranges
+    for usage_page in usage_pages.iter().copied() {
+        for id in 0..=0x400 {
+            assert_eq!(
+                Usage::new(usage_page, id).id(),
+                id,
+                "{:?} {:#x}",
+                usage_page,
+                id
+            );
+        }
+    }
+}
+
+#[test]
+fn test_usage_display() {
+    let display = |usage_page, id| Usage::new(usage_page, id).to_string();
+
+    assert_eq!(display(UsagePage::GenericDesktop, 0x39), "HatSwitch");
+    assert_eq!(display(UsagePage::SimulationControls, 0xBB), "Throttle");
+    assert_eq!(display(UsagePage::GameControls, 0x20), "PointOfView");
+    assert_eq!(display(UsagePage::KeyboardKeypad, 0xE0), "LeftControl");
+    assert_eq!(display(UsagePage::Led, 0x03), "ScrollLock");
+    assert_eq!(display(UsagePage::Button, 0), "NoButtonPressed");
+    assert_eq!(display(UsagePage::Button, 12), "Button 12");
+    assert_eq!(display(UsagePage::Consumer, 0xCD), "PlayPause");
+    assert_eq!(display(UsagePage::Digitizers, 0x42), "TipSwitch");
+
+    // IDs missing from a table fall back to their raw value
+    assert_eq!(
+        display(UsagePage::SimulationControls, 0x300),
+        "Reserved(0x300)"
+    );
+    assert_eq!(display(UsagePage::GameControls, 0x300), "Reserved(0x300)");
+    assert_eq!(display(UsagePage::KeyboardKeypad, 0x300), "Reserved(0x300)");
+    assert_eq!(display(UsagePage::Led, 0x300), "Reserved(0x300)");
+    assert_eq!(display(UsagePage::Consumer, 0x300), "Reserved(0x300)");
+    assert_eq!(display(UsagePage::Digitizers, 0x300), "Reserved(0x300)");
+
+    // Pages without a usage table keep the raw ID
+    assert_eq!(
+        Usage::new(UsagePage::VendorDefined, 0x21),
+        Usage::Other(0x21)
+    );
+    assert_eq!(display(UsagePage::VendorDefined, 0x21), "33");
+    assert_eq!(display(UsagePage::Reserved(0x77), 0x21), "33");
+}
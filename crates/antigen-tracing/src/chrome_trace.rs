@@ -0,0 +1,502 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Write,
+    path::Path,
+    thread::ThreadId,
+    time::Instant,
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use tracing::Metadata;
+
+use crate::{
+    SpanId, TraceEvent, TraceEventVariant, TraceField, TraceFields, TraceLeafVariant, TraceThread,
+    TraceTree,
+};
+
+/// Chrome trace-event phase
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub enum ChromePhase {
+    #[serde(rename = "B")]
+    Begin,
+    #[serde(rename = "E")]
+    End,
+    #[serde(rename = "i")]
+    Instant,
+    #[serde(rename = "M")]
+    Metadata,
+}
+
+/// Single entry in the `traceEvents` array of a Chrome trace
+#[derive(Debug, Clone, Serialize)]
+pub struct ChromeTraceEvent {
+    pub name: String,
+    pub cat: String,
+    pub ph: ChromePhase,
+    /// Timestamp in microseconds
+    pub ts: f64,
+    pub pid: u32,
+    pub tid: u64,
+    /// Instant event scope
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s: Option<&'static str>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, Value>,
+    /// Position among events with equal timestamps, where equal positions keep insertion order
+    #[serde(skip)]
+    order: (u8, isize),
+}
+
+/// Position of a trace tree event among others with the same timestamp
+///
+/// Spans end innermost first before new spans begin outermost first,
+/// while zero-length spans end after everything else begins so that they stay nested.
+fn tree_order(ph: ChromePhase, depth: usize, zero_length: bool) -> (u8, isize) {
+    let depth = depth as isize;
+    match ph {
+        ChromePhase::Metadata => (0, 0),
+        ChromePhase::End if zero_length => (4, -depth),
+        ChromePhase::End => (1, -depth),
+        ChromePhase::Begin => (2, depth),
+        ChromePhase::Instant => (3, 0),
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChromeTrace<'a> {
+    trace_events: &'a [ChromeTraceEvent],
+    display_time_unit: &'static str,
+}
+
+fn field_value(field: &TraceField) -> Value {
+    match field {
+        TraceField::Debug(value) | TraceField::Str(value) | TraceField::Error(value) => {
+            Value::String(value.clone())
+        }
+        TraceField::I64(value) => Value::from(*value),
+        TraceField::U64(value) => Value::from(*value),
+        TraceField::Bool(value) => Value::from(*value),
+    }
+}
+
+fn args(fields: &TraceFields) -> BTreeMap<String, Value> {
+    fields
+        .iter()
+        .map(|(name, field)| (name.to_string(), field_value(field)))
+        .collect()
+}
+
+/// Live span awaiting its enter / exit events
+#[derive(Debug)]
+struct PendingSpan {
+    metadata: &'static Metadata<'static>,
+    args: BTreeMap<String, Value>,
+}
+
+/// Converts [`TraceTree`]s or live [`TraceEvent`]s into the Chrome trace-event JSON format
+///
+/// The output can be loaded in `chrome://tracing` or Perfetto.
+/// Thread IDs are assigned in order of first appearance, with thread names emitted as metadata events.
+#[derive(Debug)]
+pub struct ChromeTraceExporter {
+    origin: Instant,
+    pid: u32,
+    threads: HashMap<ThreadId, u64>,
+    spans: BTreeMap<SpanId, PendingSpan>,
+    events: Vec<ChromeTraceEvent>,
+}
+
+impl Default for ChromeTraceExporter {
+    fn default() -> Self {
+        ChromeTraceExporter::new(Instant::now())
+    }
+}
+
+impl ChromeTraceExporter {
+    /// Create an exporter with timestamps measured from `origin`
+    pub fn new(origin: Instant) -> Self {
+        ChromeTraceExporter {
+            origin,
+            pid: std::process::id(),
+            threads: Default::default(),
+            spans: Default::default(),
+            events: Default::default(),
+        }
+    }
+
+    pub fn events(&self) -> &[ChromeTraceEvent] {
+        &self.events
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    fn timestamp(&self, instant: Instant) -> f64 {
+        instant.saturating_duration_since(self.origin).as_secs_f64() * 1_000_000.0
+    }
+
+    /// Chrome thread ID for a thread, emitting a name metadata event on first use
+    fn tid(&mut self, thread: &TraceThread) -> u64 {
+        if let Some(tid) = self.threads.get(&thread.id()) {
+            return *tid;
+        }
+
+        let tid = self.threads.len() as u64 + 1;
+        self.threads.insert(thread.id(), tid);
+
+        let name = thread
+            .name()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| format!("{:?}", thread.id()));
+
+        self.events.push(ChromeTraceEvent {
+            name: "thread_name".into(),
+            cat: String::new(),
+            ph: ChromePhase::Metadata,
+            ts: 0.0,
+            pid: self.pid,
+            tid,
+            s: None,
+            args: std::iter::once(("name".to_string(), Value::String(name))).collect(),
+            order: (0, 0),
+        });
+
+        tid
+    }
+
+    fn push(
+        &mut self,
+        thread: &TraceThread,
+        metadata: &'static Metadata<'static>,
+        ph: ChromePhase,
+        instant: Instant,
+        args: BTreeMap<String, Value>,
+        order: (u8, isize),
+    ) {
+        let tid = self.tid(thread);
+
+        // Events are named by their message where available, rather than their callsite
+        let name = match (ph, args.get("message")) {
+            (ChromePhase::Instant, Some(Value::String(message))) => message.clone(),
+            _ => metadata.name().to_string(),
+        };
+
+        self.events.push(ChromeTraceEvent {
+            name,
+            cat: metadata.target().to_string(),
+            ph,
+            ts: self.timestamp(instant),
+            pid: self.pid,
+            tid,
+            s: if let ChromePhase::Instant = ph {
+                Some("t")
+            } else {
+                None
+            },
+            args,
+            order,
+        });
+    }
+
+    /// Add the spans and events of a collected trace tree
+    ///
+    /// Trace trees only record the thread each span was created on,
+    /// so spans entered on a different thread are placed on their creating thread's track.
+    /// Use [`ChromeTraceExporter::add_event`] to place spans by the thread that entered them.
+    pub fn add_tree(&mut self, trace_tree: &TraceTree) {
+        for leaf in trace_tree.leaves.values() {
+            let mut depth = 0;
+            let mut parent_id = leaf.parent_id;
+            while let Some(parent) = parent_id.and_then(|id| trace_tree.leaves.get(&id)) {
+                depth += 1;
+                parent_id = parent.parent_id;
+            }
+
+            match leaf.variant {
+                TraceLeafVariant::Span { ref intervals, .. } => {
+                    // Fields are attached to the first begin event only
+                    let mut fields = Some(args(&leaf.fields));
+                    for interval in intervals {
+                        self.push(
+                            &leaf.thread,
+                            leaf.metadata,
+                            ChromePhase::Begin,
+                            interval.entered,
+                            fields.take().unwrap_or_default(),
+                            tree_order(ChromePhase::Begin, depth, false),
+                        );
+
+                        if let Some(exited) = interval.exited {
+                            self.push(
+                                &leaf.thread,
+                                leaf.metadata,
+                                ChromePhase::End,
+                                exited,
+                                Default::default(),
+                                tree_order(ChromePhase::End, depth, exited <= interval.entered),
+                            );
+                        }
+                    }
+                }
+                TraceLeafVariant::Event { instant } => self.push(
+                    &leaf.thread,
+                    leaf.metadata,
+                    ChromePhase::Instant,
+                    instant,
+                    args(&leaf.fields),
+                    tree_order(ChromePhase::Instant, depth, false),
+                ),
+            }
+        }
+    }
+
+    /// Add a single event from a live [`TraceReceiver`](crate::TraceReceiver) stream
+    ///
+    /// Events with equal timestamps are written in the order they were added,
+    /// which matches the order they happened in on each thread.
+    ///
+    /// Span fields are attached to the begin event when created, and to the end event when recorded later.
+    pub fn add_event(&mut self, event: &TraceEvent) {
+        let thread = &event.thread;
+        match &event.variant {
+            TraceEventVariant::NewSpan {
+                id,
+                metadata,
+                fields,
+                ..
+            } => {
+                self.spans.insert(
+                    *id,
+                    PendingSpan {
+                        metadata,
+                        args: args(fields),
+                    },
+                );
+            }
+            TraceEventVariant::Record { id, fields } => {
+                if let Some(span) = self.spans.get_mut(id) {
+                    span.args.extend(args(fields));
+                }
+            }
+            TraceEventVariant::Event {
+                metadata,
+                fields,
+                instant,
+                ..
+            } => self.push(
+                thread,
+                metadata,
+                ChromePhase::Instant,
+                *instant,
+                args(fields),
+                (0, 0),
+            ),
+            TraceEventVariant::Enter { id, instant } | TraceEventVariant::Exit { id, instant } => {
+                let ph = if let TraceEventVariant::Enter { .. } = event.variant {
+                    ChromePhase::Begin
+                } else {
+                    ChromePhase::End
+                };
+
+                if let Some(span) = self.spans.get_mut(id) {
+                    let metadata = span.metadata;
+                    let args = std::mem::take(&mut span.args);
+                    self.push(thread, metadata, ph, *instant, args, (0, 0));
+                }
+            }
+            TraceEventVariant::Close { id } => {
+                self.spans.remove(id);
+            }
+            TraceEventVariant::FollowsFrom { .. } => (),
+        }
+    }
+
+    /// Serialize collected events as a Chrome trace JSON object
+    pub fn write<W: Write>(&self, writer: W) -> serde_json::Result<()> {
+        let mut events = self.events.clone();
+        events.sort_by(|lhs, rhs| {
+            lhs.ts
+                .partial_cmp(&rhs.ts)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| lhs.order.cmp(&rhs.order))
+        });
+
+        serde_json::to_writer(
+            writer,
+            &ChromeTrace {
+                trace_events: &events,
+                display_time_unit: "ms",
+            },
+        )
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        let mut buf = vec![];
+        self.write(&mut buf)?;
+        Ok(String::from_utf8(buf).expect("serde_json produced invalid UTF-8"))
+    }
+
+    pub fn write_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(file)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{capture_events, TraceReceiver};
+
+    /// A span entered twice, with an event logged during the first entry
+    fn traced() -> Vec<TraceEvent> {
+        capture_events(|| {
+            let span = tracing::trace_span!("work", count = 3);
+            {
+                let _span = span.enter();
+                tracing::trace!(answer = 42, "hello");
+            }
+            let _span = span.enter();
+        })
+    }
+
+    fn phases(exporter: &ChromeTraceExporter) -> Vec<(ChromePhase, &str)> {
+        exporter
+            .events()
+            .iter()
+            .map(|event| (event.ph, event.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_add_event() {
+        let mut exporter = ChromeTraceExporter::new(Instant::now());
+        for event in traced() {
+            exporter.add_event(&event);
+        }
+
+        assert_eq!(
+            phases(&exporter),
+            [
+                (ChromePhase::Metadata, "thread_name"),
+                (ChromePhase::Begin, "work"),
+                (ChromePhase::Instant, "hello"),
+                (ChromePhase::End, "work"),
+                (ChromePhase::Begin, "work"),
+                (ChromePhase::End, "work"),
+            ]
+        );
+
+        let events = exporter.events();
+        assert_eq!(events[1].args.get("count"), Some(&Value::from(3)));
+        assert_eq!(events[2].args.get("answer"), Some(&Value::from(42)));
+        assert_eq!(events[2].s, Some("t"));
+        assert!(events[4].args.is_empty());
+
+        let tid = events[0].tid;
+        assert!(events
+            .iter()
+            .all(|event| event.tid == tid && event.pid == std::process::id()));
+        assert!(events[1..]
+            .windows(2)
+            .all(|events| events[0].ts <= events[1].ts));
+    }
+
+    #[test]
+    fn test_add_tree() {
+        let origin = Instant::now();
+        let mut trace_tree = TraceTree::default();
+        for event in traced() {
+            TraceReceiver::apply(&mut trace_tree, event);
+        }
+
+        let mut exporter = ChromeTraceExporter::new(origin);
+        exporter.add_tree(&trace_tree);
+
+        let mut phases = phases(&exporter);
+        phases.sort_by_key(|(ph, _)| *ph as u8);
+        assert_eq!(
+            phases,
+            [
+                (ChromePhase::Begin, "work"),
+                (ChromePhase::Begin, "work"),
+                (ChromePhase::End, "work"),
+                (ChromePhase::End, "work"),
+                (ChromePhase::Instant, "hello"),
+                (ChromePhase::Metadata, "thread_name"),
+            ]
+        );
+
+        // Fields are only attached to the first begin event of a span
+        let begin_args = exporter
+            .events()
+            .iter()
+            .filter(|event| event.ph == ChromePhase::Begin)
+            .map(|event| event.args.len())
+            .collect::<Vec<_>>();
+        assert_eq!(begin_args, [1, 0]);
+
+        let json: Value = serde_json::from_str(&exporter.to_json().unwrap()).unwrap();
+        assert_eq!(json["displayTimeUnit"], "ms");
+
+        let trace_events = json["traceEvents"].as_array().unwrap();
+        assert_eq!(trace_events.len(), 6);
+        assert_eq!(trace_events[0]["ph"], "M");
+        assert!(trace_events[0]["args"]["name"].is_string());
+
+        let timestamps = trace_events
+            .iter()
+            .map(|event| event["ts"].as_f64().unwrap())
+            .collect::<Vec<_>>();
+        assert!(timestamps.windows(2).all(|ts| ts[0] <= ts[1]));
+        assert!(trace_events[1..]
+            .iter()
+            .all(|event| event["ts"].as_f64().unwrap() >= 0.0 && event.get("cat").is_some()));
+    }
+
+    #[test]
+    fn test_equal_timestamps_nest() {
+        let mut trace_tree = TraceTree::default();
+        for event in capture_events(|| {
+            let _outer = tracing::trace_span!("outer").entered();
+            let _inner = tracing::trace_span!("inner").entered();
+        }) {
+            TraceReceiver::apply(&mut trace_tree, event);
+        }
+
+        // Collapse every interval onto the same instant, as a coarse clock would
+        let instant = Instant::now();
+        for leaf in trace_tree.leaves.values_mut() {
+            if let TraceLeafVariant::Span { intervals, .. } = &mut leaf.variant {
+                for interval in intervals.iter_mut() {
+                    interval.entered = instant;
+                    interval.exited = Some(instant);
+                }
+            }
+        }
+
+        let mut exporter = ChromeTraceExporter::new(instant);
+        exporter.add_tree(&trace_tree);
+
+        let json: Value = serde_json::from_str(&exporter.to_json().unwrap()).unwrap();
+        let phases = json["traceEvents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| {
+                format!(
+                    "{} {}",
+                    event["ph"].as_str().unwrap(),
+                    event["name"].as_str().unwrap()
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            phases,
+            ["M thread_name", "B outer", "B inner", "E inner", "E outer"]
+        );
+    }
+}
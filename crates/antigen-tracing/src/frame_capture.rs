@@ -0,0 +1,456 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    thread::ThreadId,
+};
+
+use tracing::Metadata;
+
+use crate::{
+    next_trace_leaf_id, SpanId, SpanInterval, TraceEvent, TraceEventVariant, TraceFields,
+    TraceLeaf, TraceLeafId, TraceLeafVariant, TraceThread, TraceTree,
+};
+
+/// Default number of completed frames retained per thread
+pub const DEFAULT_FRAME_HISTORY: usize = 8;
+
+/// Creation data for a span, retained so it can be inserted into frames it's entered in
+#[derive(Debug)]
+struct SpanInfo {
+    parent_id: Option<SpanId>,
+    metadata: &'static Metadata<'static>,
+    fields: TraceFields,
+}
+
+/// In-progress and completed frames for a single thread
+#[derive(Debug, Default)]
+struct ThreadFrames {
+    current: Option<(SpanId, TraceTree)>,
+    frames: VecDeque<TraceTree>,
+    frame_count: usize,
+}
+
+/// Splits a stream of [`TraceEvent`]s into per-thread frames
+///
+/// A frame opens when a thread enters a span matching the root predicate,
+/// and closes when that same thread exits it.
+/// Only events from the owning thread are recorded, so each frame is a complete
+/// [`TraceTree`] of what happened on one thread between those two points.
+///
+/// The generation of each frame's tree is its frame index on that thread.
+pub struct FrameCapture {
+    root: Box<dyn Fn(&Metadata<'static>) -> bool + Send + Sync>,
+    history: usize,
+    spans: BTreeMap<SpanId, SpanInfo>,
+    threads: HashMap<ThreadId, ThreadFrames>,
+}
+
+impl std::fmt::Debug for FrameCapture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameCapture")
+            .field("history", &self.history)
+            .field("spans", &self.spans)
+            .field("threads", &self.threads)
+            .finish()
+    }
+}
+
+impl FrameCapture {
+    /// Capture frames rooted at spans for which `root` returns true
+    pub fn new<F>(root: F) -> Self
+    where
+        F: Fn(&Metadata<'static>) -> bool + Send + Sync + 'static,
+    {
+        FrameCapture {
+            root: Box::new(root),
+            history: DEFAULT_FRAME_HISTORY,
+            spans: Default::default(),
+            threads: Default::default(),
+        }
+    }
+
+    /// Capture frames rooted at spans with the given name
+    pub fn with_root_name(name: &'static str) -> Self {
+        Self::new(move |metadata| metadata.name() == name)
+    }
+
+    /// Set the number of completed frames retained per thread
+    pub fn history(mut self, history: usize) -> Self {
+        self.history = history;
+        self
+    }
+
+    pub fn set_history(&mut self, history: usize) {
+        self.history = history;
+        for thread in self.threads.values_mut() {
+            Self::truncate(&mut thread.frames, history);
+        }
+    }
+
+    /// Threads that have started at least one frame
+    pub fn threads(&self) -> impl Iterator<Item = ThreadId> + '_ {
+        self.threads.keys().copied()
+    }
+
+    /// Completed frames for a thread, oldest first
+    pub fn frames(&self, thread: ThreadId) -> impl DoubleEndedIterator<Item = &TraceTree> {
+        self.threads
+            .get(&thread)
+            .into_iter()
+            .flat_map(|thread| thread.frames.iter())
+    }
+
+    /// Most recently completed frame for a thread
+    pub fn latest(&self, thread: ThreadId) -> Option<&TraceTree> {
+        self.threads
+            .get(&thread)
+            .and_then(|thread| thread.frames.back())
+    }
+
+    /// Frame currently being recorded on a thread
+    pub fn current(&self, thread: ThreadId) -> Option<&TraceTree> {
+        self.threads
+            .get(&thread)
+            .and_then(|thread| thread.current.as_ref())
+            .map(|(_, trace_tree)| trace_tree)
+    }
+
+    pub fn clear(&mut self) {
+        for thread in self.threads.values_mut() {
+            thread.frames.clear();
+        }
+    }
+
+    fn truncate(frames: &mut VecDeque<TraceTree>, history: usize) {
+        while frames.len() > history {
+            frames.pop_front();
+        }
+    }
+
+    /// Route a single event into the frame currently open on its thread
+    pub fn push(&mut self, TraceEvent { thread, variant }: TraceEvent) {
+        // Track span creation data regardless of frame state
+        match &variant {
+            TraceEventVariant::NewSpan {
+                id,
+                parent_id,
+                metadata,
+                fields,
+            } => {
+                self.spans.insert(
+                    *id,
+                    SpanInfo {
+                        parent_id: *parent_id,
+                        metadata,
+                        fields: fields.clone(),
+                    },
+                );
+            }
+            TraceEventVariant::Record { id, fields } => {
+                if let Some(span) = self.spans.get_mut(id) {
+                    span.fields.extend(fields.clone());
+                }
+            }
+            _ => (),
+        }
+
+        let is_root = match &variant {
+            TraceEventVariant::Enter { id, .. } => self
+                .spans
+                .get(id)
+                .map(|span| (self.root)(span.metadata))
+                .unwrap_or_default(),
+            _ => false,
+        };
+
+        // Threads are only tracked once they enter a root span
+        let thread_frames = if is_root {
+            self.threads.entry(thread.id()).or_default()
+        } else {
+            match self.threads.get_mut(&thread.id()) {
+                Some(thread_frames) => thread_frames,
+                None => {
+                    if let TraceEventVariant::Close { id } = variant {
+                        self.spans.remove(&id);
+                    }
+                    return;
+                }
+            }
+        };
+
+        // Open a new frame when a root span is entered
+        if let TraceEventVariant::Enter { id, .. } = &variant {
+            if is_root && thread_frames.current.is_none() {
+                let trace_tree = TraceTree {
+                    generation: thread_frames.frame_count,
+                    ..Default::default()
+                };
+                thread_frames.current = Some((*id, trace_tree));
+                thread_frames.frame_count += 1;
+            }
+        }
+
+        let (root_id, trace_tree) = match &mut thread_frames.current {
+            Some((root_id, trace_tree)) => (*root_id, trace_tree),
+            None => {
+                if let TraceEventVariant::Close { id } = variant {
+                    self.spans.remove(&id);
+                }
+                return;
+            }
+        };
+
+        let closes_frame = matches!(variant, TraceEventVariant::Exit { id, .. } if id == root_id);
+
+        match variant {
+            TraceEventVariant::NewSpan {
+                id,
+                parent_id,
+                metadata,
+                fields,
+            } => {
+                Self::insert_span(trace_tree, thread, id, parent_id, metadata, fields);
+            }
+            TraceEventVariant::Record { id, fields } => {
+                if let Ok(leaf) = trace_tree.try_get_mut_by_span_id(&id) {
+                    leaf.fields.extend(fields);
+                }
+            }
+            TraceEventVariant::FollowsFrom { id, follows } => {
+                let follows = trace_tree.trace_leaf_id(&follows).ok().copied();
+                if let (Some(follows_id), Ok(leaf)) =
+                    (follows, trace_tree.try_get_mut_by_span_id(&id))
+                {
+                    if let TraceLeafVariant::Span { follows, .. } = &mut leaf.variant {
+                        follows.push(follows_id);
+                    }
+                }
+            }
+            TraceEventVariant::Event {
+                parent_id,
+                metadata,
+                fields,
+                instant,
+            } => {
+                let parent_id = parent_id
+                    .and_then(|span_id| trace_tree.trace_leaf_id(&span_id).ok())
+                    .copied();
+
+                trace_tree.leaves.insert(
+                    next_trace_leaf_id(),
+                    TraceLeaf {
+                        generation: trace_tree.generation(),
+                        thread,
+                        parent_id,
+                        metadata,
+                        fields,
+                        variant: TraceLeafVariant::Event { instant },
+                    },
+                );
+            }
+            TraceEventVariant::Enter { id, instant } => {
+                if let Some(leaf_id) = Self::ensure_span(trace_tree, &self.spans, thread, id) {
+                    if let TraceLeafVariant::Span { intervals, .. } =
+                        &mut trace_tree.leaves.get_mut(&leaf_id).unwrap().variant
+                    {
+                        if !matches!(intervals.last(), Some(SpanInterval { exited: None, .. })) {
+                            intervals.push(SpanInterval {
+                                entered: instant,
+                                exited: None,
+                            });
+                        }
+                    }
+                }
+            }
+            TraceEventVariant::Exit { id, instant } => {
+                if let Ok(leaf) = trace_tree.try_get_mut_by_span_id(&id) {
+                    if let TraceLeafVariant::Span { intervals, .. } = &mut leaf.variant {
+                        if let Some(interval) = intervals.last_mut() {
+                            interval.exited.get_or_insert(instant);
+                        }
+                    }
+                }
+            }
+            TraceEventVariant::Close { id } => {
+                self.spans.remove(&id);
+                if let Ok(leaf) = trace_tree.try_get_mut_by_span_id(&id) {
+                    if let TraceLeafVariant::Span { open, .. } = &mut leaf.variant {
+                        *open = false;
+                    }
+                }
+            }
+        }
+
+        if closes_frame {
+            if let Some((_, trace_tree)) = thread_frames.current.take() {
+                thread_frames.frames.push_back(trace_tree);
+                Self::truncate(&mut thread_frames.frames, self.history);
+            }
+        }
+    }
+
+    fn insert_span(
+        trace_tree: &mut TraceTree,
+        thread: TraceThread,
+        id: SpanId,
+        parent_id: Option<SpanId>,
+        metadata: &'static Metadata<'static>,
+        fields: TraceFields,
+    ) -> TraceLeafId {
+        let trace_leaf_id = next_trace_leaf_id();
+        let parent_id = parent_id
+            .and_then(|span_id| trace_tree.trace_leaf_id(&span_id).ok())
+            .copied();
+
+        trace_tree
+            .span_id_to_trace_leaf_id
+            .insert(id, trace_leaf_id);
+        trace_tree.leaves.insert(
+            trace_leaf_id,
+            TraceLeaf {
+                generation: trace_tree.generation(),
+                thread,
+                parent_id,
+                metadata,
+                fields,
+                variant: TraceLeafVariant::Span {
+                    open: true,
+                    intervals: Default::default(),
+                    follows: Default::default(),
+                },
+            },
+        );
+
+        trace_leaf_id
+    }
+
+    /// Find the leaf for a span, inserting it if the span was created outside of this frame
+    fn ensure_span(
+        trace_tree: &mut TraceTree,
+        spans: &BTreeMap<SpanId, SpanInfo>,
+        thread: TraceThread,
+        id: SpanId,
+    ) -> Option<TraceLeafId> {
+        if let Ok(trace_leaf_id) = trace_tree.trace_leaf_id(&id) {
+            return Some(*trace_leaf_id);
+        }
+
+        let span = spans.get(&id)?;
+        Some(Self::insert_span(
+            trace_tree,
+            thread,
+            id,
+            span.parent_id,
+            span.metadata,
+            span.fields.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture_events;
+
+    fn span_names(trace_tree: &TraceTree) -> Vec<&'static str> {
+        let mut names = trace_tree
+            .leaves
+            .values()
+            .filter(|leaf| matches!(leaf.variant, TraceLeafVariant::Span { .. }))
+            .map(|leaf| leaf.metadata.name())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+
+    fn event_count(trace_tree: &TraceTree) -> usize {
+        trace_tree
+            .leaves
+            .values()
+            .filter(|leaf| matches!(leaf.variant, TraceLeafVariant::Event { .. }))
+            .count()
+    }
+
+    #[test]
+    fn test_frames() {
+        let events = capture_events(|| {
+            let outer = tracing::trace_span!("outer");
+            let _outer = outer.enter();
+            tracing::trace!("before frames");
+
+            for i in 0..3 {
+                let frame = tracing::trace_span!("frame", i);
+                let _frame = frame.enter();
+
+                let child = tracing::trace_span!("child");
+                let _child = child.enter();
+                tracing::trace!("inside frame");
+            }
+        });
+
+        let mut frame_capture = FrameCapture::with_root_name("frame").history(2);
+        for event in events {
+            frame_capture.push(event);
+        }
+
+        let thread = std::thread::current().id();
+        assert!(frame_capture.current(thread).is_none());
+
+        // Only the most recent frames are kept, numbered by frame index
+        let frames = frame_capture.frames(thread).collect::<Vec<_>>();
+        assert_eq!(
+            frames
+                .iter()
+                .map(|trace_tree| trace_tree.generation())
+                .collect::<Vec<_>>(),
+            [1, 2]
+        );
+
+        // Spans outside of the root aren't captured
+        for trace_tree in frames {
+            assert_eq!(span_names(trace_tree), ["child", "frame"]);
+            assert_eq!(event_count(trace_tree), 1);
+
+            let (root_id, root) = trace_tree.roots().next().unwrap();
+            assert_eq!(root.metadata.name(), "frame");
+            assert!(matches!(
+                root.variant,
+                TraceLeafVariant::Span { ref intervals, .. }
+                    if intervals.len() == 1 && intervals[0].exited.is_some()
+            ));
+            assert_eq!(trace_tree.children(root_id).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_frames_exclude_other_threads() {
+        let events = capture_events(|| {
+            let frame = tracing::trace_span!("frame");
+            let _frame = frame.enter();
+
+            let dispatch = tracing::dispatcher::get_default(|dispatch| dispatch.clone());
+            std::thread::spawn(move || {
+                tracing::dispatcher::with_default(&dispatch, || {
+                    let other = tracing::trace_span!("other thread");
+                    let _other = other.enter();
+                    tracing::trace!("on other thread");
+                })
+            })
+            .join()
+            .unwrap();
+        });
+
+        let mut frame_capture = FrameCapture::with_root_name("frame");
+        for event in events {
+            frame_capture.push(event);
+        }
+
+        let thread = std::thread::current().id();
+        let trace_tree = frame_capture.latest(thread).unwrap();
+        assert_eq!(span_names(trace_tree), ["frame"]);
+        assert_eq!(event_count(trace_tree), 0);
+
+        // The other thread never entered a root span, so it isn't tracked
+        assert_eq!(frame_capture.threads().collect::<Vec<_>>(), [thread]);
+        assert_eq!(frame_capture.frames(thread).count(), 1);
+    }
+}
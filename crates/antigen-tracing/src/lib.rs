@@ -1,27 +1,40 @@
+mod chrome_trace;
 mod fields;
+mod frame_capture;
 mod parent_id;
 mod span_id;
+mod trace_data;
+mod trace_diagnostics;
 mod trace_event;
 mod trace_field;
+mod trace_filter;
 mod trace_leaf;
 mod trace_leaf_id;
+mod trace_log;
 mod trace_receiver;
 mod trace_sender;
+mod trace_stats;
 mod trace_thread;
 mod trace_tree;
 mod trace_visitor;
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+pub use chrome_trace::*;
 pub use fields::*;
+pub use frame_capture::*;
 pub use parent_id::*;
 pub use span_id::*;
+pub use trace_diagnostics::*;
 pub use trace_event::*;
 pub use trace_field::*;
+pub use trace_filter::*;
 pub use trace_leaf::*;
 pub use trace_leaf_id::*;
+pub use trace_log::*;
 pub use trace_receiver::*;
 pub use trace_sender::*;
+pub use trace_stats::*;
 pub use trace_thread::*;
 pub use trace_tree::*;
 pub use trace_visitor::*;
@@ -33,3 +46,16 @@ pub fn next_trace_leaf_id() -> TraceLeafId {
     NEXT_TRACE_LEAF_ID.fetch_add(1, Ordering::Relaxed).into()
 }
 
+/// Run `f` under a subscriber that sends its spans and events to a [`TraceReceiver`],
+/// returning everything that was collected
+#[cfg(test)]
+pub(crate) fn capture_events<F: FnOnce()>(f: F) -> Vec<TraceEvent> {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let (sender, receiver) = TraceSender::unbounded();
+    tracing::subscriber::with_default(
+        tracing_subscriber::registry::Registry::default().with(sender),
+        f,
+    );
+    receiver.try_iter().collect()
+}
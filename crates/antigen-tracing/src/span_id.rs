@@ -6,6 +6,17 @@ use tracing::Id;
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct SpanId(NonZeroU64);
 
+impl SpanId {
+    /// Create a span ID from its raw value, which must be non-zero
+    pub fn from_u64(id: u64) -> Option<Self> {
+        NonZeroU64::new(id).map(SpanId)
+    }
+
+    pub fn into_u64(self) -> u64 {
+        self.0.get()
+    }
+}
+
 impl From<&Id> for SpanId {
     fn from(id: &Id) -> Self {
         SpanId((*id).into_non_zero_u64())
@@ -0,0 +1,288 @@
+use std::{collections::BTreeMap, time::Instant};
+
+use reflection::data::Data;
+
+use crate::{TraceField, TraceFields, TraceLeaf, TraceLeafId, TraceLeafVariant, TraceTree};
+
+impl From<&TraceField> for Data {
+    fn from(field: &TraceField) -> Self {
+        match field {
+            TraceField::Debug(value) | TraceField::Str(value) | TraceField::Error(value) => {
+                Data::String(value.clone())
+            }
+            TraceField::I64(value) => Data::I64(*value),
+            TraceField::U64(value) => Data::U64(*value),
+            TraceField::Bool(value) => Data::Bool(*value),
+        }
+    }
+}
+
+fn fields_data(fields: &TraceFields) -> Data {
+    Data::Map(
+        fields
+            .iter()
+            .map(|(name, field)| (Data::String(name.to_string()), field.into()))
+            .collect(),
+    )
+}
+
+/// Seconds since `origin`
+fn seconds(origin: Instant, instant: Instant) -> Data {
+    Data::F64(instant.saturating_duration_since(origin).as_secs_f64())
+}
+
+fn option(data: Option<Data>) -> Data {
+    Data::Option(data.map(Box::new))
+}
+
+/// Builds nested [`Data`] for a trace tree, with each span containing its children
+struct TraceDataBuilder<'a> {
+    trace_tree: &'a TraceTree,
+    children: BTreeMap<TraceLeafId, Vec<TraceLeafId>>,
+    origin: Instant,
+}
+
+impl<'a> TraceDataBuilder<'a> {
+    fn new(trace_tree: &'a TraceTree) -> Self {
+        let mut children = BTreeMap::<TraceLeafId, Vec<TraceLeafId>>::default();
+        for (id, leaf) in trace_tree.leaves.iter() {
+            if let Some(parent_id) = leaf.parent_id {
+                children.entry(parent_id).or_default().push(*id);
+            }
+        }
+
+        let origin = trace_tree
+            .leaves
+            .values()
+            .filter_map(TraceLeaf::entered)
+            .min()
+            .unwrap_or_else(Instant::now);
+
+        TraceDataBuilder {
+            trace_tree,
+            children,
+            origin,
+        }
+    }
+
+    /// Leaves without a parent, or whose parent has been pruned
+    fn roots(&self) -> Data {
+        let leaves = &self.trace_tree.leaves;
+        Data::Seq(
+            leaves
+                .iter()
+                .filter(|(_, leaf)| match leaf.parent_id {
+                    Some(parent_id) => !leaves.contains_key(&parent_id),
+                    None => true,
+                })
+                .map(|(id, leaf)| self.leaf(*id, leaf))
+                .collect(),
+        )
+    }
+
+    fn leaf(&self, id: TraceLeafId, leaf: &TraceLeaf) -> Data {
+        let metadata = leaf.metadata;
+        let mut fields = vec![
+            ("name", Data::String(metadata.name().to_string())),
+            ("target", Data::String(metadata.target().to_string())),
+            ("level", Data::String(metadata.level().to_string())),
+            (
+                "thread",
+                Data::String(
+                    leaf.thread
+                        .name()
+                        .map(ToString::to_string)
+                        .unwrap_or_else(|| format!("{:?}", leaf.thread.id())),
+                ),
+            ),
+            ("fields", fields_data(&leaf.fields)),
+        ];
+
+        match &leaf.variant {
+            TraceLeafVariant::Span {
+                open, intervals, ..
+            } => {
+                fields.push(("open", Data::Bool(*open)));
+                fields.push((
+                    "entered",
+                    option(leaf.entered().map(|entered| seconds(self.origin, entered))),
+                ));
+                fields.push((
+                    "busy",
+                    option(leaf.busy_time().map(|busy| Data::F64(busy.as_secs_f64()))),
+                ));
+                fields.push((
+                    "intervals",
+                    Data::Seq(
+                        intervals
+                            .iter()
+                            .map(|interval| {
+                                Data::Tuple(vec![
+                                    seconds(self.origin, interval.entered),
+                                    option(
+                                        interval.exited.map(|exited| seconds(self.origin, exited)),
+                                    ),
+                                ])
+                            })
+                            .collect(),
+                    ),
+                ));
+
+                let children = self
+                    .children
+                    .get(&id)
+                    .into_iter()
+                    .flatten()
+                    .map(|child| self.leaf(*child, &self.trace_tree.leaves[child]))
+                    .collect();
+                fields.push(("children", Data::Seq(children)));
+
+                Data::Struct {
+                    name: "Span",
+                    fields,
+                }
+            }
+            TraceLeafVariant::Event { instant } => {
+                fields.push(("instant", seconds(self.origin, *instant)));
+                Data::Struct {
+                    name: "Event",
+                    fields,
+                }
+            }
+        }
+    }
+}
+
+/// Nested representation of a trace tree mirroring its span hierarchy
+///
+/// Instants are given in seconds since the earliest recorded instant, and busy time in seconds.
+impl From<&TraceTree> for Data {
+    fn from(trace_tree: &TraceTree) -> Self {
+        let builder = TraceDataBuilder::new(trace_tree);
+        Data::Struct {
+            name: "TraceTree",
+            fields: vec![
+                ("generation", Data::U64(trace_tree.generation() as u64)),
+                ("roots", builder.roots()),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{capture_events, TraceReceiver};
+
+    /// Named field of a [`Data::Struct`]
+    fn field<'a>(data: &'a Data, name: &str) -> &'a Data {
+        match data {
+            Data::Struct { fields, .. } => fields
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, data)| data)
+                .unwrap_or_else(|| panic!("No field {}", name)),
+            _ => panic!("Not a struct: {:?}", data),
+        }
+    }
+
+    fn seq(data: &Data) -> &[Data] {
+        match data {
+            Data::Seq(seq) => seq,
+            _ => panic!("Not a sequence: {:?}", data),
+        }
+    }
+
+    fn struct_name(data: &Data) -> &'static str {
+        match data {
+            Data::Struct { name, .. } => name,
+            _ => panic!("Not a struct: {:?}", data),
+        }
+    }
+
+    fn traced() -> TraceTree {
+        let mut trace_tree = TraceTree::default();
+        for event in capture_events(|| {
+            let _parent = tracing::info_span!("parent", count = 3).entered();
+            let _child = tracing::trace_span!("child").entered();
+            tracing::debug!(done = true, "event");
+        }) {
+            TraceReceiver::apply(&mut trace_tree, event);
+        }
+        trace_tree
+    }
+
+    #[test]
+    fn test_field_data() {
+        assert_eq!(
+            Data::from(&TraceField::Debug("debug".into())),
+            Data::String("debug".into())
+        );
+        assert_eq!(
+            Data::from(&TraceField::Error("error".into())),
+            Data::String("error".into())
+        );
+        assert_eq!(Data::from(&TraceField::I64(-1)), Data::I64(-1));
+        assert_eq!(Data::from(&TraceField::U64(1)), Data::U64(1));
+        assert_eq!(Data::from(&TraceField::Bool(true)), Data::Bool(true));
+    }
+
+    #[test]
+    fn test_tree_data() {
+        let trace_tree = traced();
+        let data = Data::from(&trace_tree);
+        assert_eq!(struct_name(&data), "TraceTree");
+        assert_eq!(field(&data, "generation"), &Data::U64(0));
+
+        let roots = seq(field(&data, "roots"));
+        assert_eq!(roots.len(), 1);
+
+        let parent = &roots[0];
+        assert_eq!(struct_name(parent), "Span");
+        assert_eq!(field(parent, "name"), &Data::String("parent".into()));
+        assert_eq!(field(parent, "level"), &Data::String("INFO".into()));
+        assert_eq!(
+            field(parent, "fields"),
+            &Data::Map(vec![(Data::String("count".into()), Data::I64(3))])
+        );
+        assert_eq!(field(parent, "open"), &Data::Bool(false));
+
+        // Instants are relative to the first span entered
+        assert_eq!(
+            field(parent, "entered"),
+            &Data::Option(Some(Box::new(Data::F64(0.0))))
+        );
+        assert!(matches!(field(parent, "busy"), Data::Option(Some(_))));
+        assert_eq!(seq(field(parent, "intervals")).len(), 1);
+
+        let children = seq(field(parent, "children"));
+        assert_eq!(children.len(), 1);
+        assert_eq!(field(&children[0], "name"), &Data::String("child".into()));
+
+        let events = seq(field(&children[0], "children"));
+        assert_eq!(events.len(), 1);
+        assert_eq!(struct_name(&events[0]), "Event");
+        assert!(matches!(field(&events[0], "instant"), Data::F64(_)));
+        assert_eq!(
+            field(&events[0], "fields"),
+            &Data::Map(vec![
+                (Data::String("done".into()), Data::Bool(true)),
+                (Data::String("message".into()), Data::String("event".into())),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_pruned_parents() {
+        let mut trace_tree = traced();
+        let parent_id = *trace_tree.roots().next().unwrap().0;
+        trace_tree.leaves.remove(&parent_id);
+
+        // Leaves whose parent has been pruned are shown as roots
+        let data = Data::from(&trace_tree);
+        let roots = seq(field(&data, "roots"));
+        assert_eq!(roots.len(), 1);
+        assert_eq!(field(&roots[0], "name"), &Data::String("child".into()));
+        assert_eq!(seq(field(&roots[0], "children")).len(), 1);
+    }
+}
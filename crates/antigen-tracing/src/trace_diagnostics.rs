@@ -0,0 +1,46 @@
+/// Counts of unexpected events tolerated while building a [`TraceTree`](crate::TraceTree)
+///
+/// These usually mean the receiver started after spans were created,
+/// or that spans were pruned while still referenced.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct TraceDiagnostics {
+    /// Placeholder leaves created for span IDs with no known creation event
+    pub placeholder_spans: usize,
+    /// Spans and events whose parent span was unknown, kept as roots
+    pub orphaned_leaves: usize,
+    /// Follows-from annotations referring to an unknown span
+    pub unknown_follows: usize,
+    /// Enters received while a span was already entered
+    pub duplicate_enters: usize,
+    /// Exits received while a span wasn't entered
+    pub unmatched_exits: usize,
+    /// Closes received for spans that were already closed or unknown
+    pub unmatched_closes: usize,
+    /// Events discarded by the sender because its channel was full
+    pub dropped_overflow: usize,
+    /// Spans and events skipped by the sender's sampling rates
+    pub dropped_sampled: usize,
+    /// Spans and events rejected by the sender's filter
+    pub dropped_filtered: usize,
+}
+
+impl TraceDiagnostics {
+    /// Total number of anomalies, excluding dropped events
+    pub fn total(&self) -> usize {
+        self.placeholder_spans
+            + self.orphaned_leaves
+            + self.unknown_follows
+            + self.duplicate_enters
+            + self.unmatched_exits
+            + self.unmatched_closes
+    }
+
+    /// Total number of spans and events discarded before reaching the receiver
+    pub fn dropped(&self) -> usize {
+        self.dropped_overflow + self.dropped_sampled + self.dropped_filtered
+    }
+
+    pub fn clear(&mut self) {
+        *self = Default::default();
+    }
+}
@@ -0,0 +1,79 @@
+use tracing::{level_filters::LevelFilter, Metadata};
+
+/// Level and target based filter for spans and events collected by a [`TraceSender`](crate::TraceSender)
+///
+/// Targets are matched by module path prefix, with the longest matching prefix taking precedence
+/// over the default level.
+#[derive(Debug, Clone)]
+pub struct TraceFilter {
+    level: LevelFilter,
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Default for TraceFilter {
+    fn default() -> Self {
+        TraceFilter::new(LevelFilter::TRACE)
+    }
+}
+
+impl TraceFilter {
+    /// Create a filter enabling everything up to `level` for all targets
+    pub fn new(level: LevelFilter) -> Self {
+        TraceFilter {
+            level,
+            targets: Default::default(),
+        }
+    }
+
+    /// Override the level for a target and its submodules
+    pub fn target<T: Into<String>>(mut self, target: T, level: LevelFilter) -> Self {
+        let target = target.into();
+        self.targets.retain(|(existing, _)| *existing != target);
+        self.targets.push((target, level));
+        self.targets
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        self
+    }
+
+    /// Level applying to a target
+    pub fn level(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .find(|(prefix, _)| {
+                target.starts_with(prefix.as_str())
+                    && (target.len() == prefix.len() || target[prefix.len()..].starts_with("::"))
+            })
+            .map(|(_, level)| *level)
+            .unwrap_or(self.level)
+    }
+
+    pub fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.level(metadata.target())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_levels() {
+        let filter = TraceFilter::new(LevelFilter::INFO)
+            .target("game", LevelFilter::OFF)
+            .target("game::physics", LevelFilter::DEBUG)
+            .target("game", LevelFilter::WARN);
+
+        assert_eq!(filter.level("sandbox"), LevelFilter::INFO);
+        assert_eq!(filter.level("game"), LevelFilter::WARN);
+        assert_eq!(filter.level("game::render"), LevelFilter::WARN);
+
+        // The longest matching prefix wins, and only whole path segments match
+        assert_eq!(filter.level("game::physics"), LevelFilter::DEBUG);
+        assert_eq!(
+            filter.level("game::physics::broadphase"),
+            LevelFilter::DEBUG
+        );
+        assert_eq!(filter.level("game::physicsx"), LevelFilter::WARN);
+        assert_eq!(filter.level("gameplay"), LevelFilter::INFO);
+    }
+}
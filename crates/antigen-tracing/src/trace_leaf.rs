@@ -1,7 +1,36 @@
-use std::time::Instant;
-use tracing::metadata::Metadata;
+use std::time::{Duration, Instant};
+use tracing::{
+    callsite::{Callsite, Identifier},
+    field::FieldSet,
+    metadata::{Kind, Level, Metadata},
+    subscriber::Interest,
+};
 
-use super::{TraceLeafId, TraceThread, TraceFields};
+use super::{TraceFields, TraceLeafId, TraceThread};
+
+struct PlaceholderCallsite;
+
+impl Callsite for PlaceholderCallsite {
+    fn set_interest(&self, _: Interest) {}
+
+    fn metadata(&self) -> &Metadata<'_> {
+        &PLACEHOLDER_METADATA
+    }
+}
+
+static PLACEHOLDER_CALLSITE: PlaceholderCallsite = PlaceholderCallsite;
+
+/// Metadata for spans that were referenced before (or without) being created
+static PLACEHOLDER_METADATA: Metadata<'static> = Metadata::new(
+    "unknown span",
+    "antigen_tracing",
+    Level::TRACE,
+    None,
+    None,
+    None,
+    FieldSet::new(&[], Identifier(&PLACEHOLDER_CALLSITE)),
+    Kind::SPAN,
+);
 
 /// Trace tree entry
 #[derive(Debug)]
@@ -14,17 +43,91 @@ pub struct TraceLeaf {
     pub variant: TraceLeafVariant,
 }
 
+impl TraceLeaf {
+    /// Open span standing in for one whose creation was never received
+    pub fn placeholder(generation: usize, thread: TraceThread) -> Self {
+        TraceLeaf {
+            generation,
+            thread,
+            parent_id: None,
+            metadata: &PLACEHOLDER_METADATA,
+            fields: Default::default(),
+            variant: TraceLeafVariant::Span {
+                open: true,
+                intervals: Default::default(),
+                follows: Default::default(),
+            },
+        }
+    }
+
+    pub fn is_placeholder(&self) -> bool {
+        self.metadata.callsite() == PLACEHOLDER_METADATA.callsite()
+    }
+
+    /// Time spent inside a span across all of its enter / exit intervals,
+    /// or None for events and spans that haven't exited
+    pub fn busy_time(&self) -> Option<Duration> {
+        match &self.variant {
+            TraceLeafVariant::Span { intervals, .. } => {
+                if intervals.is_empty() {
+                    return None;
+                }
+
+                intervals
+                    .iter()
+                    .try_fold(Duration::default(), |acc, interval| {
+                        Some(acc + interval.duration()?)
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    /// Instant a span was first entered, or the instant of an event
+    pub fn entered(&self) -> Option<Instant> {
+        match &self.variant {
+            TraceLeafVariant::Span { intervals, .. } => {
+                intervals.first().map(|interval| interval.entered)
+            }
+            TraceLeafVariant::Event { instant } => Some(*instant),
+        }
+    }
+
+    /// Instant a span was last exited, or None if it's currently entered
+    pub fn exited(&self) -> Option<Instant> {
+        match &self.variant {
+            TraceLeafVariant::Span { intervals, .. } => {
+                intervals.last().and_then(|interval| interval.exited)
+            }
+            TraceLeafVariant::Event { instant } => Some(*instant),
+        }
+    }
+}
+
+/// Single period between entering and exiting a span
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SpanInterval {
+    pub entered: Instant,
+    pub exited: Option<Instant>,
+}
+
+impl SpanInterval {
+    pub fn duration(&self) -> Option<Duration> {
+        self.exited
+            .map(|exited| exited.saturating_duration_since(self.entered))
+    }
+}
+
 /// TraceLeaf variants
 #[derive(Debug)]
 pub enum TraceLeafVariant {
     Span {
         open: bool,
-        entered: Option<Instant>,
-        exited: Option<Instant>,
+        /// Every enter / exit pair, in order; spans may be re-entered, ex. by async tasks
+        intervals: Vec<SpanInterval>,
         follows: Vec<TraceLeafId>,
     },
     Event {
         instant: Instant,
     },
 }
-
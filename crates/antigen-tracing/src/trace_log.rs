@@ -0,0 +1,775 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Lines, Write},
+    path::Path,
+    sync::Mutex,
+    thread::{Thread, ThreadId},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{
+    callsite::{Callsite, Identifier},
+    field::FieldSet,
+    metadata::{Kind, Level, Metadata},
+    subscriber::Interest,
+};
+
+use crate::{
+    SpanId, TraceEvent, TraceEventVariant, TraceField, TraceFields, TraceReceiver, TraceThread,
+    TraceTree,
+};
+
+/// Fields as written to a trace log
+pub type TraceLogFields = BTreeMap<String, TraceField>;
+
+/// Callsite metadata, written once before the first event that uses it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceLogCallsite {
+    pub name: String,
+    pub target: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub module_path: Option<String>,
+    pub level: String,
+    pub span: bool,
+    pub fields: Vec<String>,
+}
+
+impl From<&Metadata<'_>> for TraceLogCallsite {
+    fn from(metadata: &Metadata<'_>) -> Self {
+        TraceLogCallsite {
+            name: metadata.name().to_string(),
+            target: metadata.target().to_string(),
+            file: metadata.file().map(ToString::to_string),
+            line: metadata.line(),
+            module_path: metadata.module_path().map(ToString::to_string),
+            level: metadata.level().to_string(),
+            span: metadata.is_span(),
+            fields: metadata
+                .fields()
+                .iter()
+                .map(|field| field.name().to_string())
+                .collect(),
+        }
+    }
+}
+
+/// [`TraceEventVariant`] with span IDs as integers and callsites as interned indices
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceLogEvent {
+    NewSpan {
+        id: u64,
+        parent_id: Option<u64>,
+        callsite: usize,
+        fields: TraceLogFields,
+    },
+    Record {
+        id: u64,
+        fields: TraceLogFields,
+    },
+    FollowsFrom {
+        id: u64,
+        follows: u64,
+    },
+    Event {
+        parent_id: Option<u64>,
+        callsite: usize,
+        fields: TraceLogFields,
+    },
+    Enter {
+        id: u64,
+    },
+    Exit {
+        id: u64,
+    },
+    Close {
+        id: u64,
+    },
+}
+
+/// Single line of a trace log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceLogEntry {
+    Callsite {
+        id: usize,
+        callsite: TraceLogCallsite,
+    },
+    Thread {
+        id: usize,
+        name: Option<String>,
+    },
+    Event {
+        thread: usize,
+        /// Nanoseconds since the start of the recording
+        time: u64,
+        event: TraceLogEvent,
+    },
+}
+
+/// An error reading or writing a trace log
+#[derive(Debug)]
+pub enum TraceLogError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    UnknownCallsite(usize),
+    UnknownThread(usize),
+    InvalidLevel(String),
+    InvalidSpanId,
+    ThreadPanicked,
+}
+
+impl std::fmt::Display for TraceLogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceLogError::Io(e) => e.fmt(f),
+            TraceLogError::Json(e) => e.fmt(f),
+            TraceLogError::UnknownCallsite(id) => write!(f, "Unknown callsite {}", id),
+            TraceLogError::UnknownThread(id) => write!(f, "Unknown thread {}", id),
+            TraceLogError::InvalidLevel(level) => write!(f, "Invalid level {:?}", level),
+            TraceLogError::InvalidSpanId => f.write_str("Invalid span ID"),
+            TraceLogError::ThreadPanicked => f.write_str("Replay thread panicked"),
+        }
+    }
+}
+
+impl std::error::Error for TraceLogError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TraceLogError::Io(e) => Some(e),
+            TraceLogError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for TraceLogError {
+    fn from(e: std::io::Error) -> Self {
+        TraceLogError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for TraceLogError {
+    fn from(e: serde_json::Error) -> Self {
+        TraceLogError::Json(e)
+    }
+}
+
+type CallsiteKey = (
+    &'static str,
+    &'static str,
+    Option<&'static str>,
+    Option<u32>,
+);
+
+fn fields_to_log(fields: &TraceFields) -> TraceLogFields {
+    fields
+        .iter()
+        .map(|(name, field)| (name.to_string(), field.clone()))
+        .collect()
+}
+
+/// Writes [`TraceEvent`]s to a trace log, one JSON [`TraceLogEntry`] per line
+///
+/// Callsites are interned by name, target, file and line, and threads by ID,
+/// with each written once ahead of the first event that refers to it.
+pub struct TraceLogWriter<W: Write> {
+    writer: W,
+    origin: Instant,
+    /// Most recent instant written, used to timestamp events that don't carry one
+    last: Instant,
+    callsites: HashMap<CallsiteKey, usize>,
+    threads: HashMap<ThreadId, usize>,
+}
+
+impl TraceLogWriter<BufWriter<File>> {
+    pub fn create<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Ok(TraceLogWriter::new(
+            BufWriter::new(File::create(path)?),
+            Instant::now(),
+        ))
+    }
+}
+
+impl<W: Write> TraceLogWriter<W> {
+    /// Create a writer with timestamps measured from `origin`
+    pub fn new(writer: W, origin: Instant) -> Self {
+        TraceLogWriter {
+            writer,
+            origin,
+            last: origin,
+            callsites: Default::default(),
+            threads: Default::default(),
+        }
+    }
+
+    fn write_entry(&mut self, entry: &TraceLogEntry) -> Result<(), TraceLogError> {
+        serde_json::to_writer(&mut self.writer, entry)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn callsite(&mut self, metadata: &'static Metadata<'static>) -> Result<usize, TraceLogError> {
+        let key = (
+            metadata.name(),
+            metadata.target(),
+            metadata.file(),
+            metadata.line(),
+        );
+
+        if let Some(id) = self.callsites.get(&key) {
+            return Ok(*id);
+        }
+
+        let id = self.callsites.len();
+        self.callsites.insert(key, id);
+        self.write_entry(&TraceLogEntry::Callsite {
+            id,
+            callsite: metadata.into(),
+        })?;
+        Ok(id)
+    }
+
+    fn thread(&mut self, thread: &TraceThread) -> Result<usize, TraceLogError> {
+        if let Some(id) = self.threads.get(&thread.id()) {
+            return Ok(*id);
+        }
+
+        let id = self.threads.len();
+        self.threads.insert(thread.id(), id);
+        self.write_entry(&TraceLogEntry::Thread {
+            id,
+            name: thread.name().map(ToString::to_string),
+        })?;
+        Ok(id)
+    }
+
+    /// Write a single event
+    ///
+    /// Events without an instant of their own share the timestamp of the event before them.
+    pub fn write_event(&mut self, event: &TraceEvent) -> Result<(), TraceLogError> {
+        let thread = self.thread(&event.thread)?;
+
+        let (instant, event) = match &event.variant {
+            TraceEventVariant::NewSpan {
+                id,
+                parent_id,
+                metadata,
+                fields,
+            } => (
+                None,
+                TraceLogEvent::NewSpan {
+                    id: id.into_u64(),
+                    parent_id: parent_id.map(SpanId::into_u64),
+                    callsite: self.callsite(metadata)?,
+                    fields: fields_to_log(fields),
+                },
+            ),
+            TraceEventVariant::Record { id, fields } => (
+                None,
+                TraceLogEvent::Record {
+                    id: id.into_u64(),
+                    fields: fields_to_log(fields),
+                },
+            ),
+            TraceEventVariant::FollowsFrom { id, follows } => (
+                None,
+                TraceLogEvent::FollowsFrom {
+                    id: id.into_u64(),
+                    follows: follows.into_u64(),
+                },
+            ),
+            TraceEventVariant::Event {
+                parent_id,
+                metadata,
+                fields,
+                instant,
+            } => (
+                Some(*instant),
+                TraceLogEvent::Event {
+                    parent_id: parent_id.map(SpanId::into_u64),
+                    callsite: self.callsite(metadata)?,
+                    fields: fields_to_log(fields),
+                },
+            ),
+            TraceEventVariant::Enter { id, instant } => {
+                (Some(*instant), TraceLogEvent::Enter { id: id.into_u64() })
+            }
+            TraceEventVariant::Exit { id, instant } => {
+                (Some(*instant), TraceLogEvent::Exit { id: id.into_u64() })
+            }
+            TraceEventVariant::Close { id } => (None, TraceLogEvent::Close { id: id.into_u64() }),
+        };
+
+        if let Some(instant) = instant {
+            self.last = self.last.max(instant);
+        }
+
+        let time = instant
+            .unwrap_or(self.last)
+            .saturating_duration_since(self.origin)
+            .as_nanos()
+            .min(u64::MAX as u128) as u64;
+
+        self.write_entry(&TraceLogEntry::Event {
+            thread,
+            time,
+            event,
+        })
+    }
+
+    /// Flush pending events from a receiver into a trace tree, writing each one to the log
+    pub fn record(
+        &mut self,
+        receiver: &TraceReceiver,
+        trace_tree: &mut TraceTree,
+    ) -> Result<(), TraceLogError> {
+        let mut result = Ok(());
+        receiver.flush_with(trace_tree, |event| {
+            if result.is_ok() {
+                result = self.write_event(event);
+            }
+        });
+        result
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Callsite for metadata reconstructed from a trace log
+///
+/// Never registered with a subscriber; it only exists to give replayed metadata a unique identity.
+struct ReplayCallsite {
+    metadata: Mutex<Option<&'static Metadata<'static>>>,
+}
+
+impl Callsite for ReplayCallsite {
+    fn set_interest(&self, _: Interest) {}
+
+    fn metadata(&self) -> &Metadata<'_> {
+        self.metadata
+            .lock()
+            .unwrap()
+            .expect("Replayed callsite has no metadata")
+    }
+}
+
+fn leak_str(string: String) -> &'static str {
+    Box::leak(string.into_boxed_str())
+}
+
+/// Reads [`TraceEvent`]s back from a trace log
+///
+/// Instants are reconstructed relative to the reader's origin,
+/// and recorded threads are represented by short-lived threads carrying the same names.
+///
+/// Replayed callsite metadata must be `'static` to be stored in a [`TraceTree`],
+/// so it's leaked once per callsite per reader.
+pub struct TraceLogReader<R: BufRead> {
+    lines: Lines<R>,
+    origin: Instant,
+    callsites: HashMap<usize, &'static Metadata<'static>>,
+    field_names: HashMap<String, &'static str>,
+    threads: HashMap<usize, Thread>,
+}
+
+impl TraceLogReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Ok(TraceLogReader::new(
+            BufReader::new(File::open(path)?),
+            Instant::now(),
+        ))
+    }
+}
+
+impl<R: BufRead> TraceLogReader<R> {
+    /// Create a reader that offsets recorded timestamps from `origin`
+    pub fn new(reader: R, origin: Instant) -> Self {
+        TraceLogReader {
+            lines: reader.lines(),
+            origin,
+            callsites: Default::default(),
+            field_names: Default::default(),
+            threads: Default::default(),
+        }
+    }
+
+    fn intern_callsite(
+        callsite: TraceLogCallsite,
+    ) -> Result<&'static Metadata<'static>, TraceLogError> {
+        let level = callsite
+            .level
+            .parse::<Level>()
+            .map_err(|_| TraceLogError::InvalidLevel(callsite.level.clone()))?;
+
+        let replay_callsite: &'static ReplayCallsite = Box::leak(Box::new(ReplayCallsite {
+            metadata: Mutex::new(None),
+        }));
+
+        let field_names: Vec<&'static str> = callsite.fields.into_iter().map(leak_str).collect();
+
+        let metadata: &'static Metadata<'static> = Box::leak(Box::new(Metadata::new(
+            leak_str(callsite.name),
+            leak_str(callsite.target),
+            level,
+            callsite.file.map(leak_str),
+            callsite.line,
+            callsite.module_path.map(leak_str),
+            FieldSet::new(
+                Box::leak(field_names.into_boxed_slice()),
+                Identifier(replay_callsite),
+            ),
+            if callsite.span {
+                Kind::SPAN
+            } else {
+                Kind::EVENT
+            },
+        )));
+
+        *replay_callsite.metadata.lock().unwrap() = Some(metadata);
+        Ok(metadata)
+    }
+
+    fn spawn_thread(name: Option<String>) -> Result<Thread, TraceLogError> {
+        let mut builder = std::thread::Builder::new();
+        if let Some(name) = name {
+            builder = builder.name(name);
+        }
+
+        builder
+            .spawn(std::thread::current)?
+            .join()
+            .map_err(|_| TraceLogError::ThreadPanicked)
+    }
+
+    fn metadata(&self, callsite: usize) -> Result<&'static Metadata<'static>, TraceLogError> {
+        self.callsites
+            .get(&callsite)
+            .copied()
+            .ok_or(TraceLogError::UnknownCallsite(callsite))
+    }
+
+    fn fields(&mut self, fields: TraceLogFields) -> TraceFields {
+        let field_names = &mut self.field_names;
+        fields
+            .into_iter()
+            .map(|(name, field)| {
+                let name = match field_names.get(&name) {
+                    Some(name) => *name,
+                    None => {
+                        let interned = leak_str(name.clone());
+                        field_names.insert(name, interned);
+                        interned
+                    }
+                };
+                (name, field)
+            })
+            .collect()
+    }
+
+    fn span_id(id: u64) -> Result<SpanId, TraceLogError> {
+        SpanId::from_u64(id).ok_or(TraceLogError::InvalidSpanId)
+    }
+
+    fn parent_id(id: Option<u64>) -> Result<Option<SpanId>, TraceLogError> {
+        id.map(Self::span_id).transpose()
+    }
+
+    /// Read the next event, registering any callsites and threads that precede it
+    pub fn next_event(&mut self) -> Result<Option<TraceEvent>, TraceLogError> {
+        loop {
+            let line = match self.lines.next() {
+                Some(line) => line?,
+                None => return Ok(None),
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (thread, time, event) = match serde_json::from_str(&line)? {
+                TraceLogEntry::Callsite { id, callsite } => {
+                    let metadata = Self::intern_callsite(callsite)?;
+                    self.callsites.insert(id, metadata);
+                    continue;
+                }
+                TraceLogEntry::Thread { id, name } => {
+                    let thread = Self::spawn_thread(name)?;
+                    self.threads.insert(id, thread);
+                    continue;
+                }
+                TraceLogEntry::Event {
+                    thread,
+                    time,
+                    event,
+                } => (thread, time, event),
+            };
+
+            let thread: TraceThread = self
+                .threads
+                .get(&thread)
+                .cloned()
+                .ok_or(TraceLogError::UnknownThread(thread))?
+                .into();
+            let instant = self.origin + Duration::from_nanos(time);
+
+            let variant = match event {
+                TraceLogEvent::NewSpan {
+                    id,
+                    parent_id,
+                    callsite,
+                    fields,
+                } => TraceEventVariant::NewSpan {
+                    id: Self::span_id(id)?,
+                    parent_id: Self::parent_id(parent_id)?,
+                    metadata: self.metadata(callsite)?,
+                    fields: self.fields(fields),
+                },
+                TraceLogEvent::Record { id, fields } => TraceEventVariant::Record {
+                    id: Self::span_id(id)?,
+                    fields: self.fields(fields),
+                },
+                TraceLogEvent::FollowsFrom { id, follows } => TraceEventVariant::FollowsFrom {
+                    id: Self::span_id(id)?,
+                    follows: Self::span_id(follows)?,
+                },
+                TraceLogEvent::Event {
+                    parent_id,
+                    callsite,
+                    fields,
+                } => TraceEventVariant::Event {
+                    parent_id: Self::parent_id(parent_id)?,
+                    metadata: self.metadata(callsite)?,
+                    fields: self.fields(fields),
+                    instant,
+                },
+                TraceLogEvent::Enter { id } => TraceEventVariant::Enter {
+                    id: Self::span_id(id)?,
+                    instant,
+                },
+                TraceLogEvent::Exit { id } => TraceEventVariant::Exit {
+                    id: Self::span_id(id)?,
+                    instant,
+                },
+                TraceLogEvent::Close { id } => TraceEventVariant::Close {
+                    id: Self::span_id(id)?,
+                },
+            };
+
+            return Ok(Some(TraceEvent { thread, variant }));
+        }
+    }
+
+    /// Rebuild a trace tree from the remainder of the log
+    pub fn replay(mut self, trace_tree: &mut TraceTree) -> Result<(), TraceLogError> {
+        while let Some(event) = self.next_event()? {
+            TraceReceiver::apply(trace_tree, event);
+        }
+
+        trace_tree.increment_generation();
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for TraceLogReader<R> {
+    type Item = Result<TraceEvent, TraceLogError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_event().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{capture_events, TraceLeafVariant};
+
+    /// Everything a trace log is expected to preserve about an event
+    fn describe(event: &TraceEvent, origin: Instant) -> String {
+        let metadata = |metadata: &Metadata<'_>| {
+            format!(
+                "{} {} {:?} {:?} {} {:?}",
+                metadata.name(),
+                metadata.target(),
+                metadata.file(),
+                metadata.line(),
+                metadata.level(),
+                metadata
+                    .fields()
+                    .iter()
+                    .map(|field| field.name())
+                    .collect::<Vec<_>>()
+            )
+        };
+        let time = |instant: &Instant| instant.duration_since(origin).as_nanos();
+
+        let variant = match &event.variant {
+            TraceEventVariant::NewSpan {
+                id,
+                parent_id,
+                metadata: m,
+                fields,
+            } => format!("new {:?} {:?} {} {:?}", id, parent_id, metadata(m), fields),
+            TraceEventVariant::Record { id, fields } => format!("record {:?} {:?}", id, fields),
+            TraceEventVariant::FollowsFrom { id, follows } => {
+                format!("follows {:?} {:?}", id, follows)
+            }
+            TraceEventVariant::Event {
+                parent_id,
+                metadata: m,
+                fields,
+                instant,
+            } => format!(
+                "event {:?} {} {:?} {}",
+                parent_id,
+                metadata(m),
+                fields,
+                time(instant)
+            ),
+            TraceEventVariant::Enter { id, instant } => format!("enter {:?} {}", id, time(instant)),
+            TraceEventVariant::Exit { id, instant } => format!("exit {:?} {}", id, time(instant)),
+            TraceEventVariant::Close { id } => format!("close {:?}", id),
+        };
+
+        format!("{:?}: {}", event.thread.name(), variant)
+    }
+
+    fn traced() -> Vec<TraceEvent> {
+        capture_events(|| {
+            let parent = tracing::info_span!("parent", count = 1, label = "first");
+            let _parent = parent.enter();
+
+            for i in 0..2u64 {
+                let child = tracing::debug_span!("child", index = i, done = tracing::field::Empty);
+                let _child = child.enter();
+                tracing::warn!(ratio = 0.5, "working on {}", i);
+                let done: &bool = &true;
+                child.record("done", done);
+            }
+
+            let follower = tracing::trace_span!("follower");
+            follower.follows_from(&parent);
+        })
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let origin = Instant::now();
+        let events = traced();
+
+        let mut writer = TraceLogWriter::new(vec![], origin);
+        for event in events.iter() {
+            writer.write_event(event).unwrap();
+        }
+        let log = writer.into_inner();
+
+        // Callsites and threads are written once each, ahead of the events using them
+        let entries = log
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice::<TraceLogEntry>(line).unwrap())
+            .collect::<Vec<_>>();
+        let callsites = entries
+            .iter()
+            .filter(|entry| matches!(entry, TraceLogEntry::Callsite { .. }))
+            .count();
+        let threads = entries
+            .iter()
+            .filter(|entry| matches!(entry, TraceLogEntry::Thread { .. }))
+            .count();
+        assert_eq!((callsites, threads), (4, 1));
+        assert_eq!(entries.len(), events.len() + 5);
+
+        let replayed = TraceLogReader::new(log.as_slice(), origin)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            replayed
+                .iter()
+                .map(|event| describe(event, origin))
+                .collect::<Vec<_>>(),
+            events
+                .iter()
+                .map(|event| describe(event, origin))
+                .collect::<Vec<_>>(),
+        );
+
+        // Replaying builds the same tree as receiving the original events
+        let mut trace_tree = TraceTree::default();
+        TraceLogReader::new(log.as_slice(), origin)
+            .replay(&mut trace_tree)
+            .unwrap();
+
+        let mut expected = TraceTree::default();
+        for event in events {
+            TraceReceiver::apply(&mut expected, event);
+        }
+
+        let summarize = |trace_tree: &TraceTree| {
+            trace_tree
+                .leaves
+                .values()
+                .map(|leaf| {
+                    let parent = leaf
+                        .parent_id
+                        .map(|parent_id| trace_tree.leaves[&parent_id].metadata.name());
+                    let follows = match &leaf.variant {
+                        TraceLeafVariant::Span { follows, .. } => follows.len(),
+                        TraceLeafVariant::Event { .. } => 0,
+                    };
+                    (
+                        leaf.metadata.name(),
+                        parent,
+                        leaf.busy_time(),
+                        follows,
+                        format!("{:?}", leaf.fields),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summarize(&trace_tree), summarize(&expected));
+        assert_eq!(trace_tree.diagnostics, expected.diagnostics);
+        assert_eq!(trace_tree.generation(), 1);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let read = |log: &str| {
+            TraceLogReader::new(log.as_bytes(), Instant::now())
+                .next_event()
+                .map(|event| event.is_some())
+        };
+
+        assert!(matches!(read(""), Ok(false)));
+        assert!(matches!(read("not json"), Err(TraceLogError::Json(_))));
+        assert!(matches!(
+            read(r#"{"event":{"thread":0,"time":0,"event":{"close":{"id":1}}}}"#),
+            Err(TraceLogError::UnknownThread(0))
+        ));
+        assert!(matches!(
+            read(concat!(
+                r#"{"thread":{"id":0,"name":null}}"#,
+                "\n",
+                r#"{"event":{"thread":0,"time":0,"event":{"event":{"parent_id":null,"callsite":3,"fields":{}}}}}"#
+            )),
+            Err(TraceLogError::UnknownCallsite(3))
+        ));
+        assert!(matches!(
+            read(concat!(
+                r#"{"thread":{"id":0,"name":null}}"#,
+                "\n",
+                r#"{"event":{"thread":0,"time":0,"event":{"enter":{"id":0}}}}"#
+            )),
+            Err(TraceLogError::InvalidSpanId)
+        ));
+    }
+}
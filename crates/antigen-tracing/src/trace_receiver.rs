@@ -1,155 +1,400 @@
+use std::sync::Arc;
+
 use crossbeam_channel::Receiver;
 
-use crate::TraceLeaf;
+use crate::{SpanId, SpanInterval, TraceDropCounters, TraceLeaf, TraceLeafId, TraceThread};
 
-use super::{TraceEvent, TraceTree, TraceEventVariant, TraceLeafVariant, next_trace_leaf_id};
+use super::{
+    next_trace_leaf_id, FrameCapture, TraceEvent, TraceEventVariant, TraceLeafVariant, TraceTree,
+};
 
 /// Receives [`TraceEvent`]s from a [`TraceSender`] and uses it to maintain a [`TraceTree`]
 pub struct TraceReceiver {
     receiver: Receiver<TraceEvent>,
+    drop_counters: Option<Arc<TraceDropCounters>>,
 }
 
 impl TraceReceiver {
     pub fn new(receiver: Receiver<TraceEvent>) -> Self {
-        Self { receiver }
+        Self {
+            receiver,
+            drop_counters: None,
+        }
     }
 
+    /// Report the drop counts of a [`TraceSender`] in [`TraceTree::diagnostics`]
+    pub fn with_drop_counters(mut self, drop_counters: Arc<TraceDropCounters>) -> Self {
+        self.drop_counters = Some(drop_counters);
+        self
+    }
+
+    /// Apply pending events to a trace tree
     pub fn flush(&self, trace_tree: &mut TraceTree) {
-        while let Ok(TraceEvent { thread, variant }) = self.receiver.try_recv() {
-            match variant {
-                TraceEventVariant::NewSpan {
-                    id,
-                    parent_id,
-                    metadata,
-                    fields,
-                } => {
-                    let trace_leaf_id = next_trace_leaf_id();
-                    trace_tree
-                        .span_id_to_trace_leaf_id
-                        .insert(id, trace_leaf_id);
-
-                    let parent_trace_leaf_id = parent_id
-                        .as_ref()
-                        .map(|span_id| trace_tree.span_id_to_trace_leaf_id.get(span_id).copied())
-                        .flatten();
-
-                    trace_tree.leaves.insert(
-                        trace_leaf_id,
-                        TraceLeaf {
-                            generation: trace_tree.generation(),
-                            thread,
-                            parent_id: parent_trace_leaf_id,
-                            metadata,
-                            fields,
-                            variant: TraceLeafVariant::Span {
-                                open: true,
-                                entered: None,
-                                exited: None,
-                                follows: Default::default(),
-                            },
-                        },
-                    );
-                }
-                TraceEventVariant::Record { id, fields } => {
-                    trace_tree
-                        .try_get_mut_by_span_id(&id)
-                        .unwrap()
-                        .fields
-                        .extend(fields.into_iter());
-                }
-                TraceEventVariant::FollowsFrom { id, follows } => {
-                    let follows_trace_leaf_id = *trace_tree.trace_leaf_id(&follows).unwrap();
-                    let trace_leaf = trace_tree.try_get_mut_by_span_id(&id).unwrap();
-
-                    if let TraceLeafVariant::Span { follows, .. } = &mut trace_leaf.variant {
-                        follows.push(follows_trace_leaf_id)
-                    } else {
-                        panic!("TraceLeaf with span ID {:?} has a follows-from annotation, but is not a Span", id);
+        self.flush_with(trace_tree, |_| ())
+    }
+
+    /// Apply pending events to a trace tree, inspecting each one beforehand
+    pub fn flush_with<F>(&self, trace_tree: &mut TraceTree, mut f: F)
+    where
+        F: FnMut(&TraceEvent),
+    {
+        while let Ok(event) = self.receiver.try_recv() {
+            f(&event);
+            Self::apply(trace_tree, event);
+        }
+
+        if let Some(drop_counters) = &self.drop_counters {
+            let diagnostics = &mut trace_tree.diagnostics;
+            diagnostics.dropped_overflow = drop_counters.overflow();
+            diagnostics.dropped_sampled = drop_counters.sampled();
+            diagnostics.dropped_filtered = drop_counters.filtered();
+        }
+
+        trace_tree.increment_generation()
+    }
+
+    /// Apply a single event to a trace tree
+    ///
+    /// Events referring to unknown spans are tolerated rather than treated as errors:
+    /// they're given placeholder leaves or kept as roots, and counted in [`TraceTree::diagnostics`].
+    pub fn apply(trace_tree: &mut TraceTree, TraceEvent { thread, variant }: TraceEvent) {
+        match variant {
+            TraceEventVariant::NewSpan {
+                id,
+                parent_id,
+                metadata,
+                fields,
+            } => {
+                let parent_trace_leaf_id = Self::parent_leaf_id(trace_tree, parent_id);
+
+                // A placeholder may already exist if this span was referenced out of order
+                if let Ok(trace_leaf) = trace_tree.try_get_mut_by_span_id(&id) {
+                    if trace_leaf.is_placeholder() {
+                        trace_leaf.parent_id = parent_trace_leaf_id;
+                        trace_leaf.metadata = metadata;
+                        let recorded = std::mem::replace(&mut trace_leaf.fields, fields);
+                        trace_leaf.fields.extend(recorded);
+                        return;
                     }
                 }
-                TraceEventVariant::Event {
-                    parent_id,
-                    metadata,
-                    fields,
-                    instant,
-                } => {
-                    let trace_leaf_id = next_trace_leaf_id();
-                    let parent_trace_leaf_id = parent_id
-                        .as_ref()
-                        .map(|span_id| trace_tree.span_id_to_trace_leaf_id.get(span_id).copied())
-                        .flatten();
-
-                    trace_tree.leaves.insert(
-                        trace_leaf_id,
-                        TraceLeaf {
-                            generation: trace_tree.generation(),
-                            thread,
-                            parent_id: parent_trace_leaf_id,
-                            metadata,
-                            fields,
-                            variant: TraceLeafVariant::Event { instant },
+
+                let trace_leaf_id = next_trace_leaf_id();
+                trace_tree
+                    .span_id_to_trace_leaf_id
+                    .insert(id, trace_leaf_id);
+
+                trace_tree.leaves.insert(
+                    trace_leaf_id,
+                    TraceLeaf {
+                        generation: trace_tree.generation(),
+                        thread,
+                        parent_id: parent_trace_leaf_id,
+                        metadata,
+                        fields,
+                        variant: TraceLeafVariant::Span {
+                            open: true,
+                            intervals: Default::default(),
+                            follows: Default::default(),
                         },
-                    );
-                }
-                TraceEventVariant::Enter { id, instant } => {
-                    let trace_leaf = trace_tree.try_get_mut_by_span_id(&id).unwrap();
-                    if let TraceLeafVariant::Span { entered, .. } = &mut trace_leaf.variant {
-                        if let None = entered {
-                            *entered = Some(instant);
-                        } else {
-                            panic!("TraceLeaf with ID {:?} was entered twice", id);
-                        }
-                    } else {
-                        panic!(
-                            "TraceLeaf with ID {:?} was entered, but is not a Span variant",
-                            id
-                        );
+                    },
+                );
+            }
+            TraceEventVariant::Record { id, fields } => {
+                Self::span_leaf(trace_tree, thread, id)
+                    .fields
+                    .extend(fields);
+            }
+            TraceEventVariant::FollowsFrom { id, follows } => {
+                let follows_trace_leaf_id = match trace_tree.trace_leaf_id(&follows) {
+                    Ok(follows_trace_leaf_id) => *follows_trace_leaf_id,
+                    Err(_) => {
+                        trace_tree.diagnostics.unknown_follows += 1;
+                        return;
                     }
+                };
+
+                if let TraceLeafVariant::Span { follows, .. } =
+                    &mut Self::span_leaf(trace_tree, thread, id).variant
+                {
+                    follows.push(follows_trace_leaf_id)
                 }
-                TraceEventVariant::Exit { id, instant } => {
-                    let trace_leaf = trace_tree.try_get_mut_by_span_id(&id).unwrap();
-                    if let TraceLeafVariant::Span {
-                        entered, exited, ..
-                    } = &mut trace_leaf.variant
-                    {
-                        if let None = entered {
-                            panic!(
-                                "TraceLeaf with SpanId {:?} was exited before being entered",
-                                id
-                            );
+            }
+            TraceEventVariant::Event {
+                parent_id,
+                metadata,
+                fields,
+                instant,
+            } => {
+                let trace_leaf_id = next_trace_leaf_id();
+                let parent_trace_leaf_id = Self::parent_leaf_id(trace_tree, parent_id);
+
+                trace_tree.leaves.insert(
+                    trace_leaf_id,
+                    TraceLeaf {
+                        generation: trace_tree.generation(),
+                        thread,
+                        parent_id: parent_trace_leaf_id,
+                        metadata,
+                        fields,
+                        variant: TraceLeafVariant::Event { instant },
+                    },
+                );
+            }
+            TraceEventVariant::Enter { id, instant } => {
+                let duplicate = match &mut Self::span_leaf(trace_tree, thread, id).variant {
+                    TraceLeafVariant::Span { intervals, .. } => match intervals.last() {
+                        Some(SpanInterval { exited: None, .. }) => true,
+                        _ => {
+                            intervals.push(SpanInterval {
+                                entered: instant,
+                                exited: None,
+                            });
+                            false
                         }
+                    },
+                    TraceLeafVariant::Event { .. } => false,
+                };
 
-                        if let None = exited {
+                if duplicate {
+                    trace_tree.diagnostics.duplicate_enters += 1;
+                }
+            }
+            TraceEventVariant::Exit { id, instant } => {
+                let unmatched = match &mut Self::span_leaf(trace_tree, thread, id).variant {
+                    TraceLeafVariant::Span { intervals, .. } => match intervals.last_mut() {
+                        Some(SpanInterval {
+                            exited: exited @ None,
+                            ..
+                        }) => {
                             *exited = Some(instant);
-                        } else {
-                            panic!("TraceLeaf with ID {:?} was exited twice", id);
+                            false
                         }
-                    } else {
-                        panic!(
-                            "TraceLeaf with ID {:?} was exited, but is not a Span variant",
-                            id
-                        );
-                    }
+                        _ => true,
+                    },
+                    TraceLeafVariant::Event { .. } => false,
+                };
+
+                if unmatched {
+                    trace_tree.diagnostics.unmatched_exits += 1;
                 }
-                TraceEventVariant::Close { id } => {
-                    let trace_leaf = trace_tree.try_get_mut_by_span_id(&id).unwrap();
-                    if let TraceLeafVariant::Span { open, .. } = &mut trace_leaf.variant {
-                        if *open {
-                            *open = false;
-                        } else {
-                            panic!("TraceLeaf with span ID {:?} was closed twice", id)
-                        }
-                    } else {
-                        panic!(
-                            "TraceLeaf with span ID {:?} was exited, but is not a Span variant",
-                            id
-                        );
-                    }
+            }
+            TraceEventVariant::Close { id } => {
+                let closed = match trace_tree.try_get_mut_by_span_id(&id) {
+                    Ok(TraceLeaf {
+                        variant: TraceLeafVariant::Span { open, .. },
+                        ..
+                    }) => std::mem::replace(open, false),
+                    _ => false,
+                };
+
+                if !closed {
+                    trace_tree.diagnostics.unmatched_closes += 1;
                 }
             }
         }
+    }
 
-        trace_tree.increment_generation()
+    /// Resolve the leaf ID of a parent span, counting unknown parents as orphans
+    fn parent_leaf_id(
+        trace_tree: &mut TraceTree,
+        parent_id: Option<SpanId>,
+    ) -> Option<TraceLeafId> {
+        let parent_id = parent_id?;
+        match trace_tree.trace_leaf_id(&parent_id) {
+            Ok(trace_leaf_id) => Some(*trace_leaf_id),
+            Err(_) => {
+                trace_tree.diagnostics.orphaned_leaves += 1;
+                None
+            }
+        }
+    }
+
+    /// Find the leaf for a span, inserting a placeholder if it's unknown
+    fn span_leaf(trace_tree: &mut TraceTree, thread: TraceThread, id: SpanId) -> &mut TraceLeaf {
+        let trace_leaf_id = match trace_tree.trace_leaf_id(&id) {
+            Ok(trace_leaf_id) => *trace_leaf_id,
+            Err(_) => {
+                let trace_leaf_id = next_trace_leaf_id();
+                let placeholder = TraceLeaf::placeholder(trace_tree.generation(), thread);
+                trace_tree
+                    .span_id_to_trace_leaf_id
+                    .insert(id, trace_leaf_id);
+                trace_tree.leaves.insert(trace_leaf_id, placeholder);
+                trace_tree.diagnostics.placeholder_spans += 1;
+                trace_leaf_id
+            }
+        };
+
+        trace_tree.leaves.get_mut(&trace_leaf_id).unwrap()
+    }
+
+    /// Drain pending events into a [`FrameCapture`] instead of a single [`TraceTree`]
+    pub fn flush_frames(&self, frame_capture: &mut FrameCapture) {
+        for event in self.try_iter() {
+            frame_capture.push(event);
+        }
+    }
+
+    /// Iterate over pending events without blocking, for consumers other than [`TraceTree`]
+    pub fn try_iter(&self) -> impl Iterator<Item = TraceEvent> + '_ {
+        self.receiver.try_iter()
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use tracing::Metadata;
+
+    use super::*;
+    use crate::{capture_events, TraceDiagnostics, TraceField, TraceFields};
+
+    fn id(id: u64) -> SpanId {
+        SpanId::from_u64(id).unwrap()
+    }
+
+    fn fields(name: &'static str, value: u64) -> TraceFields {
+        std::iter::once((name, TraceField::U64(value))).collect()
+    }
+
+    /// Span and event metadata taken from real callsites
+    fn metadata() -> (&'static Metadata<'static>, &'static Metadata<'static>) {
+        let events = capture_events(|| {
+            let _span = tracing::trace_span!("span").entered();
+            tracing::trace!("event");
+        });
+
+        let span = events.iter().find_map(|event| match event.variant {
+            TraceEventVariant::NewSpan { metadata, .. } => Some(metadata),
+            _ => None,
+        });
+        let event = events.iter().find_map(|event| match event.variant {
+            TraceEventVariant::Event { metadata, .. } => Some(metadata),
+            _ => None,
+        });
+        (span.unwrap(), event.unwrap())
+    }
+
+    fn apply(trace_tree: &mut TraceTree, events: Vec<TraceEvent>) {
+        for event in events {
+            TraceReceiver::apply(trace_tree, event);
+        }
+    }
+
+    #[test]
+    fn test_apply_in_order() {
+        let mut trace_tree = TraceTree::default();
+        apply(
+            &mut trace_tree,
+            capture_events(|| {
+                let _parent = tracing::trace_span!("parent").entered();
+                let _child = tracing::trace_span!("child").entered();
+                tracing::trace!("event");
+            }),
+        );
+
+        assert_eq!(trace_tree.diagnostics, TraceDiagnostics::default());
+        assert_eq!(trace_tree.leaves.len(), 3);
+
+        let (parent_id, parent) = trace_tree.roots().next().unwrap();
+        assert_eq!(parent.metadata.name(), "parent");
+        assert!(matches!(
+            parent.variant,
+            TraceLeafVariant::Span { open: false, .. }
+        ));
+
+        let (child_id, child) = trace_tree.children(parent_id).next().unwrap();
+        assert_eq!(child.metadata.name(), "child");
+        assert!(child.busy_time().is_some());
+        assert_eq!(trace_tree.children(child_id).count(), 1);
+    }
+
+    #[test]
+    fn test_apply_before_new_span() {
+        let (span, _) = metadata();
+        let mut trace_tree = TraceTree::default();
+        apply(
+            &mut trace_tree,
+            vec![
+                TraceEvent::record(id(1), fields("recorded", 1)),
+                TraceEvent::enter(id(1)),
+                TraceEvent::new_span(id(1), None, span, fields("created", 2)),
+                TraceEvent::exit(id(1)),
+            ],
+        );
+
+        // The placeholder is filled in rather than duplicated, keeping earlier data
+        assert_eq!(trace_tree.leaves.len(), 1);
+        let leaf = trace_tree.try_get_by_span_id(&id(1)).unwrap();
+        assert!(!leaf.is_placeholder());
+        assert_eq!(leaf.metadata.name(), "span");
+        assert_eq!(
+            leaf.fields.keys().copied().collect::<Vec<_>>(),
+            ["created", "recorded"]
+        );
+        assert!(leaf.busy_time().is_some());
+
+        assert_eq!(
+            trace_tree.diagnostics,
+            TraceDiagnostics {
+                placeholder_spans: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_apply_unknown_spans() {
+        let (span, event) = metadata();
+        let mut trace_tree = TraceTree::default();
+        apply(
+            &mut trace_tree,
+            vec![
+                // Parents that were never created
+                TraceEvent::new_span(id(1), Some(id(9)), span, Default::default()),
+                TraceEvent::event(Some(id(9)), event, Default::default()),
+                TraceEvent::event(Some(id(1)), event, Default::default()),
+                // Spans that are referenced without being created
+                TraceEvent::follows_from(id(1), id(9)),
+                TraceEvent::exit(id(2)),
+                TraceEvent::close(id(3)),
+                // Repeated enters, exits and closes
+                TraceEvent::enter(id(1)),
+                TraceEvent::enter(id(1)),
+                TraceEvent::exit(id(1)),
+                TraceEvent::close(id(1)),
+                TraceEvent::close(id(1)),
+            ],
+        );
+
+        assert_eq!(
+            trace_tree.diagnostics,
+            TraceDiagnostics {
+                placeholder_spans: 1,
+                orphaned_leaves: 2,
+                unknown_follows: 1,
+                duplicate_enters: 1,
+                unmatched_exits: 1,
+                unmatched_closes: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(trace_tree.diagnostics.total(), 8);
+
+        // Orphans are kept as roots, alongside the placeholder for span 2
+        let roots = trace_tree
+            .roots()
+            .map(|(_, leaf)| (leaf.metadata.name(), leaf.is_placeholder()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            roots,
+            [
+                ("span", false),
+                (event.name(), false),
+                ("unknown span", true)
+            ]
+        );
+
+        let span_leaf_id = trace_tree.trace_leaf_id(&id(1)).unwrap();
+        assert_eq!(trace_tree.children(span_leaf_id).count(), 1);
+    }
+}
@@ -1,17 +1,248 @@
-use crossbeam_channel::Sender;
-use tracing::{Id, Subscriber, span::{Attributes, Record}};
-use tracing_subscriber::{Layer, layer::Context};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
 
-use crate::{TraceEvent, ParentId, Fields};
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use tracing::{
+    callsite::Identifier,
+    span::{Attributes, Record},
+    Id, Metadata, Subscriber,
+};
+use tracing_subscriber::{layer::Context, Layer};
+
+use crate::{Fields, ParentId, SpanId, TraceEvent, TraceFilter, TraceReceiver};
+
+/// What to discard when a bounded [`TraceSender`] channel is full
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Discard the event being sent
+    DropNewest,
+    /// Discard the oldest queued event to make room
+    DropOldest,
+}
+
+impl Default for DropPolicy {
+    fn default() -> Self {
+        DropPolicy::DropNewest
+    }
+}
+
+/// Running counts of events discarded by a [`TraceSender`], shared with its [`TraceReceiver`]
+#[derive(Debug, Default)]
+pub struct TraceDropCounters {
+    overflow: AtomicUsize,
+    sampled: AtomicUsize,
+    filtered: AtomicUsize,
+}
+
+impl TraceDropCounters {
+    /// Events discarded because the channel was full
+    pub fn overflow(&self) -> usize {
+        self.overflow.load(Ordering::Relaxed)
+    }
+
+    /// Spans and events skipped by sampling
+    pub fn sampled(&self) -> usize {
+        self.sampled.load(Ordering::Relaxed)
+    }
+
+    /// Spans and events rejected by the [`TraceFilter`]
+    pub fn filtered(&self) -> usize {
+        self.filtered.load(Ordering::Relaxed)
+    }
+
+    fn increment(&self, reason: DropReason) {
+        match reason {
+            DropReason::Overflow => &self.overflow,
+            DropReason::Sampled => &self.sampled,
+            DropReason::Filtered => &self.filtered,
+        }
+        .fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Why a span was dropped, inherited by its children
+#[derive(Debug, Copy, Clone)]
+enum DropReason {
+    Overflow,
+    Sampled,
+    Filtered,
+}
+
+/// Keep one in every `rate` spans or events from a callsite
+#[derive(Debug, Default)]
+struct Sampling {
+    callsites: HashMap<Identifier, usize>,
+    names: HashMap<&'static str, usize>,
+    counts: HashMap<Identifier, usize>,
+}
+
+impl Sampling {
+    fn is_empty(&self) -> bool {
+        self.callsites.is_empty() && self.names.is_empty()
+    }
+
+    fn sample(&mut self, metadata: &Metadata<'_>) -> bool {
+        let callsite = metadata.callsite();
+        let rate = match self
+            .callsites
+            .get(&callsite)
+            .or_else(|| self.names.get(metadata.name()))
+        {
+            Some(rate) if *rate > 1 => *rate,
+            _ => return true,
+        };
+
+        // Counts wrap at the rate, keeping the first of every `rate` occurrences
+        let count = self.counts.entry(callsite).or_default();
+        let keep = *count == 0;
+        *count = (*count + 1) % rate;
+        keep
+    }
+}
 
 /// [`tracing-subscriber::layer::Layer`] for collecting [`TraceEvent`]s and sending them to a [`TraceReceiver`]
+///
+/// By default every span and event is sent over an unbounded channel.
+/// A bounded channel with a [`DropPolicy`], a [`TraceFilter`] and per-callsite sampling
+/// can be used to cap memory usage when tracing stays enabled for long periods.
+///
+/// Children of dropped spans are dropped along with them, and all drops are counted
+/// in the shared [`TraceDropCounters`].
 pub struct TraceSender {
     sender: Sender<TraceEvent>,
+    /// Receiving end used to discard queued events under [`DropPolicy::DropOldest`]
+    oldest: Option<Receiver<TraceEvent>>,
+    filter: TraceFilter,
+    sampling: Mutex<Sampling>,
+    dropped_spans: Mutex<HashMap<SpanId, DropReason>>,
+    counters: Arc<TraceDropCounters>,
 }
 
 impl TraceSender {
     pub fn new(sender: Sender<TraceEvent>) -> Self {
-        Self { sender }
+        Self {
+            sender,
+            oldest: None,
+            filter: Default::default(),
+            sampling: Default::default(),
+            dropped_spans: Default::default(),
+            counters: Default::default(),
+        }
+    }
+
+    /// Create a sender and receiver connected by an unbounded channel
+    pub fn unbounded() -> (Self, TraceReceiver) {
+        let (s, r) = crossbeam_channel::unbounded();
+        let sender = TraceSender::new(s);
+        let receiver = TraceReceiver::new(r).with_drop_counters(sender.drop_counters());
+        (sender, receiver)
+    }
+
+    /// Create a sender and receiver connected by a channel holding at most `capacity` events
+    pub fn bounded(capacity: usize, policy: DropPolicy) -> (Self, TraceReceiver) {
+        let (s, r) = crossbeam_channel::bounded(capacity);
+        let mut sender = TraceSender::new(s);
+        if let DropPolicy::DropOldest = policy {
+            sender.oldest = Some(r.clone());
+        }
+        let receiver = TraceReceiver::new(r).with_drop_counters(sender.drop_counters());
+        (sender, receiver)
+    }
+
+    /// Only collect spans and events enabled by `filter`
+    pub fn filter(mut self, filter: TraceFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Keep one in every `rate` spans or events from a specific callsite
+    pub fn sample_callsite(self, callsite: Identifier, rate: usize) -> Self {
+        self.sampling
+            .lock()
+            .unwrap()
+            .callsites
+            .insert(callsite, rate);
+        self
+    }
+
+    /// Keep one in every `rate` spans or events from each callsite with the given name
+    ///
+    /// Rates set with [`TraceSender::sample_callsite`] take precedence.
+    pub fn sample_name(self, name: &'static str, rate: usize) -> Self {
+        self.sampling.lock().unwrap().names.insert(name, rate);
+        self
+    }
+
+    pub fn drop_counters(&self) -> Arc<TraceDropCounters> {
+        self.counters.clone()
+    }
+
+    /// Decide whether to collect a span or event, given its parent
+    fn admit(&self, metadata: &Metadata<'_>, parent_id: Option<SpanId>) -> Option<DropReason> {
+        if !self.filter.enabled(metadata) {
+            return Some(DropReason::Filtered);
+        }
+
+        if let Some(parent_id) = parent_id {
+            if let Some(reason) = self.dropped_reason(&parent_id) {
+                return Some(reason);
+            }
+        }
+
+        let mut sampling = self.sampling.lock().unwrap();
+        if !sampling.is_empty() && !sampling.sample(metadata) {
+            return Some(DropReason::Sampled);
+        }
+
+        None
+    }
+
+    fn dropped_reason(&self, id: &SpanId) -> Option<DropReason> {
+        self.dropped_spans.lock().unwrap().get(id).copied()
+    }
+
+    fn is_dropped(&self, id: &SpanId) -> bool {
+        self.dropped_spans.lock().unwrap().contains_key(id)
+    }
+
+    fn drop_span(&self, id: SpanId, reason: DropReason) {
+        self.counters.increment(reason);
+        self.dropped_spans.lock().unwrap().insert(id, reason);
+    }
+
+    /// Send an event according to the drop policy, returning false if it was discarded
+    fn send(&self, mut event: TraceEvent) -> bool {
+        loop {
+            match self.sender.try_send(event) {
+                Ok(()) => return true,
+                Err(TrySendError::Full(rejected)) => match &self.oldest {
+                    Some(oldest) => {
+                        // Make room by discarding the oldest event, then try again
+                        if oldest.try_recv().is_ok() {
+                            self.counters.increment(DropReason::Overflow);
+                        }
+                        event = rejected;
+                    }
+                    None => {
+                        self.counters.increment(DropReason::Overflow);
+                        return false;
+                    }
+                },
+                // The receiver is gone, so there's nobody to count drops for
+                Err(TrySendError::Disconnected(_)) => return false,
+            }
+        }
+    }
+
+    fn send_span_event(&self, id: &SpanId, event: impl FnOnce() -> TraceEvent) {
+        if !self.is_dropped(id) {
+            self.send(event());
+        }
     }
 }
 
@@ -23,51 +254,199 @@ where
         let span_id = id.into();
         let parent_id = attrs.parent_id(ctx);
         let metadata = attrs.metadata();
-        let fields = attrs.tracing_fields();
 
-        self.sender
-            .send(TraceEvent::new_span(span_id, parent_id, metadata, fields))
-            .unwrap();
+        if let Some(reason) = self.admit(metadata, parent_id) {
+            self.drop_span(span_id, reason);
+            return;
+        }
+
+        let fields = attrs.tracing_fields();
+        if !self.send(TraceEvent::new_span(span_id, parent_id, metadata, fields)) {
+            // Suppress the rest of the span rather than leaving the receiver with placeholders
+            self.dropped_spans
+                .lock()
+                .unwrap()
+                .insert(span_id, DropReason::Overflow);
+        }
     }
 
     fn on_record(&self, id: &Id, record: &Record<'_>, _ctx: Context<'_, S>) {
         let span_id = id.into();
-        let fields = record.tracing_fields();
-        self.sender
-            .send(TraceEvent::record(span_id, fields))
-            .unwrap();
+        self.send_span_event(&span_id, || {
+            TraceEvent::record(span_id, record.tracing_fields())
+        });
     }
 
     fn on_follows_from(&self, id: &Id, follows: &Id, _ctx: Context<'_, S>) {
         let span_id = id.into();
         let follows_id = follows.into();
-        self.sender
-            .send(TraceEvent::follows_from(span_id, follows_id))
-            .unwrap();
+        if !self.is_dropped(&follows_id) {
+            self.send_span_event(&span_id, || TraceEvent::follows_from(span_id, follows_id));
+        }
     }
 
     fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
         let parent_id = event.parent_id(ctx);
         let metadata = event.metadata();
+
+        if let Some(reason) = self.admit(metadata, parent_id) {
+            self.counters.increment(reason);
+            return;
+        }
+
         let fields = event.tracing_fields();
-        self.sender
-            .send(TraceEvent::event(parent_id, metadata, fields))
-            .unwrap();
+        self.send(TraceEvent::event(parent_id, metadata, fields));
     }
 
     fn on_enter(&self, id: &Id, _ctx: Context<'_, S>) {
         let span_id = id.into();
-        self.sender.send(TraceEvent::enter(span_id)).unwrap();
+        self.send_span_event(&span_id, || TraceEvent::enter(span_id));
     }
 
     fn on_exit(&self, id: &Id, _ctx: Context<'_, S>) {
         let span_id = id.into();
-        self.sender.send(TraceEvent::exit(span_id)).unwrap();
+        self.send_span_event(&span_id, || TraceEvent::exit(span_id));
     }
 
     fn on_close(&self, id: Id, _ctx: Context<'_, S>) {
         let span_id = id.into();
-        self.sender.send(TraceEvent::close(span_id)).unwrap();
+
+        // Span IDs are reused once closed, so forget dropped spans here
+        if self
+            .dropped_spans
+            .lock()
+            .unwrap()
+            .remove(&span_id)
+            .is_none()
+        {
+            self.send(TraceEvent::close(span_id));
+        }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use tracing::level_filters::LevelFilter;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::{TraceDiagnostics, TraceField, TraceTree};
+
+    /// Run `f` under `sender`, then flush everything it collected into a new tree
+    fn collect<F: FnOnce()>(sender: TraceSender, receiver: TraceReceiver, f: F) -> TraceTree {
+        tracing::subscriber::with_default(
+            tracing_subscriber::registry::Registry::default().with(sender),
+            f,
+        );
+
+        let mut trace_tree = TraceTree::default();
+        receiver.flush(&mut trace_tree);
+        trace_tree
+    }
+
+    /// Span names and event messages of a tree, in creation order
+    fn names(trace_tree: &TraceTree) -> Vec<String> {
+        trace_tree
+            .leaves
+            .values()
+            .map(|leaf| match leaf.fields.get("message") {
+                Some(TraceField::Debug(message)) => message.clone(),
+                _ => leaf.metadata.name().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_filter() {
+        let (sender, receiver) = TraceSender::unbounded();
+        let sender = sender.filter(TraceFilter::new(LevelFilter::INFO));
+
+        let trace_tree = collect(sender, receiver, || {
+            let _kept = tracing::info_span!("kept").entered();
+            tracing::info!("kept event");
+            tracing::trace!("filtered event");
+
+            // Children of a filtered span are dropped with it, regardless of level
+            let _filtered = tracing::trace_span!("filtered").entered();
+            tracing::info!("child of filtered span");
+        });
+
+        assert_eq!(names(&trace_tree), ["kept", "kept event"]);
+        assert_eq!(trace_tree.diagnostics.total(), 0);
+        assert_eq!(
+            trace_tree.diagnostics,
+            TraceDiagnostics {
+                dropped_filtered: 3,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_sampling() {
+        let (sender, receiver) = TraceSender::unbounded();
+        let sender = sender.sample_name("sampled", 3);
+
+        let trace_tree = collect(sender, receiver, || {
+            for _ in 0..7 {
+                let _sampled = tracing::trace_span!("sampled").entered();
+                tracing::trace!("child of sampled span");
+            }
+        });
+
+        assert_eq!(
+            names(&trace_tree),
+            [
+                "sampled",
+                "child of sampled span",
+                "sampled",
+                "child of sampled span",
+                "sampled",
+                "child of sampled span"
+            ]
+        );
+        assert_eq!(trace_tree.diagnostics.dropped_sampled, 8);
+        assert_eq!(trace_tree.diagnostics.dropped(), 8);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let emit = || {
+            for i in 0..5 {
+                tracing::trace!("{}", i);
+            }
+        };
+
+        let (sender, receiver) = TraceSender::bounded(2, DropPolicy::DropNewest);
+        let counters = sender.drop_counters();
+        let trace_tree = collect(sender, receiver, emit);
+        assert_eq!(names(&trace_tree), ["0", "1"]);
+        assert_eq!(trace_tree.diagnostics.dropped_overflow, 3);
+        assert_eq!(counters.overflow(), 3);
+
+        let (sender, receiver) = TraceSender::bounded(2, DropPolicy::DropOldest);
+        let trace_tree = collect(sender, receiver, emit);
+        assert_eq!(names(&trace_tree), ["3", "4"]);
+        assert_eq!(trace_tree.diagnostics.dropped_overflow, 3);
+    }
+
+    #[test]
+    fn test_drop_counters_without_receiver_counters() {
+        let (s, r) = crossbeam_channel::unbounded();
+        let sender = TraceSender::new(s).filter(TraceFilter::new(LevelFilter::OFF));
+        let counters = sender.drop_counters();
+
+        // Counts are only reported in the tree by receivers sharing them
+        let trace_tree = collect(sender, TraceReceiver::new(r), || {
+            tracing::error!("filtered")
+        });
+        assert_eq!(counters.filtered(), 1);
+        assert_eq!(trace_tree.diagnostics.dropped(), 0);
+
+        let (s, r) = crossbeam_channel::unbounded();
+        let sender = TraceSender::new(s).filter(TraceFilter::new(LevelFilter::OFF));
+        let receiver = TraceReceiver::new(r).with_drop_counters(sender.drop_counters());
+        let trace_tree = collect(sender, receiver, || tracing::error!("filtered"));
+        assert_eq!(trace_tree.diagnostics.dropped_filtered, 1);
+    }
+}
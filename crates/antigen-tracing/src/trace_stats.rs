@@ -0,0 +1,399 @@
+use std::{collections::HashMap, time::Duration};
+
+use tracing::{callsite::Identifier, Metadata};
+
+use crate::{TraceLeafId, TraceLeafVariant, TraceTree};
+
+/// Histogram sub-buckets per power of two
+const SUB_BUCKETS: usize = 4;
+
+/// Powers of two covered by a [`DurationHistogram`], from 1ns to roughly 18 minutes
+const OCTAVES: usize = 40;
+
+/// Log-scale histogram of durations, used to approximate percentiles in constant space
+///
+/// Each power of two is split into [`SUB_BUCKETS`] linear buckets,
+/// bounding the relative error of a percentile to roughly 1 / SUB_BUCKETS.
+#[derive(Debug, Clone)]
+pub struct DurationHistogram {
+    buckets: Vec<u64>,
+    count: u64,
+}
+
+impl Default for DurationHistogram {
+    fn default() -> Self {
+        DurationHistogram {
+            buckets: vec![0; OCTAVES * SUB_BUCKETS],
+            count: 0,
+        }
+    }
+}
+
+impl DurationHistogram {
+    fn bucket(nanos: u64) -> usize {
+        if nanos < 2 {
+            return 0;
+        }
+
+        let octave = 63 - nanos.leading_zeros() as usize;
+        let sub = (((nanos - (1 << octave)) as u128 * SUB_BUCKETS as u128) >> octave) as usize;
+        (octave * SUB_BUCKETS + sub).min(OCTAVES * SUB_BUCKETS - 1)
+    }
+
+    /// Inclusive upper bound of a bucket in nanoseconds
+    fn upper_bound(bucket: usize) -> u64 {
+        let octave = bucket / SUB_BUCKETS;
+        let sub = (bucket % SUB_BUCKETS) as u64 + 1;
+        ((1u64 << octave) + ((sub << octave) / SUB_BUCKETS as u64) - 1).max(1 << octave)
+    }
+
+    pub fn record(&mut self, duration: Duration) {
+        let nanos = duration.as_nanos().min(u64::MAX as u128) as u64;
+        self.buckets[Self::bucket(nanos)] += 1;
+        self.count += 1;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Approximate duration below which `percentile` (0..=100) of samples fall
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = ((percentile / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (i, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(Duration::from_nanos(Self::upper_bound(i)));
+            }
+        }
+
+        None
+    }
+
+    pub fn clear(&mut self) {
+        for bucket in self.buckets.iter_mut() {
+            *bucket = 0;
+        }
+        self.count = 0;
+    }
+}
+
+/// Running busy-time statistics for a single callsite
+#[derive(Debug, Clone)]
+pub struct CallsiteStats {
+    pub metadata: &'static Metadata<'static>,
+    count: u64,
+    total: Duration,
+    min: Option<Duration>,
+    max: Duration,
+    histogram: DurationHistogram,
+}
+
+impl CallsiteStats {
+    pub fn new(metadata: &'static Metadata<'static>) -> Self {
+        CallsiteStats {
+            metadata,
+            count: 0,
+            total: Duration::default(),
+            min: None,
+            max: Duration::default(),
+            histogram: Default::default(),
+        }
+    }
+
+    pub fn record(&mut self, duration: Duration) {
+        self.count += 1;
+        self.total += duration;
+        self.min = Some(self.min.map_or(duration, |min| min.min(duration)));
+        self.max = self.max.max(duration);
+        self.histogram.record(duration);
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.metadata.name()
+    }
+
+    pub fn module_path(&self) -> Option<&'static str> {
+        self.metadata.module_path()
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        Some(self.max).filter(|_| self.count > 0)
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                self.total.as_secs_f64() / self.count as f64,
+            ))
+        }
+    }
+
+    /// Approximate percentile, clamped to the observed min and max
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        let value = self.histogram.percentile(percentile)?;
+        Some(value.max(self.min?).min(self.max))
+    }
+
+    pub fn p50(&self) -> Option<Duration> {
+        self.percentile(50.0)
+    }
+
+    pub fn p95(&self) -> Option<Duration> {
+        self.percentile(95.0)
+    }
+
+    pub fn p99(&self) -> Option<Duration> {
+        self.percentile(99.0)
+    }
+
+    pub fn histogram(&self) -> &DurationHistogram {
+        &self.histogram
+    }
+}
+
+/// Per-callsite span statistics, accumulated across [`TraceTree`] generations
+///
+/// Each enter / exit interval of a span is recorded once, after it has exited,
+/// so the tree can be pruned between updates without losing the aggregates.
+#[derive(Debug, Default, Clone)]
+pub struct TraceStats {
+    callsites: HashMap<Identifier, CallsiteStats>,
+    /// Number of intervals already recorded for each span
+    recorded: HashMap<TraceLeafId, usize>,
+}
+
+impl TraceStats {
+    /// Record the busy time of any span intervals that have exited since the last update
+    pub fn update(&mut self, trace_tree: &TraceTree) {
+        for (id, leaf) in trace_tree.leaves.iter() {
+            let intervals = match &leaf.variant {
+                TraceLeafVariant::Span { intervals, .. } => intervals,
+                _ => continue,
+            };
+
+            // Only the last interval can still be open
+            let recorded = self.recorded.get(id).copied().unwrap_or(0);
+            let mut count = 0;
+            for interval in intervals.iter().skip(recorded) {
+                match interval.duration() {
+                    Some(duration) => self.record(leaf.metadata, duration),
+                    None => break,
+                }
+                count += 1;
+            }
+            self.recorded.insert(*id, recorded + count);
+        }
+
+        // Forget leaves that have been pruned, since they can't be seen again
+        let leaves = &trace_tree.leaves;
+        self.recorded.retain(|id, _| leaves.contains_key(id));
+    }
+
+    /// Record a single span duration
+    pub fn record(&mut self, metadata: &'static Metadata<'static>, duration: Duration) {
+        self.callsites
+            .entry(metadata.callsite())
+            .or_insert_with(|| CallsiteStats::new(metadata))
+            .record(duration)
+    }
+
+    pub fn get(&self, callsite: &Identifier) -> Option<&CallsiteStats> {
+        self.callsites.get(callsite)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Identifier, &CallsiteStats)> {
+        self.callsites.iter()
+    }
+
+    /// Statistics for callsites with the given module path and name
+    ///
+    /// More than one callsite can share a name within a module, ex. spans created in different functions.
+    pub fn query<'a>(
+        &'a self,
+        module_path: &'a str,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a CallsiteStats> {
+        self.callsites
+            .values()
+            .filter(move |stats| stats.module_path() == Some(module_path) && stats.name() == name)
+    }
+
+    /// Statistics for all callsites within a module path, including submodules
+    pub fn module<'a>(&'a self, module_path: &'a str) -> impl Iterator<Item = &'a CallsiteStats> {
+        self.callsites
+            .values()
+            .filter(move |stats| match stats.module_path() {
+                Some(path) if path.starts_with(module_path) => {
+                    let rest = &path[module_path.len()..];
+                    rest.is_empty() || rest.starts_with("::")
+                }
+                _ => false,
+            })
+    }
+
+    pub fn clear(&mut self) {
+        self.callsites.clear();
+        self.recorded.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{capture_events, TraceReceiver};
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    /// Metadata of a real span callsite, since [`CallsiteStats`] needs a static reference
+    fn work_metadata() -> &'static Metadata<'static> {
+        let mut metadata = None;
+        capture_events(|| metadata = tracing::trace_span!("work").metadata());
+        metadata.unwrap()
+    }
+
+    fn trace_work(trace_tree: &mut TraceTree) {
+        for event in capture_events(|| {
+            let _work = tracing::trace_span!("work").entered();
+        }) {
+            TraceReceiver::apply(trace_tree, event);
+        }
+    }
+
+    #[test]
+    fn test_histogram_percentiles() {
+        let mut histogram = DurationHistogram::default();
+        assert_eq!(histogram.percentile(50.0), None);
+
+        for millis in 1..=100 {
+            histogram.record(ms(millis));
+        }
+        assert_eq!(histogram.count(), 100);
+
+        // Percentiles are bucket upper bounds, within a quarter of the true value
+        for (percentile, expected) in [(50.0, ms(50)), (95.0, ms(95)), (99.0, ms(99))].iter() {
+            let value = histogram.percentile(*percentile).unwrap();
+            assert!(value >= *expected, "p{} = {:?}", percentile, value);
+            assert!(
+                value <= expected.mul_f64(1.25),
+                "p{} = {:?}",
+                percentile,
+                value
+            );
+        }
+
+        histogram.clear();
+        assert_eq!(histogram.count(), 0);
+        assert_eq!(histogram.percentile(50.0), None);
+    }
+
+    #[test]
+    fn test_callsite_stats() {
+        let mut stats = CallsiteStats::new(work_metadata());
+        assert_eq!((stats.min(), stats.max(), stats.mean()), (None, None, None));
+        assert_eq!(stats.p50(), None);
+
+        for millis in [1, 3, 2].iter() {
+            stats.record(ms(*millis));
+        }
+
+        assert_eq!(stats.name(), "work");
+        assert_eq!(stats.count(), 3);
+        assert_eq!(stats.total(), ms(6));
+        assert_eq!(stats.min(), Some(ms(1)));
+        assert_eq!(stats.max(), Some(ms(3)));
+        assert_eq!(stats.mean(), Some(ms(2)));
+
+        // Bucket bounds never escape the observed range
+        let p0 = stats.percentile(0.0).unwrap();
+        assert!(p0 >= ms(1) && p0 <= ms(3));
+        assert_eq!(stats.p99(), Some(ms(3)));
+    }
+
+    #[test]
+    fn test_update_across_generations() {
+        let mut trace_tree = TraceTree::default();
+        let mut trace_stats = TraceStats::default();
+
+        trace_work(&mut trace_tree);
+        trace_stats.update(&trace_tree);
+        trace_stats.update(&trace_tree);
+
+        let count = |trace_stats: &TraceStats| {
+            trace_stats
+                .query(module_path!(), "work")
+                .map(CallsiteStats::count)
+                .sum::<u64>()
+        };
+
+        // Intervals are only recorded once, no matter how many updates see them
+        assert_eq!(count(&trace_stats), 1);
+
+        // Pruned history keeps contributing to the aggregate
+        trace_tree.prune_closed();
+        trace_work(&mut trace_tree);
+        trace_stats.update(&trace_tree);
+        assert_eq!(count(&trace_stats), 2);
+
+        assert_eq!(trace_stats.module("antigen_tracing").count(), 1);
+        assert_eq!(
+            trace_stats.module("antigen_tracing::trace_stats").count(),
+            1
+        );
+        assert_eq!(trace_stats.module("antigen_trac").count(), 0);
+        assert_eq!(trace_stats.query(module_path!(), "other").count(), 0);
+
+        trace_stats.clear();
+        assert_eq!(trace_stats.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_update_reentered_span() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let (sender, receiver) = crate::TraceSender::unbounded();
+        let mut trace_tree = TraceTree::default();
+        let mut trace_stats = TraceStats::default();
+
+        tracing::subscriber::with_default(
+            tracing_subscriber::registry::Registry::default().with(sender),
+            || {
+                let work = tracing::trace_span!("work");
+                let mut enter = |trace_stats: &mut TraceStats| {
+                    work.in_scope(|| ());
+                    receiver.flush(&mut trace_tree);
+                    trace_stats.update(&trace_tree);
+                };
+
+                enter(&mut trace_stats);
+                enter(&mut trace_stats);
+            },
+        );
+
+        // Each interval of the still-open span is counted exactly once
+        let stats = trace_stats.query(module_path!(), "work").next().unwrap();
+        assert_eq!(stats.count(), 2);
+        assert!(stats.total() >= stats.max().unwrap());
+    }
+}
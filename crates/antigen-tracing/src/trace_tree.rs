@@ -2,7 +2,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use tracing::{Metadata, callsite::Identifier};
 
-use crate::{SpanId, TraceLeaf, TraceLeafId, TraceLeafVariant};
+use crate::{SpanId, TraceDiagnostics, TraceLeaf, TraceLeafId, TraceLeafVariant};
 
 /// Collected profiling data
 #[derive(Debug, Default)]
@@ -10,6 +10,7 @@ pub struct TraceTree {
     pub leaves: BTreeMap<TraceLeafId, TraceLeaf>,
     pub span_id_to_trace_leaf_id: BTreeMap<SpanId, TraceLeafId>,
     pub generation: usize,
+    pub diagnostics: TraceDiagnostics,
 }
 
 impl TraceTree {
@@ -52,7 +53,7 @@ impl TraceTree {
     pub fn roots(&self) -> impl Iterator<Item = (&TraceLeafId, &TraceLeaf)> {
         self.leaves
             .iter()
-            .filter(|(_, leaf)| matches!(leaf.parent_id, None))
+            .filter(|(_, leaf)| leaf.parent_id.is_none())
     }
 
     pub fn children<'a>(
@@ -0,0 +1,94 @@
+use legion::{
+    query::{FilterResult, LayoutFilter},
+    serialize::{Registry, WorldDeserializer},
+    storage::ComponentTypeId,
+};
+
+/// [`LayoutFilter`] matching archetypes that contain every component in a set
+///
+/// Used to narrow down world serialization before a query is evaluated against individual entities.
+#[derive(Debug, Default, Clone)]
+pub struct ComponentSetFilter {
+    components: Vec<ComponentTypeId>,
+    unknown: bool,
+}
+
+impl ComponentSetFilter {
+    /// Construct a filter from a set of registered component names
+    ///
+    /// Names unknown to the registry cause the filter to match nothing.
+    pub fn new<'a, I>(registry: &Registry<String>, names: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut components = vec![];
+        let mut unknown = false;
+
+        for name in names {
+            match registry.unmap_id(&name.to_string()) {
+                Ok(id) => components.push(id),
+                Err(_) => unknown = true,
+            }
+        }
+
+        ComponentSetFilter {
+            components,
+            unknown,
+        }
+    }
+}
+
+impl LayoutFilter for ComponentSetFilter {
+    fn matches_layout(&self, components: &[ComponentTypeId]) -> FilterResult {
+        if self.unknown {
+            return FilterResult::Match(false);
+        }
+
+        FilterResult::Match(
+            self.components
+                .iter()
+                .all(|component| components.contains(component)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> Registry<String> {
+        let mut registry = Registry::default();
+        registry.register::<i32>("I32".to_string());
+        registry.register::<f32>("F32".to_string());
+        registry
+    }
+
+    fn matches(filter: &ComponentSetFilter, components: &[ComponentTypeId]) -> bool {
+        matches!(filter.matches_layout(components), FilterResult::Match(true))
+    }
+
+    #[test]
+    fn test_matches_superset() {
+        let filter = ComponentSetFilter::new(&registry(), vec!["I32"]);
+        let i32_id = ComponentTypeId::of::<i32>();
+        let f32_id = ComponentTypeId::of::<f32>();
+
+        assert!(matches(&filter, &[i32_id]));
+        assert!(matches(&filter, &[f32_id, i32_id]));
+        assert!(!matches(&filter, &[f32_id]));
+        assert!(!matches(&filter, &[]));
+    }
+
+    #[test]
+    fn test_empty_set_matches_everything() {
+        let filter = ComponentSetFilter::new(&registry(), vec![]);
+        assert!(matches(&filter, &[]));
+        assert!(matches(&filter, &[ComponentTypeId::of::<f32>()]));
+    }
+
+    #[test]
+    fn test_unknown_component_matches_nothing() {
+        let filter = ComponentSetFilter::new(&registry(), vec!["I32", "Unknown"]);
+        assert!(!matches(&filter, &[ComponentTypeId::of::<i32>()]));
+    }
+}
@@ -0,0 +1,299 @@
+use legion::serialize::Registry;
+use reflection::data::Data;
+
+use crate::{Archetype, Components};
+
+use super::{Comparison, ComponentSetFilter, FieldPredicate};
+
+/// Component name used to resolve `name:` terms
+pub const NAME_COMPONENT: &str = "Name";
+
+/// Single whitespace-separated term of an [`EntityQuery`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryTerm {
+    /// `name:foo` - case-insensitive substring of the entity's [`NAME_COMPONENT`]
+    Name(String),
+    /// `uuid:1234` or `#1234` - entity UUID prefix
+    Uuid(String),
+    /// `has:Component` - entity has a component with this name
+    Has(String),
+    /// `Component.field op value` - field comparison
+    Field(FieldPredicate),
+    /// Bare text - matches name, UUID prefix or component name
+    Text(String),
+}
+
+/// Search query over entities, parsed from a search bar string
+///
+/// Terms are separated by whitespace and must all match.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EntityQuery {
+    terms: Vec<QueryTerm>,
+}
+
+impl EntityQuery {
+    pub fn parse(str: &str) -> Self {
+        let tokens = tokenize(str);
+        let mut terms = vec![];
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = &tokens[i];
+
+            // Rejoin predicates written with spaces around the operator
+            if Comparison::find(token).is_none() {
+                if let Some(next) = tokens.get(i + 1) {
+                    if Comparison::ALL.iter().any(|(symbol, _)| next == symbol) {
+                        if let Some(rhs) = tokens.get(i + 2) {
+                            let joined = format!("{}{}{}", token, next, rhs);
+                            if let Some(predicate) = FieldPredicate::parse(&joined) {
+                                terms.push(QueryTerm::Field(predicate));
+                                i += 3;
+                                continue;
+                            }
+                        }
+                    }
+                }
+            }
+
+            terms.push(parse_term(token));
+            i += 1;
+        }
+
+        EntityQuery { terms }
+    }
+
+    pub fn terms(&self) -> &[QueryTerm] {
+        &self.terms
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Component names an entity must have in order to match this query
+    pub fn required_components(&self) -> Vec<&str> {
+        let mut components = self
+            .terms
+            .iter()
+            .flat_map(|term| match term {
+                QueryTerm::Name(_) => Some(NAME_COMPONENT),
+                QueryTerm::Has(name) => Some(name.as_str()),
+                QueryTerm::Field(predicate) => Some(predicate.component.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        components.sort_unstable();
+        components.dedup();
+        components
+    }
+
+    /// Build a legion [`LayoutFilter`](legion::query::LayoutFilter) that pre-filters archetypes
+    /// by this query's required components
+    pub fn layout_filter(&self, registry: &Registry<String>) -> ComponentSetFilter {
+        ComponentSetFilter::new(registry, self.required_components())
+    }
+
+    /// Evaluate this query against a single entity
+    pub fn matches_entity(&self, uuid: &uuid::Uuid, components: &Components) -> bool {
+        let names = components.keys().map(String::as_str).collect::<Vec<_>>();
+        self.matches(uuid, &names, |name| components.get(name))
+    }
+
+    /// Returns true if any entity in the archetype matches this query
+    pub fn matches_archetype(&self, archetype: &Archetype) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let names = archetype.layout().collect::<Vec<_>>();
+        archetype
+            .entities()
+            .iter()
+            .enumerate()
+            .any(|(index, uuid)| {
+                self.matches(uuid, &names, |name| {
+                    archetype
+                        .components()
+                        .get(name)
+                        .and_then(|column| column.get(index))
+                })
+            })
+    }
+
+    fn matches<'a, F>(&self, uuid: &uuid::Uuid, names: &[&str], component: F) -> bool
+    where
+        F: Fn(&str) -> Option<&'a Data>,
+    {
+        let uuid = uuid.to_hyphenated().to_string();
+        let name = component(NAME_COMPONENT).and_then(entity_name);
+
+        let name_matches = |pattern: &str| {
+            name.as_ref()
+                .map(|name| name.to_lowercase().contains(&pattern.to_lowercase()))
+                .unwrap_or(false)
+        };
+
+        let uuid_matches = |prefix: &str| {
+            let prefix = prefix.to_lowercase();
+            uuid.starts_with(&prefix) || uuid.replace('-', "").starts_with(&prefix)
+        };
+
+        self.terms.iter().all(|term| match term {
+            QueryTerm::Name(pattern) => name_matches(pattern),
+            QueryTerm::Uuid(prefix) => uuid_matches(prefix),
+            QueryTerm::Has(name) => names.contains(&name.as_str()),
+            QueryTerm::Field(predicate) => component(&predicate.component)
+                .map(|data| predicate.matches(data))
+                .unwrap_or(false),
+            QueryTerm::Text(text) => {
+                let lower = text.to_lowercase();
+                name_matches(text)
+                    || uuid_matches(text)
+                    || names
+                        .iter()
+                        .any(|name| name.to_lowercase().contains(&lower))
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for EntityQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            match term {
+                QueryTerm::Name(name) => write!(f, "name:{}", name)?,
+                QueryTerm::Uuid(uuid) => write!(f, "uuid:{}", uuid)?,
+                QueryTerm::Has(name) => write!(f, "has:{}", name)?,
+                QueryTerm::Field(predicate) => write!(f, "{}", predicate)?,
+                QueryTerm::Text(text) => write!(f, "{}", text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Extract the string payload of a serialized name component
+pub fn entity_name(data: &Data) -> Option<&str> {
+    match data {
+        Data::String(name) => Some(name),
+        Data::NewtypeStruct { data, .. } => entity_name(data),
+        _ => None,
+    }
+}
+
+fn parse_term(token: &str) -> QueryTerm {
+    if let Some(name) = token.strip_prefix("name:") {
+        QueryTerm::Name(name.to_string())
+    } else if let Some(uuid) = token.strip_prefix("uuid:") {
+        QueryTerm::Uuid(uuid.to_string())
+    } else if let Some(uuid) = token.strip_prefix('#') {
+        QueryTerm::Uuid(uuid.to_string())
+    } else if let Some(name) = token.strip_prefix("has:") {
+        QueryTerm::Has(name.to_string())
+    } else if let Some(predicate) = FieldPredicate::parse(token) {
+        QueryTerm::Field(predicate)
+    } else {
+        QueryTerm::Text(token.to_string())
+    }
+}
+
+/// Split on whitespace, keeping double-quoted sections intact
+fn tokenize(str: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in str.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::parse_components;
+
+    fn entity() -> (uuid::Uuid, Components) {
+        let uuid = uuid::Uuid::parse_str("a1b2c3d4-0000-0000-0000-000000000000").unwrap();
+        let components = parse_components(Data::Map(vec![
+            (
+                Data::String(NAME_COMPONENT.to_string()),
+                Data::NewtypeStruct {
+                    name: NAME_COMPONENT,
+                    data: Box::new(Data::String("Player One".to_string())),
+                },
+            ),
+            (
+                Data::String("Health".to_string()),
+                Data::Struct {
+                    name: "Health",
+                    fields: vec![("current", Data::U32(80))].into_iter().collect(),
+                },
+            ),
+        ]))
+        .unwrap();
+        (uuid, components)
+    }
+
+    #[test]
+    fn test_parse() {
+        let query =
+            EntityQuery::parse(r#"name:"Player One" #a1b2 has:Health Health.current > 50 foo"#);
+        assert_eq!(
+            query.terms(),
+            [
+                QueryTerm::Name("Player One".to_string()),
+                QueryTerm::Uuid("a1b2".to_string()),
+                QueryTerm::Has("Health".to_string()),
+                QueryTerm::Field(FieldPredicate::parse("Health.current>50").unwrap()),
+                QueryTerm::Text("foo".to_string()),
+            ]
+        );
+        assert_eq!(query.required_components(), ["Health", NAME_COMPONENT]);
+        assert!(EntityQuery::parse("  ").is_empty());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let query = EntityQuery::parse("name:foo uuid:12 has:Health Health.current <= 3 bar");
+        assert_eq!(EntityQuery::parse(&query.to_string()), query);
+    }
+
+    #[test]
+    fn test_matches_entity() {
+        let (uuid, components) = entity();
+        let matches = |str: &str| EntityQuery::parse(str).matches_entity(&uuid, &components);
+
+        assert!(matches(""));
+        assert!(matches("name:player"));
+        assert!(matches("uuid:A1B2C3D4-0000"));
+        assert!(matches("#a1b2c3d40000"));
+        assert!(matches("has:Health Health.current >= 80"));
+        assert!(matches("health"));
+        assert!(matches("one"));
+
+        assert!(!matches("name:enemy"));
+        assert!(!matches("has:Position"));
+        assert!(!matches("Health.current > 80"));
+        assert!(!matches("name:player has:Position"));
+    }
+}
@@ -0,0 +1,247 @@
+use std::cmp::Ordering;
+
+use reflection::data::Data;
+
+/// Comparison operator used by a [`FieldPredicate`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    /// Operators in match order; two-character operators must precede their prefixes
+    pub const ALL: [(&'static str, Comparison); 6] = [
+        ("==", Comparison::Eq),
+        ("!=", Comparison::Ne),
+        ("<=", Comparison::Le),
+        (">=", Comparison::Ge),
+        ("<", Comparison::Lt),
+        (">", Comparison::Gt),
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+
+    /// Find the first operator in `str`, returning its byte offset and length
+    pub fn find(str: &str) -> Option<(usize, usize, Comparison)> {
+        let mut candidates = Comparison::ALL
+            .iter()
+            .flat_map(|(symbol, comparison)| {
+                str.find(symbol)
+                    .map(|index| (index, symbol.len(), *comparison))
+            })
+            .collect::<Vec<_>>();
+
+        // Earliest match first, longest operator wins ties
+        candidates.sort_by(|(lhs_idx, lhs_len, _), (rhs_idx, rhs_len, _)| {
+            lhs_idx.cmp(rhs_idx).then(rhs_len.cmp(lhs_len))
+        });
+        candidates.into_iter().next()
+    }
+
+    fn test(&self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Eq => ordering == Ordering::Equal,
+            Comparison::Ne => ordering != Ordering::Equal,
+            Comparison::Lt => ordering == Ordering::Less,
+            Comparison::Le => ordering != Ordering::Greater,
+            Comparison::Gt => ordering == Ordering::Greater,
+            Comparison::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// Predicate over a component field, ex. `Position.x > 5`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldPredicate {
+    pub component: String,
+    pub path: Vec<String>,
+    pub comparison: Comparison,
+    pub value: String,
+}
+
+impl FieldPredicate {
+    /// Parse a predicate of the form `Component.field.field <op> value`
+    pub fn parse(str: &str) -> Option<Self> {
+        let (index, len, comparison) = Comparison::find(str)?;
+        let lhs = str[..index].trim();
+        let value = str[index + len..].trim().trim_matches('"').to_string();
+
+        let mut parts = lhs.split('.').map(str::trim);
+        let component = parts.next().filter(|part| !part.is_empty())?.to_string();
+        let path = parts.map(ToString::to_string).collect::<Vec<_>>();
+        if path.iter().any(String::is_empty) {
+            return None;
+        }
+
+        Some(FieldPredicate {
+            component,
+            path,
+            comparison,
+            value,
+        })
+    }
+
+    /// Evaluate this predicate against a component's serialized data
+    pub fn matches(&self, component: &Data) -> bool {
+        let mut data = component;
+        for key in &self.path {
+            data = match field(data, key) {
+                Some(data) => data,
+                None => return false,
+            }
+        }
+
+        match compare(data, &self.value) {
+            Some(ordering) => self.comparison.test(ordering),
+            None => false,
+        }
+    }
+}
+
+impl std::fmt::Display for FieldPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.component)?;
+        for key in &self.path {
+            write!(f, ".{}", key)?;
+        }
+        write!(f, " {} {}", self.comparison.symbol(), self.value)
+    }
+}
+
+/// Strip transparent wrappers so predicates can address the underlying value
+fn unwrap(data: &Data) -> &Data {
+    match data {
+        Data::NewtypeStruct { data, .. } => unwrap(data),
+        Data::Option(Some(data)) => unwrap(data),
+        _ => data,
+    }
+}
+
+/// Non-panicking field lookup by struct field name, map key, or sequence index
+fn field<'a>(data: &'a Data, key: &str) -> Option<&'a Data> {
+    match unwrap(data) {
+        Data::Struct { fields, .. } | Data::StructVariant { fields, .. } => fields
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, data)| data),
+        Data::Map(entries) => entries
+            .iter()
+            .find(|(k, _)| matches!(unwrap(k), Data::String(k) if k == key))
+            .map(|(_, data)| data),
+        Data::Seq(data)
+        | Data::Tuple(data)
+        | Data::TupleStruct { data, .. }
+        | Data::TupleVariant { data, .. } => data.get(key.parse::<usize>().ok()?),
+        Data::NewtypeVariant { data, .. } => field(data, key),
+        _ => None,
+    }
+}
+
+/// Compare a leaf value against the textual operand of a predicate
+fn compare(data: &Data, value: &str) -> Option<Ordering> {
+    let number = |n: f64| value.parse::<f64>().ok().and_then(|v| n.partial_cmp(&v));
+
+    match unwrap(data) {
+        Data::Bool(b) => value.parse::<bool>().ok().map(|v| b.cmp(&v)),
+        Data::I8(n) => number(*n as f64),
+        Data::I16(n) => number(*n as f64),
+        Data::I32(n) => number(*n as f64),
+        Data::I64(n) => number(*n as f64),
+        Data::I128(n) => number(*n as f64),
+        Data::U8(n) => number(*n as f64),
+        Data::U16(n) => number(*n as f64),
+        Data::U32(n) => number(*n as f64),
+        Data::U64(n) => number(*n as f64),
+        Data::U128(n) => number(*n as f64),
+        Data::F32(n) => number(*n as f64),
+        Data::F64(n) => number(*n),
+        Data::Char(c) => Some(c.to_string().as_str().cmp(value)),
+        Data::String(s) => Some(s.as_str().cmp(value)),
+        Data::Option(None) => Some(if value == "None" {
+            Ordering::Equal
+        } else {
+            Ordering::Less
+        }),
+        Data::UnitStruct { name } => Some((*name).cmp(value)),
+        Data::UnitVariant { variant, .. } | Data::NewtypeVariant { variant, .. } => {
+            Some((*variant).cmp(value))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position() -> Data {
+        Data::Struct {
+            name: "Position",
+            fields: vec![
+                ("x", Data::F32(5.0)),
+                ("y", Data::Option(Some(Box::new(Data::I32(-2))))),
+                ("tags", Data::Seq(vec![Data::String("player".to_string())])),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let predicate = FieldPredicate::parse("Position.x >= 5").unwrap();
+        assert_eq!(predicate.component, "Position");
+        assert_eq!(predicate.path, ["x"]);
+        assert_eq!(predicate.comparison, Comparison::Ge);
+        assert_eq!(predicate.value, "5");
+        assert_eq!(predicate.to_string(), "Position.x >= 5");
+
+        let predicate = FieldPredicate::parse(r#"Name == "Player One""#).unwrap();
+        assert!(predicate.path.is_empty());
+        assert_eq!(predicate.value, "Player One");
+
+        assert!(FieldPredicate::parse("Position.x").is_none());
+        assert!(FieldPredicate::parse("== 5").is_none());
+        assert!(FieldPredicate::parse("Position..x == 5").is_none());
+    }
+
+    #[test]
+    fn test_find_prefers_longest_operator() {
+        assert_eq!(Comparison::find("a<=b"), Some((1, 2, Comparison::Le)));
+        assert_eq!(Comparison::find("a<b"), Some((1, 1, Comparison::Lt)));
+        assert_eq!(Comparison::find("a!=b"), Some((1, 2, Comparison::Ne)));
+        assert_eq!(Comparison::find("ab"), None);
+    }
+
+    #[test]
+    fn test_matches() {
+        let data = position();
+        let matches = |str: &str| FieldPredicate::parse(str).unwrap().matches(&data);
+
+        assert!(matches("Position.x == 5"));
+        assert!(matches("Position.x > 4.5"));
+        assert!(!matches("Position.x < 5"));
+        assert!(matches("Position.y < 0"));
+        assert!(matches("Position.tags.0 == player"));
+
+        // Missing fields and mismatched types never match
+        assert!(!matches("Position.z == 5"));
+        assert!(!matches("Position.tags.1 == player"));
+        assert!(!matches("Position.x == five"));
+        assert!(!matches("Position.tags == player"));
+    }
+}
@@ -0,0 +1,7 @@
+mod component_set;
+mod entity_query;
+mod field_predicate;
+
+pub use component_set::*;
+pub use entity_query::*;
+pub use field_predicate::*;
@@ -2,9 +2,11 @@
 ///
 /// Provides reflection data parsing for legion's serialization formats, and methods for displaying
 /// such data at runtime
+mod filter;
 mod parser;
 mod registry;
 
+pub use filter::*;
 pub use parser::*;
 pub use registry::*;
 
@@ -1,5 +1,7 @@
+use std::collections::BTreeMap;
+
 use legion::{
-    query::EntityFilter,
+    query::LayoutFilter,
     serialize::{CustomEntitySerializer, WorldSerializer},
     World,
 };
@@ -24,9 +26,8 @@ impl Archetypes {
         filter: &F,
         world_serializer: &W,
         entity_serializer: &E,
-    ) 
-    where
-        F: EntityFilter + Clone,
+    ) where
+        F: LayoutFilter + Clone,
         W: WorldSerializer,
         E: CustomEntitySerializer<SerializedID = uuid::Uuid> + 'static,
     {
@@ -35,6 +36,108 @@ impl Archetypes {
         let serialized = reflection::to_data(&serializable, false).unwrap();
         self.0 = Some(serialized);
     }
+
+    /// Parse the packed world representation into a list of [`Archetype`]s
+    pub fn parse(&self) -> Result<Vec<Archetype>, ParseArchetypesError> {
+        let world = if let Some(world) = &self.0 {
+            world
+        } else {
+            return Ok(vec![]);
+        };
+
+        let map = if let Data::Map(map) = world {
+            map
+        } else {
+            return Err(ParseArchetypesError::WorldIsNotAMap(world.clone()));
+        };
+
+        let mut archetypes = vec![];
+        for (key, value) in map {
+            if !matches!(
+                key,
+                Data::UnitVariant {
+                    variant: "packed",
+                    ..
+                }
+            ) {
+                return Err(ParseArchetypesError::UnexpectedWorldKey(key.clone()));
+            }
+
+            let seq = if let Data::Seq(seq) = value {
+                seq
+            } else {
+                return Err(ParseArchetypesError::WorldIsNotASeq(value.clone()));
+            };
+
+            for archetype in seq {
+                archetypes.push(Archetype::parse(archetype)?);
+            }
+        }
+
+        Ok(archetypes)
+    }
+}
+
+/// A single archetype parsed from legion's packed world representation
+#[derive(Debug, Default, Clone)]
+pub struct Archetype {
+    entities: Vec<uuid::Uuid>,
+    components: BTreeMap<String, Vec<Data>>,
+}
+
+impl Archetype {
+    pub fn parse(data: &Data) -> Result<Self, ParseArchetypeError> {
+        let fields = if let Data::Struct { fields, .. } = data {
+            fields
+        } else {
+            return Err(ParseArchetypeError);
+        };
+
+        let mut entities = vec![];
+        let mut components = BTreeMap::default();
+
+        for (key, value) in fields {
+            match *key {
+                "entities" => {
+                    let seq = value.downcast_seq().map_err(|_| ParseArchetypeError)?;
+                    for entity in seq {
+                        let bytes = entity
+                            .downcast_byte_array()
+                            .map_err(|_| ParseArchetypeError)?;
+                        entities
+                            .push(uuid::Uuid::from_slice(bytes).map_err(|_| ParseArchetypeError)?);
+                    }
+                }
+                "components" => {
+                    let map = value.downcast_map().map_err(|_| ParseArchetypeError)?;
+                    for (key, value) in map {
+                        let key = key.downcast_string().map_err(|_| ParseArchetypeError)?;
+                        let column = value.downcast_seq().map_err(|_| ParseArchetypeError)?;
+                        components.insert(key.clone(), column.clone());
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Archetype {
+            entities,
+            components,
+        })
+    }
+
+    pub fn entities(&self) -> &[uuid::Uuid] {
+        &self.entities
+    }
+
+    pub fn components(&self) -> &BTreeMap<String, Vec<Data>> {
+        &self.components
+    }
+
+    /// Iterate over the names of this archetype's component types
+    pub fn layout(&self) -> impl Iterator<Item = &str> {
+        self.components.keys().map(String::as_str)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -43,6 +146,7 @@ pub struct ParseArchetypeError;
 #[derive(Debug, Clone)]
 pub enum ParseArchetypesError {
     UnexpectedWorldKey(Data),
+    WorldIsNotAMap(Data),
     WorldIsNotASeq(Data),
     Archetype(ParseArchetypeError),
 }
@@ -52,3 +156,32 @@ impl From<ParseArchetypeError> for ParseArchetypesError {
         ParseArchetypesError::Archetype(e)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Archetypes(None).parse().unwrap().is_empty());
+
+        assert!(matches!(
+            Archetypes(Some(Data::Seq(vec![]))).parse(),
+            Err(ParseArchetypesError::WorldIsNotAMap(_))
+        ));
+
+        let packed = Data::UnitVariant {
+            name: "WorldField",
+            variant_index: 0,
+            variant: "packed",
+        };
+        assert!(matches!(
+            Archetypes(Some(Data::Map(vec![(packed, Data::Unit)]))).parse(),
+            Err(ParseArchetypesError::WorldIsNotASeq(_))
+        ));
+        assert!(matches!(
+            Archetypes(Some(Data::Map(vec![(Data::Unit, Data::Seq(vec![]))]))).parse(),
+            Err(ParseArchetypesError::UnexpectedWorldKey(_))
+        ));
+    }
+}
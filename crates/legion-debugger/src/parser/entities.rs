@@ -1,9 +1,9 @@
 use std::{collections::BTreeMap, ops::Deref};
 
 use legion::{
-    query::EntityFilter,
+    query::LayoutFilter,
     serialize::{CustomEntitySerializer, WorldSerializer},
-    Entity, World,
+    World,
 };
 
 use reflection::data::Data;
@@ -27,7 +27,7 @@ impl Entities {
         world_serializer: &W,
         entity_serializer: &E,
     ) where
-        F: EntityFilter + Clone,
+        F: LayoutFilter + Clone,
         W: WorldSerializer,
         E: CustomEntitySerializer<SerializedID = uuid::Uuid> + 'static,
     {
@@ -36,6 +36,54 @@ impl Entities {
         let serialized = reflection::to_data(&serializable, true).unwrap();
         self.0 = Some(serialized)
     }
+
+    /// Parse the per-entity world representation into a list of entity UUIDs and their components
+    pub fn parse(&self) -> Result<Vec<(uuid::Uuid, Components)>, ParseEntitiesError> {
+        let world = if let Some(world) = &self.0 {
+            world
+        } else {
+            return Ok(vec![]);
+        };
+
+        let map = if let Data::Map(map) = world {
+            map
+        } else {
+            return Err(ParseEntitiesError::WorldIsNotAMap(world.clone()));
+        };
+
+        let mut entities = vec![];
+        for (key, value) in map {
+            if !matches!(
+                key,
+                Data::UnitVariant {
+                    variant: "entities",
+                    ..
+                }
+            ) {
+                return Err(ParseEntitiesError::UnexpectedWorldKey(key.clone()));
+            }
+
+            let entity_map = if let Data::Map(entity_map) = value {
+                entity_map
+            } else {
+                return Err(ParseEntitiesError::WorldIsNotAMap(value.clone()));
+            };
+
+            for (key, value) in entity_map {
+                let key = if let Data::String(key) = key {
+                    key
+                } else {
+                    return Err(ParseEntitiesError::KeyIsNotAString(key.clone()));
+                };
+
+                let uuid = uuid::Uuid::parse_str(key)?;
+                let components = parse_components(value.clone())?;
+                entities.push((uuid, components));
+            }
+        }
+
+        Ok(entities)
+    }
 }
 
 #[derive(Debug, Clone)]
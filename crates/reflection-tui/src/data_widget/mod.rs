@@ -11,7 +11,7 @@ mod widget_string;
 mod widget_struct;
 mod widget_unit;
 
-use std::any::TypeId;
+use std::{any::TypeId, collections::BTreeMap, ops::Range};
 
 use reflection::data::Data;
 use tui::buffer::Buffer;
@@ -50,6 +50,34 @@ pub trait WidgetState {
 
 pub type BoxedDataWidget<'a> = Box<dyn DataWidget + 'a>;
 
+/// Scroll so that `selected` is within `rows` visible rows, returning the visible index range
+pub(crate) fn visible_range(
+    scroll: &mut usize,
+    selected: usize,
+    len: usize,
+    rows: usize,
+) -> Range<usize> {
+    let rows = rows.max(1);
+
+    if selected < *scroll {
+        *scroll = selected;
+    } else if selected >= *scroll + rows {
+        *scroll = selected + 1 - rows;
+    }
+    *scroll = (*scroll).min(len.saturating_sub(rows));
+
+    *scroll..(*scroll + rows).min(len)
+}
+
+/// Drop child states that are neither visible nor selected
+pub(crate) fn prune_fields<T>(
+    fields: &mut BTreeMap<usize, T>,
+    visible: Range<usize>,
+    selected: usize,
+) {
+    fields.retain(|i, _| visible.contains(i) || *i == selected);
+}
+
 /// A dynamically sized widget
 pub trait DataWidget: WidgetState {
     fn size_complex(
@@ -129,7 +157,13 @@ pub fn detail_widgets(
         },
         Data::NewtypeStruct { data, .. } => predicate(data, parent_type),
         Data::NewtypeVariant { data, .. } => predicate(data, parent_type),
-        Data::Seq(v) => Some(Box::new(ListWidget::new("Sequence", v))),
+        Data::Seq(v) => {
+            if is_byte_seq(v) {
+                ByteArrayWidget::from_seq(v).map(|widget| Box::new(widget) as BoxedDataWidget)
+            } else {
+                Some(Box::new(ListWidget::new("Sequence", v)))
+            }
+        }
         Data::Tuple(v) => Some(Box::new(ListWidget::new("Tuple", v))),
         Data::TupleStruct { name, data } => Some(Box::new(ListWidget::new(*name, data))),
         Data::TupleVariant { variant, data, .. } => Some(Box::new(ListWidget::new(variant, data))),
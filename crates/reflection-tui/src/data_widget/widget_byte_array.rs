@@ -1,45 +1,462 @@
+use std::{any::TypeId, convert::TryFrom};
+
+use reflection::data::Data;
 use tui::{
     buffer::Buffer,
     layout::Rect,
-    widgets::{Paragraph, Widget},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Widget},
 };
 
-use crate::{DataWidget, ReflectionWidgetState, WidgetState};
+use crate::{DataWidget, LayoutIterator, ReflectionWidgetState, WidgetHitbox, WidgetState};
+
+/// Number of bytes displayed per hex dump row
+pub const BYTES_PER_ROW: usize = 16;
+
+/// Maximum width of an image preview, in terminal cells
+const PREVIEW_MAX_WIDTH: u16 = 64;
+
+/// Width of a hex dump row: offset column, hex bytes with a center gap, ASCII column
+const ROW_WIDTH: u16 = 8 + 2 + BYTES_PER_ROW as u16 * 3 + 1 + 1 + BYTES_PER_ROW as u16;
+
+/// Pixel layout of a previewable image
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ImageFormat {
+    R8,
+    Rgba8,
+}
+
+impl ImageFormat {
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            ImageFormat::R8 => 1,
+            ImageFormat::Rgba8 => 4,
+        }
+    }
+}
+
+/// Dimensions and format of byte data that can be previewed as an image
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ImageInfo {
+    pub width: u32,
+    pub height: u32,
+    pub format: ImageFormat,
+}
+
+impl ImageInfo {
+    /// Infer the pixel format of `len` bytes of `width` x `height` image data
+    pub fn new(width: u32, height: u32, len: usize) -> Option<Self> {
+        let pixels = width as usize * height as usize;
+        if pixels == 0 {
+            return None;
+        }
+
+        let format = if len == pixels {
+            ImageFormat::R8
+        } else if len == pixels * 4 {
+            ImageFormat::Rgba8
+        } else {
+            return None;
+        };
+
+        Some(ImageInfo {
+            width,
+            height,
+            format,
+        })
+    }
+
+    /// Detect image-like struct fields, ex. those of `antigen_components::Image`
+    ///
+    /// Returns the name of the byte field alongside its image info.
+    pub fn detect(fields: &[(&'static str, Data)]) -> Option<(&'static str, ImageInfo)> {
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| *key == name)
+                .and_then(|(_, data)| data_u32(data))
+        };
+
+        let width = field("width")?;
+        let height = field("height")?;
+
+        fields.iter().find_map(|(key, data)| {
+            let len = byte_len(data)?;
+            ImageInfo::new(width, height, len).map(|info| (*key, info))
+        })
+    }
+
+    /// Terminal size of a half-block preview fitting within `area`
+    fn preview_size(&self, area: Rect) -> (u16, u16) {
+        let max_width = area.width.saturating_sub(2).clamp(1, PREVIEW_MAX_WIDTH);
+        let max_height = area.height.saturating_sub(2).max(1);
+
+        let scale = (self.width as f32 / max_width as f32)
+            .max(self.height as f32 / (max_height as f32 * 2.0))
+            .max(1.0);
 
-pub struct ByteArrayWidget<'a>(&'a mut Vec<u8>);
+        let width = (self.width as f32 / scale).ceil() as u16;
+        let height = (self.height as f32 / scale / 2.0).ceil() as u16;
+
+        (width + 2, height + 2)
+    }
+
+    fn pixel(&self, bytes: Bytes, x: u32, y: u32) -> Color {
+        let i = (y as usize * self.width as usize + x as usize) * self.format.bytes_per_pixel();
+        match self.format {
+            ImageFormat::R8 => match bytes.get(i) {
+                Some(v) => Color::Rgb(v, v, v),
+                None => Color::Reset,
+            },
+            ImageFormat::Rgba8 => match (
+                bytes.get(i),
+                bytes.get(i + 1),
+                bytes.get(i + 2),
+                bytes.get(i + 3),
+            ) {
+                (Some(r), Some(g), Some(b), Some(a)) => {
+                    let blend = |c: u8| (c as u16 * a as u16 / 255) as u8;
+                    Color::Rgb(blend(r), blend(g), blend(b))
+                }
+                _ => Color::Reset,
+            },
+        }
+    }
+}
+
+fn data_u32(data: &Data) -> Option<u32> {
+    match data {
+        Data::U8(v) => Some(*v as u32),
+        Data::U16(v) => Some(*v as u32),
+        Data::U32(v) => Some(*v),
+        Data::U64(v) => u32::try_from(*v).ok(),
+        Data::I32(v) => u32::try_from(*v).ok(),
+        Data::I64(v) => u32::try_from(*v).ok(),
+        _ => None,
+    }
+}
+
+fn byte_len(data: &Data) -> Option<usize> {
+    match data {
+        Data::ByteArray(v) => Some(v.len()),
+        Data::Seq(v) if is_byte_seq(v) => Some(v.len()),
+        _ => None,
+    }
+}
+
+/// Returns true if `seq` is a non-empty sequence of [`Data::U8`]
+pub fn is_byte_seq(seq: &[Data]) -> bool {
+    !seq.is_empty() && seq.iter().all(|data| matches!(data, Data::U8(_)))
+}
+
+/// Bytes borrowed from either a [`Data::ByteArray`] or a sequence of [`Data::U8`]
+#[derive(Debug, Copy, Clone)]
+enum Bytes<'a> {
+    Slice(&'a [u8]),
+    Seq(&'a [Data]),
+}
+
+impl Bytes<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Bytes::Slice(bytes) => bytes.len(),
+            Bytes::Seq(seq) => seq.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<u8> {
+        match self {
+            Bytes::Slice(bytes) => bytes.get(index).copied(),
+            Bytes::Seq(seq) => match seq.get(index) {
+                Some(Data::U8(v)) => Some(*v),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Paged hex + ASCII dump of a byte array, with optional image preview
+pub struct ByteArrayWidget<'a> {
+    bytes: Bytes<'a>,
+    image: Option<ImageInfo>,
+}
 
 impl<'a> From<&'a mut Vec<u8>> for ByteArrayWidget<'a> {
     fn from(v: &'a mut Vec<u8>) -> Self {
-        ByteArrayWidget(v)
+        ByteArrayWidget {
+            bytes: Bytes::Slice(v.as_slice()),
+            image: None,
+        }
+    }
+}
+
+impl<'a> ByteArrayWidget<'a> {
+    /// Construct from a sequence of [`Data::U8`]
+    pub fn from_seq(seq: &'a [Data]) -> Option<Self> {
+        if !is_byte_seq(seq) {
+            return None;
+        }
+
+        Some(ByteArrayWidget {
+            bytes: Bytes::Seq(seq),
+            image: None,
+        })
+    }
+
+    /// Construct from either a [`Data::ByteArray`] or a sequence of [`Data::U8`]
+    pub fn from_data(data: &'a mut Data) -> Option<Self> {
+        match data {
+            Data::ByteArray(v) => Some(ByteArrayWidget::from(v)),
+            Data::Seq(v) => ByteArrayWidget::from_seq(v),
+            _ => None,
+        }
+    }
+
+    pub fn image(self, image: Option<ImageInfo>) -> Self {
+        ByteArrayWidget { image, ..self }
+    }
+
+    fn row_count(&self) -> usize {
+        let len = self.bytes.len();
+        len / BYTES_PER_ROW + usize::from(len % BYTES_PER_ROW != 0)
+    }
+
+    fn preview_enabled(&self, state: &ReflectionWidgetState) -> bool {
+        self.image.is_some()
+            && matches!(
+                state,
+                ReflectionWidgetState::ByteArray { preview: true, .. }
+            )
+    }
+
+    fn render_dump(&self, area: Rect, buf: &mut Buffer, state: &mut ReflectionWidgetState) {
+        let (selected, scroll, len, page, focused) = if let ReflectionWidgetState::ByteArray {
+            selected,
+            scroll,
+            len,
+            page,
+            focused,
+            ..
+        } = state
+        {
+            (selected, scroll, len, page, focused)
+        } else {
+            unreachable!()
+        };
+
+        *len = self.bytes.len();
+        if *selected >= *len {
+            *selected = len.saturating_sub(1);
+        }
+
+        let title = match self.image {
+            Some(image) => format!(
+                "Bytes ({}) {}x{} {:?}",
+                self.bytes.len(),
+                image.width,
+                image.height,
+                image.format
+            ),
+            None => format!("Bytes ({})", self.bytes.len()),
+        };
+
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        // Keep the selected row in view
+        let visible_rows = inner.height as usize;
+        let selected_row = *selected / BYTES_PER_ROW;
+        *page = visible_rows.max(1);
+        if selected_row < *scroll {
+            *scroll = selected_row;
+        } else if selected_row >= *scroll + *page {
+            *scroll = selected_row + 1 - *page;
+        }
+
+        let selected = *selected;
+        let focused = *focused;
+        let highlight = Style::default().add_modifier(Modifier::REVERSED);
+        let offset_style = Style::default().fg(Color::DarkGray);
+
+        let mut hitbox = WidgetHitbox {
+            area,
+            items: vec![],
+        };
+
+        let rows = (*scroll..self.row_count()).take(visible_rows);
+
+        for (y, row) in (inner.y..).zip(rows) {
+            buf.set_stringn(
+                inner.x,
+                y,
+                format!("{:08x}", row * BYTES_PER_ROW),
+                inner.width as usize,
+                offset_style,
+            );
+
+            let hex_x = inner.x + 10;
+            let ascii_x = hex_x + BYTES_PER_ROW as u16 * 3 + 2;
+
+            let start = row * BYTES_PER_ROW;
+            let end = (start + BYTES_PER_ROW).min(self.bytes.len());
+            for (column, index) in (start..end).enumerate() {
+                let byte = match self.bytes.get(index) {
+                    Some(byte) => byte,
+                    None => continue,
+                };
+                let gap = if column >= BYTES_PER_ROW / 2 { 1 } else { 0 };
+
+                let hex_rect =
+                    Rect::new(hex_x + column as u16 * 3 + gap, y, 2, 1).intersection(inner);
+                let ascii_rect = Rect::new(ascii_x + column as u16, y, 1, 1).intersection(inner);
+
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                };
+
+                let style = if focused && index == selected {
+                    highlight
+                } else {
+                    Style::default()
+                };
+
+                if hex_rect.width > 0 {
+                    buf.set_stringn(
+                        hex_rect.x,
+                        y,
+                        format!("{:02x}", byte),
+                        hex_rect.width as usize,
+                        style,
+                    );
+                    hitbox.items.push((hex_rect, 0, index));
+                }
+
+                if ascii_rect.width > 0 {
+                    buf.set_stringn(ascii_rect.x, y, c.to_string(), 1, style);
+                    hitbox.items.push((ascii_rect, 0, index));
+                }
+            }
+        }
+
+        state.set_hitbox(hitbox);
+    }
+
+    fn render_preview(&self, area: Rect, buf: &mut Buffer) {
+        let image = match self.image {
+            Some(image) => image,
+            None => return,
+        };
+
+        let block = Block::default().title("Preview").borders(Borders::ALL);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        // Each cell displays two vertically stacked pixels via an upper half block
+        let scale_x = image.width as f32 / inner.width as f32;
+        let scale_y = image.height as f32 / (inner.height as f32 * 2.0);
+        let scale = scale_x.max(scale_y).max(1.0);
+
+        for cy in 0..inner.height {
+            for cx in 0..inner.width {
+                let x = (cx as f32 * scale) as u32;
+                let top = (cy as f32 * 2.0 * scale) as u32;
+                let bottom = ((cy as f32 * 2.0 + 1.0) * scale) as u32;
+
+                if x >= image.width || top >= image.height {
+                    continue;
+                }
+
+                let fg = image.pixel(self.bytes, x, top);
+                let bg = if bottom < image.height {
+                    image.pixel(self.bytes, x, bottom)
+                } else {
+                    Color::Reset
+                };
+
+                buf.get_mut(inner.x + cx, inner.y + cy)
+                    .set_symbol("▀")
+                    .set_fg(fg)
+                    .set_bg(bg);
+            }
+        }
     }
 }
 
-impl WidgetState for ByteArrayWidget<'_> {}
+impl WidgetState for ByteArrayWidget<'_> {
+    fn wants_init_state(&mut self, state: &ReflectionWidgetState) -> bool {
+        !matches!(state, ReflectionWidgetState::ByteArray { .. })
+    }
+
+    fn default_state(&mut self) -> ReflectionWidgetState {
+        ReflectionWidgetState::ByteArray {
+            selected: 0,
+            scroll: 0,
+            len: self.bytes.len(),
+            page: 1,
+            focused: false,
+            preview: self.image.is_some(),
+            hitbox: Default::default(),
+        }
+    }
+}
 
 impl DataWidget for ByteArrayWidget<'_> {
     fn size_complex(
         &mut self,
         area: Rect,
-        _predicate: &dyn Fn(&mut reflection::data::Data, std::any::TypeId) -> Option<Box<dyn DataWidget + '_>>,
+        _predicate: &dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
     ) -> (u16, u16) {
         (
-            (format!("{:?}", self.0).len() as u16).min(area.width),
-            1.min(area.height),
+            (ROW_WIDTH + 2).min(area.width),
+            (self.row_count() as u16).saturating_add(2).min(area.height),
         )
     }
 
+    fn allocate_complex_impl(
+        &mut self,
+        builder: &mut crate::LayoutBuilder,
+        state: &mut ReflectionWidgetState,
+        predicate: &dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
+    ) {
+        let (width, height) = self.size_complex(builder.area(), predicate);
+        builder.allocate_size(width, height);
+
+        if self.preview_enabled(state) {
+            if let Some(image) = self.image {
+                let (width, height) = image.preview_size(builder.area());
+                builder.allocate_size(width, height);
+            }
+        }
+    }
+
     fn render_complex_impl(
         &mut self,
-        mut layout: crate::LayoutIterator,
+        mut layout: LayoutIterator,
         buf: &mut Buffer,
-        _state: &mut ReflectionWidgetState,
-        _predicate: &dyn Fn(&mut reflection::data::Data, std::any::TypeId) -> Option<Box<dyn DataWidget + '_>>,
+        state: &mut ReflectionWidgetState,
+        _predicate: &dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
     ) {
-        if let Some(area) = layout
+        let preview = self.preview_enabled(state);
+
+        match layout
             .next()
             .expect("Insufficient layout cells for widget list")
         {
-            Paragraph::new(format!("{:?}", self.0)).render(area, buf)
+            Some(area) => self.render_dump(area, buf, state),
+            None => state.set_hitbox(WidgetHitbox::default()),
+        }
+
+        if preview {
+            if let Some(Some(area)) = layout.next() {
+                self.render_preview(area, buf);
+            }
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::any::TypeId;
+use std::{any::TypeId, ops::Range};
 
 use reflection::data::Data;
 use tui::{
@@ -7,8 +7,12 @@ use tui::{
     style::{Modifier, Style},
     widgets::{Block, Borders, Widget},
 };
+use tui_widgets::Scrollbar;
 
-use crate::{DataWidget, LayoutIterator, ReflectionWidgetState, WidgetState};
+use crate::{
+    prune_fields, visible_range, DataWidget, LayoutIterator, ReflectionWidgetState, WidgetHitbox,
+    WidgetState,
+};
 
 pub enum ListItemSlot {}
 pub enum ListDetailSlot {}
@@ -22,6 +26,47 @@ impl<'a, 'b> ListWidget<'a, 'b> {
     pub fn new(name: &'a str, data: &'b mut Vec<Data>) -> Self {
         ListWidget { name, data }
     }
+
+    /// Width of the list block, measuring only the items in `range`
+    fn width(
+        &mut self,
+        area: Rect,
+        range: Range<usize>,
+        predicate: &dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
+    ) -> u16 {
+        let name_len = self.name.len() as u16;
+        self.data[range].iter_mut().fold(name_len + 2, |acc, next| {
+            acc.max(
+                predicate(next, TypeId::of::<ListItemSlot>())
+                    .map(|mut next| next.size_complex(area, predicate))
+                    .unwrap_or_default()
+                    .0
+                    + 2
+                    + 2 * PADDING,
+            )
+        })
+    }
+
+    /// Update the list state for the current data, returning the visible index range
+    fn update_state(&self, area: Rect, state: &mut ReflectionWidgetState) -> Range<usize> {
+        if let ReflectionWidgetState::List {
+            selected,
+            scroll,
+            len,
+            ..
+        } = state
+        {
+            *len = self.data.len();
+            if *selected >= *len {
+                *selected = len.saturating_sub(1);
+            }
+
+            let rows = area.height.saturating_sub(2) as usize;
+            visible_range(scroll, *selected, *len, rows)
+        } else {
+            unreachable!()
+        }
+    }
 }
 
 const PADDING: u16 = 1;
@@ -34,13 +79,12 @@ impl<'a, 'b> WidgetState for ListWidget<'a, 'b> {
     fn default_state(&mut self) -> ReflectionWidgetState {
         ReflectionWidgetState::List {
             selected: Default::default(),
+            scroll: Default::default(),
+            len: self.data.len(),
             focused: false,
             focused_field: None,
-            fields: self
-                .data
-                .iter()
-                .map(|_| ReflectionWidgetState::None)
-                .collect(),
+            fields: Default::default(),
+            hitbox: Default::default(),
         }
     }
 }
@@ -51,20 +95,11 @@ impl<'a, 'b> DataWidget for ListWidget<'a, 'b> {
         area: Rect,
         predicate: &dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
     ) -> (u16, u16) {
-        let name_len = self.name.len() as u16;
-        let max_width = self.data.iter_mut().fold(0, |acc, next| {
-            acc.max(
-                (name_len + 2).max(
-                    predicate(next, TypeId::of::<ListItemSlot>())
-                        .map(|mut next| next.size_complex(area, predicate))
-                        .unwrap_or_default()
-                        .0
-                        + 2
-                        + 2 * PADDING,
-                ),
-            )
-        });
-        (max_width, area.height.min(self.data.len() as u16 + 2))
+        let rows = (area.height.saturating_sub(2) as usize).min(self.data.len());
+        (
+            self.width(area, 0..rows, predicate),
+            area.height.min(self.data.len() as u16 + 2),
+        )
     }
 
     fn allocate_complex_impl(
@@ -73,8 +108,10 @@ impl<'a, 'b> DataWidget for ListWidget<'a, 'b> {
         state: &mut ReflectionWidgetState,
         predicate: &dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
     ) {
-        let (width, height) = self.size_complex(builder.area(), predicate);
-        builder.allocate_size(width, height);
+        let area = builder.area();
+        let range = self.update_state(area, state);
+        let width = self.width(area, range, predicate);
+        builder.allocate_size(width, area.height.min(self.data.len() as u16 + 2));
 
         let (selected, focused, state_fields) = if let ReflectionWidgetState::List {
             selected,
@@ -90,14 +127,13 @@ impl<'a, 'b> DataWidget for ListWidget<'a, 'b> {
 
         if *focused {
             // Allocate complex value panel
-            let data = self
-                .data
-                .get_mut(*selected)
-                .unwrap_or_else(|| panic!("No field for selected index {}", selected));
-
-            if let Some(mut widget) = predicate(data, TypeId::of::<ListDetailSlot>()) {
-                let state_field = state_fields.get_mut(*selected).unwrap();
-                widget.allocate_complex(builder, state_field, predicate);
+            if let Some(data) = self.data.get_mut(*selected) {
+                if let Some(mut widget) = predicate(data, TypeId::of::<ListDetailSlot>()) {
+                    let state_field = state_fields
+                        .entry(*selected)
+                        .or_insert(ReflectionWidgetState::None);
+                    widget.allocate_complex(builder, state_field, predicate);
+                }
             }
         }
     }
@@ -130,27 +166,23 @@ impl<'a, 'b> DataWidget for ListWidget<'a, 'b> {
 
         if focused {
             // Draw complex value panel
-            let data = self.data.get_mut(selected).unwrap();
-            if let Some(mut widget) = predicate(data, TypeId::of::<ListDetailSlot>()) {
-                let state = state_fields.get_mut(selected).unwrap();
-                widget.render_complex(layout, buf, state, predicate);
+            if let Some(data) = self.data.get_mut(selected) {
+                if let Some(mut widget) = predicate(data, TypeId::of::<ListDetailSlot>()) {
+                    let state = state_fields
+                        .entry(selected)
+                        .or_insert(ReflectionWidgetState::None);
+                    widget.render_complex(layout, buf, state, predicate);
+                }
             }
         }
 
+        let mut hitbox = WidgetHitbox::default();
+        let mut visible = 0..0;
+
         if let Some(area) = area {
-            let name_len = self.name.len() as u16;
-            let max_width = self.data.iter_mut().fold(0, |acc, next| {
-                acc.max(
-                    (name_len + 2).max(
-                        predicate(next, TypeId::of::<ListItemSlot>())
-                            .map(|mut next| next.size_complex(area, predicate))
-                            .unwrap_or_default()
-                            .0
-                            + 2
-                            + 2 * PADDING,
-                    ),
-                )
-            });
+            visible = self.update_state(area, state);
+
+            let max_width = self.width(area, visible.clone(), predicate);
 
             let block_area = Rect {
                 width: max_width,
@@ -160,8 +192,28 @@ impl<'a, 'b> DataWidget for ListWidget<'a, 'b> {
             let block = Block::default().title(self.name).borders(Borders::ALL);
             let mut inner_area = block.inner(block_area);
             block.render(block_area, buf);
+            hitbox.area = block_area;
+
+            Scrollbar::new(visible.start, visible.len(), self.data.len()).render(
+                Rect {
+                    x: block_area.right().saturating_sub(1),
+                    width: 1,
+                    ..inner_area
+                },
+                buf,
+            );
+
+            for (i, item) in self
+                .data
+                .iter_mut()
+                .enumerate()
+                .skip(visible.start)
+                .take(visible.len())
+            {
+                if inner_area.height == 0 {
+                    break;
+                }
 
-            for (i, item) in self.data.iter_mut().enumerate() {
                 if let Some(mut widget) = predicate(item, TypeId::of::<ListItemSlot>()) {
                     let (widget_width, widget_height) = widget.size_complex(inner_area, predicate);
 
@@ -173,6 +225,7 @@ impl<'a, 'b> DataWidget for ListWidget<'a, 'b> {
                     };
 
                     widget.render_complex(vec![Some(item_area)].into_iter(), buf, state, predicate);
+                    hitbox.items.push((item_area, 0, i));
 
                     if focused {
                         if selected == i {
@@ -185,11 +238,14 @@ impl<'a, 'b> DataWidget for ListWidget<'a, 'b> {
 
                     inner_area.y += widget_height;
                     inner_area.height = inner_area.height.saturating_sub(widget_height);
-                    if inner_area.height == 0 {
-                        break;
-                    }
                 }
             }
         }
+
+        if let ReflectionWidgetState::List { fields, .. } = state {
+            prune_fields(fields, visible, selected);
+        }
+
+        state.set_hitbox(hitbox);
     }
 }
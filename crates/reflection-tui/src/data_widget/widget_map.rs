@@ -1,4 +1,4 @@
-use std::any::TypeId;
+use std::{any::TypeId, ops::Range};
 
 use reflection::data::Data;
 
@@ -8,8 +8,12 @@ use tui::{
     style::{Modifier, Style},
     widgets::{Block, Borders, Widget},
 };
+use tui_widgets::Scrollbar;
 
-use crate::{DataWidget, LayoutIterator, ReflectionWidgetState, WidgetState};
+use crate::{
+    prune_fields, visible_range, DataWidget, LayoutIterator, ReflectionWidgetState, WidgetHitbox,
+    WidgetState,
+};
 
 pub enum MapKeySlot {}
 pub enum MapValueSlot {}
@@ -25,6 +29,62 @@ impl<'a> From<&'a mut Vec<(Data, Data)>> for MapWidget<'a> {
 
 const PADDING: u16 = 1;
 
+const NAME: &str = "Map";
+
+impl MapWidget<'_> {
+    /// Key and value column widths, measuring only the entries in `range`
+    fn widths(
+        &mut self,
+        area: Rect,
+        range: Range<usize>,
+        predicate: &dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
+    ) -> (u16, u16) {
+        let entries = &mut self.0[range];
+
+        let max_key_width = entries.iter_mut().fold(0, |acc, (next, _)| {
+            acc.max(
+                predicate(next, TypeId::of::<MapKeySlot>())
+                    .map(|mut next| next.size_complex(area, predicate))
+                    .unwrap_or_default()
+                    .0,
+            )
+        });
+
+        let max_value_width = entries.iter_mut().fold(0, |acc, (_, next)| {
+            acc.max(
+                predicate(next, TypeId::of::<MapValueSlot>())
+                    .map(|mut next| next.size_complex(area, predicate))
+                    .unwrap_or_default()
+                    .0,
+            )
+        });
+
+        (max_key_width, max_value_width)
+    }
+
+    fn width(key_width: u16, value_width: u16) -> u16 {
+        ((key_width + PADDING * 2) + (value_width + PADDING * 2) + 3).max(NAME.len() as u16 + 2)
+    }
+
+    /// Update the map state for the current data, returning the visible entry range
+    fn update_state(&self, area: Rect, state: &mut ReflectionWidgetState) -> Range<usize> {
+        if let ReflectionWidgetState::Map {
+            row, scroll, len, ..
+        } = state
+        {
+            *len = self.0.len();
+            if *row >= *len {
+                *row = len.saturating_sub(1);
+            }
+
+            let rows = area.height.saturating_sub(2) as usize;
+            visible_range(scroll, *row, *len, rows)
+        } else {
+            unreachable!()
+        }
+    }
+}
+
 // Struct / StructVariant implementation
 impl WidgetState for MapWidget<'_> {
     fn wants_init_state(&mut self, state: &ReflectionWidgetState) -> bool {
@@ -35,13 +95,12 @@ impl WidgetState for MapWidget<'_> {
         ReflectionWidgetState::Map {
             column: Default::default(),
             row: Default::default(),
+            scroll: Default::default(),
+            len: self.0.len(),
             focused: false,
             focused_field: None,
-            fields: self
-                .0
-                .iter()
-                .map(|(_, _)| (ReflectionWidgetState::None, ReflectionWidgetState::None))
-                .collect(),
+            fields: Default::default(),
+            hitbox: Default::default(),
         }
     }
 }
@@ -52,29 +111,13 @@ impl DataWidget for MapWidget<'_> {
         area: Rect,
         predicate: &dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
     ) -> (u16, u16) {
-        // Calculate widths
-        let max_key_width = self.0.iter_mut().fold(0, |acc, (next, _)| {
-            acc.max(
-                predicate(next, TypeId::of::<MapKeySlot>())
-                    .map(|mut next| next.size_complex(area, predicate))
-                    .unwrap_or_default()
-                    .0,
-            )
-        });
+        let rows = (area.height.saturating_sub(2) as usize).min(self.0.len());
+        let (key_width, value_width) = self.widths(area, 0..rows, predicate);
 
-        let max_value_width = self.0.iter_mut().fold(0, |acc, (_, next)| {
-            acc.max(
-                predicate(next, TypeId::of::<MapValueSlot>())
-                    .map(|mut next| next.size_complex(area, predicate))
-                    .unwrap_or_default()
-                    .0,
-            )
-        });
-
-        let max_width =
-            (max_key_width as u16 + PADDING * 2) + (max_value_width as u16 + PADDING * 2) + 3;
-
-        (max_width, area.height.min(self.0.len() as u16 + 2))
+        (
+            Self::width(key_width, value_width),
+            area.height.min(self.0.len() as u16 + 2),
+        )
     }
 
     fn allocate_complex_impl(
@@ -83,8 +126,13 @@ impl DataWidget for MapWidget<'_> {
         state: &mut ReflectionWidgetState,
         predicate: &dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
     ) {
-        let (width, height) = self.size_complex(builder.area(), predicate);
-        builder.allocate_size(width, height);
+        let area = builder.area();
+        let range = self.update_state(area, state);
+        let (key_width, value_width) = self.widths(area, range, predicate);
+        builder.allocate_size(
+            Self::width(key_width, value_width),
+            area.height.min(self.0.len() as u16 + 2),
+        );
 
         let (row, column, focused, state_fields) = if let ReflectionWidgetState::Map {
             row,
@@ -101,25 +149,24 @@ impl DataWidget for MapWidget<'_> {
 
         if *focused {
             // Allocate complex value panel
-            let data = self
-                .0
-                .get_mut(*row)
-                .unwrap_or_else(|| panic!("No field for selected index {}", row));
-
-            let data = if *column == 0 {
-                &mut data.0
-            } else {
-                &mut data.1
-            };
-
-            if let Some(mut widget) = predicate(data, TypeId::of::<MapDetailSlot>()) {
-                let state_field = state_fields.get_mut(*row).unwrap();
-                let state_field = if *column == 0 {
-                    &mut state_field.0
+            if let Some(data) = self.0.get_mut(*row) {
+                let data = if *column == 0 {
+                    &mut data.0
                 } else {
-                    &mut state_field.1
+                    &mut data.1
                 };
-                widget.allocate_complex(builder, state_field, predicate);
+
+                if let Some(mut widget) = predicate(data, TypeId::of::<MapDetailSlot>()) {
+                    let state_field = state_fields
+                        .entry(*row)
+                        .or_insert((ReflectionWidgetState::None, ReflectionWidgetState::None));
+                    let state_field = if *column == 0 {
+                        &mut state_field.0
+                    } else {
+                        &mut state_field.1
+                    };
+                    widget.allocate_complex(builder, state_field, predicate);
+                }
             }
         }
     }
@@ -135,9 +182,6 @@ impl DataWidget for MapWidget<'_> {
             .next()
             .expect("Insufficient layout cells for widget list");
 
-        let name = "Map";
-        let fields = &mut *self.0;
-
         let (column, row, focused, state_fields) = if let ReflectionWidgetState::Map {
             column,
             row,
@@ -151,143 +195,147 @@ impl DataWidget for MapWidget<'_> {
             unreachable!()
         };
 
-        if *row == usize::MAX {
-            *row = fields.len() - 1
-        } else if *row >= fields.len() {
-            *row = 0
-        }
-
         let column = *column;
         let row = *row;
         let focused = *focused;
 
         if focused {
             // Draw complex value panel
-            let data = fields.get_mut(row).unwrap();
-
-            let data = if column == 0 {
-                &mut data.0
-            } else {
-                &mut data.1
-            };
-
-            if let Some(mut widget) = predicate(data, TypeId::of::<MapDetailSlot>()) {
-                let state = state_fields.get_mut(row).unwrap();
-                let state = if column == 0 {
-                    &mut state.0
+            if let Some(data) = self.0.get_mut(row) {
+                let data = if column == 0 {
+                    &mut data.0
                 } else {
-                    &mut state.1
+                    &mut data.1
                 };
-                widget.render_complex(layout, buf, state, predicate);
+
+                if let Some(mut widget) = predicate(data, TypeId::of::<MapDetailSlot>()) {
+                    let state = state_fields
+                        .entry(row)
+                        .or_insert((ReflectionWidgetState::None, ReflectionWidgetState::None));
+                    let state = if column == 0 {
+                        &mut state.0
+                    } else {
+                        &mut state.1
+                    };
+                    widget.render_complex(layout, buf, state, predicate);
+                }
             }
         }
 
-        let area = if let Some(area) = area { area } else { return };
+        let area = if let Some(area) = area {
+            area
+        } else {
+            if let ReflectionWidgetState::Map { fields, .. } = state {
+                prune_fields(fields, 0..0, row);
+            }
+            state.set_hitbox(WidgetHitbox::default());
+            return;
+        };
 
-        // Calculate widths
-        let max_key_width = fields.iter_mut().fold(0, |acc, (next, _)| {
-            acc.max(
-                predicate(next, TypeId::of::<MapKeySlot>())
-                    .map(|mut next| next.size_complex(area, predicate))
-                    .unwrap_or_default()
-                    .0,
-            )
-        });
+        let visible = self.update_state(area, state);
+        let mut hitbox = WidgetHitbox::default();
 
-        let max_value_width = fields.iter_mut().fold(0, |acc, (_, next)| {
-            acc.max(
-                predicate(next, TypeId::of::<MapValueSlot>())
-                    .map(|mut next| next.size_complex(area, predicate))
-                    .unwrap_or_default()
-                    .0,
-            )
-        });
+        // Calculate widths
+        let (max_key_width, max_value_width) = self.widths(area, visible.clone(), predicate);
 
         // Layout chunks
         let layout_chunks = tui::layout::Layout::default()
             .direction(Direction::Horizontal)
             .constraints(vec![
-                Constraint::Length(
-                    ((max_key_width as u16 + PADDING * 2)
-                        + (max_value_width as u16 + PADDING * 2)
-                        + 3)
-                    .max(name.len() as u16 + 2),
-                ),
+                Constraint::Length(Self::width(max_key_width, max_value_width)),
                 Constraint::Min(0),
             ])
             .split(area);
 
         // Draw outer block
-        let struct_block = Block::default().title(name).borders(Borders::ALL);
+        let struct_block = Block::default().title(NAME).borders(Borders::ALL);
         let struct_inner_area = struct_block.inner(layout_chunks[0]);
         struct_block.render(layout_chunks[0], buf);
+        hitbox.area = layout_chunks[0];
+
+        Scrollbar::new(visible.start, visible.len(), self.0.len()).render(
+            Rect {
+                x: layout_chunks[0].right().saturating_sub(1),
+                width: 1,
+                ..struct_inner_area
+            },
+            buf,
+        );
 
         // Struct chunks
         let struct_chunks = tui::layout::Layout::default()
             .direction(Direction::Horizontal)
             .constraints(vec![
-                Constraint::Length(max_key_width as u16 + PADDING * 2),
+                Constraint::Length(max_key_width + PADDING * 2),
                 Constraint::Min(0),
             ])
             .split(struct_inner_area);
 
-        // Draw key list
-        let simple_area = struct_chunks[0];
+        let fields = self
+            .0
+            .iter_mut()
+            .enumerate()
+            .skip(visible.start)
+            .take(visible.len());
+
+        // Draw key and value lists
+        let key_area = struct_chunks[0];
+        let value_block = Block::default().borders(Borders::LEFT);
+        let value_area = value_block.inner(struct_chunks[1]);
+        value_block.render(struct_chunks[1], buf);
+
+        let mut y = key_area.y;
+        for (i, (key, value)) in fields {
+            if y >= key_area.bottom() {
+                break;
+            }
 
-        let simple_list_x = simple_area.x + PADDING;
-        let mut simple_list_y = simple_area.y;
-        for (i, (value, _)) in fields.iter_mut().enumerate() {
-            if let Some(mut widget) = predicate(value, TypeId::of::<MapKeySlot>()) {
-                let (_, widget_height) = widget.size_complex(simple_area, predicate);
+            let mut height = 1;
 
-                let item_area = Rect {
-                    x: simple_list_x,
-                    y: simple_list_y,
-                    width: max_key_width as u16,
-                    height: widget_height,
-                };
+            for (cell_column, data, slot, x, width) in [
+                (
+                    0,
+                    key,
+                    TypeId::of::<MapKeySlot>(),
+                    key_area.x + PADDING,
+                    max_key_width,
+                ),
+                (
+                    1,
+                    value,
+                    TypeId::of::<MapValueSlot>(),
+                    value_area.x + PADDING,
+                    max_value_width,
+                ),
+            ] {
+                if let Some(mut widget) = predicate(data, slot) {
+                    let (_, widget_height) = widget.size_complex(value_area, predicate);
+
+                    let item_area = Rect {
+                        x,
+                        y,
+                        width,
+                        height: widget_height.min(key_area.bottom() - y),
+                    };
 
-                widget.render_complex(vec![Some(item_area)].into_iter(), buf, state, predicate);
+                    widget.render_complex(vec![Some(item_area)].into_iter(), buf, state, predicate);
+                    hitbox.items.push((item_area, cell_column, i));
 
-                if focused {
-                    if column == 0 && row == i {
+                    if focused && column == cell_column && row == i {
                         buf.set_style(item_area, Style::default().add_modifier(Modifier::REVERSED));
                     }
-                }
 
-                simple_list_y += widget_height;
+                    height = height.max(widget_height);
+                }
             }
-        }
-
-        // Draw value list
-        let simple_area = struct_chunks[1];
-        let simple_block = Block::default().borders(Borders::LEFT);
-        let simple_inner_area = simple_block.inner(simple_area);
-        simple_block.render(simple_area, buf);
-
-        let simple_list_x = simple_inner_area.x + PADDING;
-        let mut simple_list_y = simple_inner_area.y;
-        for (i, (_, value)) in fields.iter_mut().enumerate() {
-            if let Some(mut widget) = predicate(value, TypeId::of::<MapValueSlot>()) {
-                let (_, widget_height) = widget.size_complex(simple_inner_area, predicate);
-
-                let item_area = Rect {
-                    x: simple_list_x,
-                    y: simple_list_y,
-                    width: max_value_width as u16,
-                    height: widget_height,
-                };
-
-                widget.render_complex(vec![Some(item_area)].into_iter(), buf, state, predicate);
 
-                if focused {
-                    if column == 1 && row == i {
-                        buf.set_style(item_area, Style::default().add_modifier(Modifier::REVERSED));
-                    }
-                }
+            y += height;
+        }
 
-                simple_list_y += widget_height;
-            }
+        if let ReflectionWidgetState::Map { fields, .. } = state {
+            prune_fields(fields, visible, row);
         }
+
+        state.set_hitbox(hitbox);
     }
 }
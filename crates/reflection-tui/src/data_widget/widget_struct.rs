@@ -9,7 +9,10 @@ use tui::{
     widgets::{Block, Borders, Widget},
 };
 
-use crate::{DataWidget, LayoutIterator, ReflectionWidgetState, WidgetState};
+use crate::{
+    ByteArrayWidget, DataWidget, ImageInfo, LayoutIterator, ReflectionWidgetState, WidgetHitbox,
+    WidgetState,
+};
 
 pub enum StructValueSlot {}
 pub enum StructDetailSlot {}
@@ -27,6 +30,23 @@ impl<'a, 'b> StructWidget<'a, 'b> {
 
 const PADDING: u16 = 1;
 
+/// Widget for the detail panel of a struct field
+///
+/// Byte fields of image-like structs get a hex dump with image preview,
+/// while all other fields are delegated to `predicate`.
+fn detail_widget<'a>(
+    key: &str,
+    data: &'a mut Data,
+    image: Option<(&'static str, ImageInfo)>,
+    predicate: &'a dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
+) -> Option<Box<dyn DataWidget + 'a>> {
+    match image {
+        Some((image_key, info)) if image_key == key => ByteArrayWidget::from_data(data)
+            .map(|widget| Box::new(widget.image(Some(info))) as Box<dyn DataWidget>),
+        _ => predicate(data, TypeId::of::<StructDetailSlot>()),
+    }
+}
+
 // Struct / StructVariant implementation
 impl<'a, 'b> WidgetState for StructWidget<'a, 'b> {
     fn wants_init_state(&mut self, state: &ReflectionWidgetState) -> bool {
@@ -43,6 +63,7 @@ impl<'a, 'b> WidgetState for StructWidget<'a, 'b> {
                 .iter()
                 .map(|(key, _)| (*key, ReflectionWidgetState::None))
                 .collect(),
+            hitbox: Default::default(),
         }
     }
 }
@@ -97,13 +118,15 @@ impl<'a, 'b> DataWidget for StructWidget<'a, 'b> {
         };
 
         if *focused {
+            let image = ImageInfo::detect(self.fields);
+
             // Allocate complex value panel
             let (field_key, data) = self
                 .fields
                 .get_mut(*selected)
                 .unwrap_or_else(|| panic!("No field for selected index {}", selected));
 
-            if let Some(mut widget) = predicate(data, TypeId::of::<StructDetailSlot>()) {
+            if let Some(mut widget) = detail_widget(field_key, data, image, predicate) {
                 let (_, state_field) = state_fields
                     .iter_mut()
                     .find(|(key, _)| key == field_key)
@@ -141,9 +164,11 @@ impl<'a, 'b> DataWidget for StructWidget<'a, 'b> {
         let selected = *selected;
 
         if focused {
+            let image = ImageInfo::detect(self.fields);
+
             // Draw complex value panel
             let (field_key, data) = self.fields.get_mut(selected).unwrap();
-            if let Some(mut widget) = predicate(data, TypeId::of::<StructDetailSlot>()) {
+            if let Some(mut widget) = detail_widget(field_key, data, image, predicate) {
                 let (_, state) = state_fields
                     .iter_mut()
                     .find(|(key, _)| key == field_key)
@@ -152,6 +177,8 @@ impl<'a, 'b> DataWidget for StructWidget<'a, 'b> {
             }
         }
 
+        let mut hitbox = WidgetHitbox::default();
+
         if let Some(area) = area {
             // Calculate widths
             let max_key_width = self
@@ -181,6 +208,7 @@ impl<'a, 'b> DataWidget for StructWidget<'a, 'b> {
             let struct_block = Block::default().title(self.name).borders(Borders::ALL);
             let struct_inner_area = struct_block.inner(layout_chunks[0]);
             struct_block.render(layout_chunks[0], buf);
+            hitbox.area = layout_chunks[0];
 
             // Struct chunks
             let struct_chunks = tui::layout::Layout::default()
@@ -229,6 +257,17 @@ impl<'a, 'b> DataWidget for StructWidget<'a, 'b> {
 
                     widget.render_complex(vec![Some(item_area)].into_iter(), buf, state, predicate);
 
+                    // Clicking either the key or value selects the field
+                    hitbox.items.push((
+                        Rect {
+                            x: struct_inner_area.x,
+                            width: struct_inner_area.width,
+                            ..item_area
+                        },
+                        0,
+                        i,
+                    ));
+
                     if focused {
                         if selected == i {
                             buf.set_style(
@@ -245,5 +284,7 @@ impl<'a, 'b> DataWidget for StructWidget<'a, 'b> {
                 }
             }
         }
+
+        state.set_hitbox(hitbox);
     }
 }
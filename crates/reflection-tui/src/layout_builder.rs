@@ -10,6 +10,14 @@ pub trait LayoutStrategy {
 #[derive(Debug, Default)]
 pub struct LayoutHorizontal {
     sizes: Vec<(u16, u16)>,
+    offset: usize,
+}
+
+impl LayoutHorizontal {
+    /// Hide the last `offset` cells, bringing earlier ones into view
+    pub fn with_offset(self, offset: usize) -> Self {
+        LayoutHorizontal { offset, ..self }
+    }
 }
 
 impl LayoutStrategy for LayoutHorizontal {
@@ -23,8 +31,18 @@ impl LayoutStrategy for LayoutHorizontal {
         let mut x = desired.x;
         let y = desired.y;
 
+        let visible = self
+            .sizes
+            .len()
+            .saturating_sub(self.offset.min(self.sizes.len().saturating_sub(1)));
+
         for (i, (width, height)) in self.sizes.iter().copied().enumerate() {
-            if self.sizes[i..]
+            if i >= visible {
+                layout.push(None);
+                continue;
+            }
+
+            if self.sizes[i..visible]
                 .iter()
                 .copied()
                 .fold(0, |acc, (next, _)| acc + next)
@@ -50,6 +68,14 @@ impl LayoutStrategy for LayoutHorizontal {
 #[derive(Debug, Default)]
 pub struct LayoutVertical {
     sizes: Vec<(u16, u16)>,
+    offset: usize,
+}
+
+impl LayoutVertical {
+    /// Hide the last `offset` cells, bringing earlier ones into view
+    pub fn with_offset(self, offset: usize) -> Self {
+        LayoutVertical { offset, ..self }
+    }
 }
 
 impl LayoutStrategy for LayoutVertical {
@@ -63,8 +89,18 @@ impl LayoutStrategy for LayoutVertical {
         let x = desired.x;
         let mut y = desired.y;
 
+        let visible = self
+            .sizes
+            .len()
+            .saturating_sub(self.offset.min(self.sizes.len().saturating_sub(1)));
+
         for (i, (width, height)) in self.sizes.iter().copied().enumerate() {
-            if self.sizes[i..]
+            if i >= visible {
+                layout.push(None);
+                continue;
+            }
+
+            if self.sizes[i..visible]
                 .iter()
                 .copied()
                 .fold(0, |acc, (next, _)| acc + next)
@@ -0,0 +1,23 @@
+use tui::layout::Rect;
+use tui_widgets::contains;
+
+/// Screen areas of a rendered collection widget, recorded for mouse hit-testing
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WidgetHitbox {
+    pub area: Rect,
+    pub items: Vec<(Rect, usize, usize)>,
+}
+
+impl WidgetHitbox {
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        contains(self.area, column, row)
+    }
+
+    /// The (column, row) of the item rendered at the given terminal cell
+    pub fn item_at(&self, column: u16, row: u16) -> Option<(usize, usize)> {
+        self.items
+            .iter()
+            .find(|(rect, ..)| contains(*rect, column, row))
+            .map(|(_, column, row)| (*column, *row))
+    }
+}
@@ -1,6 +1,8 @@
+mod hitbox;
 mod state;
 use std::any::TypeId;
 
+pub use hitbox::*;
 pub use state::*;
 
 use reflection::data::Data;
@@ -13,6 +15,7 @@ use crate::{DataWidget, LayoutBuilder, LayoutHorizontal};
 pub struct ReflectionWidget<'a, 'b> {
     data: &'a mut Data,
     widget_predicate: &'b dyn Fn(&mut Data, TypeId) -> Option<Box<dyn DataWidget + '_>>,
+    offset: usize,
 }
 
 impl<'a, 'b> ReflectionWidget<'a, 'b> {
@@ -23,8 +26,14 @@ impl<'a, 'b> ReflectionWidget<'a, 'b> {
         ReflectionWidget {
             data,
             widget_predicate,
+            offset: 0,
         }
     }
+
+    /// Scroll the viewport back by `offset` panels, hiding the most deeply nested ones
+    pub fn offset(self, offset: usize) -> Self {
+        ReflectionWidget { offset, ..self }
+    }
 }
 
 impl<'a, 'b> StatefulWidget for ReflectionWidget<'a, 'b> {
@@ -32,7 +41,8 @@ impl<'a, 'b> StatefulWidget for ReflectionWidget<'a, 'b> {
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         if let Some(mut widget) = (self.widget_predicate)(self.data, TypeId::of::<()>()) {
-            let mut layout_builder = LayoutBuilder::new(area, LayoutHorizontal::default());
+            let mut layout_builder =
+                LayoutBuilder::new(area, LayoutHorizontal::default().with_offset(self.offset));
             widget.allocate_complex(&mut layout_builder, state, self.widget_predicate);
 
             let layout = layout_builder.build();
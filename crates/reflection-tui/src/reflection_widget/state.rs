@@ -1,26 +1,50 @@
+use std::collections::BTreeMap;
+
 use crossterm::event::{Event, KeyCode, KeyEvent};
+use tui_widgets::{MouseInput, MouseInputKind};
+
+use super::WidgetHitbox;
+use crate::BYTES_PER_ROW;
 
 /// [`reflection::Data`] eqivalent to hold persistent widget state
 pub enum ReflectionWidgetState {
     None,
+    /// Element states are created lazily and pruned once out of view
     List {
         selected: usize,
+        scroll: usize,
+        len: usize,
         focused: bool,
         focused_field: Option<usize>,
-        fields: Vec<ReflectionWidgetState>,
+        fields: BTreeMap<usize, ReflectionWidgetState>,
+        hitbox: WidgetHitbox,
     },
     Struct {
         selected: usize,
         focused: bool,
         focused_field: Option<usize>,
         fields: Vec<(&'static str, ReflectionWidgetState)>,
+        hitbox: WidgetHitbox,
     },
+    /// Entry states are created lazily and pruned once out of view
     Map {
         column: usize,
         row: usize,
+        scroll: usize,
+        len: usize,
         focused: bool,
         focused_field: Option<(usize, usize)>,
-        fields: Vec<(ReflectionWidgetState, ReflectionWidgetState)>,
+        fields: BTreeMap<usize, (ReflectionWidgetState, ReflectionWidgetState)>,
+        hitbox: WidgetHitbox,
+    },
+    ByteArray {
+        selected: usize,
+        scroll: usize,
+        len: usize,
+        page: usize,
+        focused: bool,
+        preview: bool,
+        hitbox: WidgetHitbox,
     },
 }
 
@@ -28,10 +52,8 @@ impl ReflectionWidgetState {
     pub fn focus(&mut self) -> bool {
         match self {
             ReflectionWidgetState::None => false,
-            ReflectionWidgetState::List {
-                focused, fields, ..
-            } => {
-                if fields.len() > 0 {
+            ReflectionWidgetState::List { focused, len, .. } => {
+                if *len > 0 {
                     *focused = true;
                     true
                 } else {
@@ -48,36 +70,295 @@ impl ReflectionWidgetState {
                     false
                 }
             }
-            ReflectionWidgetState::Map {
-                focused, fields, ..
-            } => {
-                if fields.len() > 0 {
+            ReflectionWidgetState::Map { focused, len, .. } => {
+                if *len > 0 {
                     *focused = true;
                     true
                 } else {
                     false
                 }
             }
+            ReflectionWidgetState::ByteArray { focused, len, .. } => {
+                *focused = *len > 0;
+                *focused
+            }
         }
     }
 
+    /// Defocus this widget along with any focused descendants
     pub fn defocus(&mut self) {
+        self.defocus_field();
+
+        match self {
+            ReflectionWidgetState::None => (),
+            ReflectionWidgetState::List { focused, .. }
+            | ReflectionWidgetState::Struct { focused, .. }
+            | ReflectionWidgetState::Map { focused, .. }
+            | ReflectionWidgetState::ByteArray { focused, .. } => *focused = false,
+        }
+    }
+
+    /// Defocus the currently focused field, if any
+    fn defocus_field(&mut self) {
+        if let Some(field) = self.focused_field_mut() {
+            field.defocus();
+        }
+
+        match self {
+            ReflectionWidgetState::None | ReflectionWidgetState::ByteArray { .. } => (),
+            ReflectionWidgetState::List { focused_field, .. }
+            | ReflectionWidgetState::Struct { focused_field, .. } => *focused_field = None,
+            ReflectionWidgetState::Map { focused_field, .. } => *focused_field = None,
+        }
+    }
+
+    /// Number of nested panels currently displayed for this widget
+    pub fn depth(&self) -> usize {
+        let selected = match self {
+            ReflectionWidgetState::None => return 0,
+            ReflectionWidgetState::ByteArray { .. } => return 1,
+            ReflectionWidgetState::List {
+                selected, fields, ..
+            } => fields.get(selected),
+            ReflectionWidgetState::Struct {
+                selected, fields, ..
+            } => fields.get(*selected).map(|(_, field)| field),
+            ReflectionWidgetState::Map {
+                column,
+                row,
+                fields,
+                ..
+            } => fields
+                .get(row)
+                .map(|(key, value)| if *column == 0 { key } else { value }),
+        };
+
+        match selected {
+            Some(field) if self.is_focused() => 1 + field.depth(),
+            _ => 1,
+        }
+    }
+
+    pub(crate) fn set_hitbox(&mut self, new_hitbox: WidgetHitbox) {
+        match self {
+            ReflectionWidgetState::None => (),
+            ReflectionWidgetState::List { hitbox, .. }
+            | ReflectionWidgetState::Struct { hitbox, .. }
+            | ReflectionWidgetState::Map { hitbox, .. }
+            | ReflectionWidgetState::ByteArray { hitbox, .. } => *hitbox = new_hitbox,
+        }
+    }
+
+    fn hitbox(&self) -> Option<&WidgetHitbox> {
+        match self {
+            ReflectionWidgetState::None => None,
+            ReflectionWidgetState::List { hitbox, .. }
+            | ReflectionWidgetState::Struct { hitbox, .. }
+            | ReflectionWidgetState::Map { hitbox, .. }
+            | ReflectionWidgetState::ByteArray { hitbox, .. } => Some(hitbox),
+        }
+    }
+
+    fn is_focused(&self) -> bool {
+        match self {
+            ReflectionWidgetState::None => false,
+            ReflectionWidgetState::List { focused, .. }
+            | ReflectionWidgetState::Struct { focused, .. }
+            | ReflectionWidgetState::Map { focused, .. }
+            | ReflectionWidgetState::ByteArray { focused, .. } => *focused,
+        }
+    }
+
+    fn focused_field_mut(&mut self) -> Option<&mut ReflectionWidgetState> {
+        match self {
+            ReflectionWidgetState::None | ReflectionWidgetState::ByteArray { .. } => None,
+            ReflectionWidgetState::List {
+                focused_field,
+                fields,
+                ..
+            } => fields.get_mut(&(*focused_field)?),
+            ReflectionWidgetState::Struct {
+                focused_field,
+                fields,
+                ..
+            } => fields.get_mut((*focused_field)?).map(|(_, field)| field),
+            ReflectionWidgetState::Map {
+                focused_field,
+                fields,
+                ..
+            } => {
+                let (column, row) = (*focused_field)?;
+                fields
+                    .get_mut(&row)
+                    .map(|(key, value)| if column == 0 { key } else { value })
+            }
+        }
+    }
+
+    /// The field shown in this widget's detail panel, if any
+    fn selected_field_mut(&mut self) -> Option<&mut ReflectionWidgetState> {
+        match self {
+            ReflectionWidgetState::None | ReflectionWidgetState::ByteArray { .. } => None,
+            ReflectionWidgetState::List {
+                selected,
+                len,
+                fields,
+                ..
+            } if *selected < *len => Some(
+                fields
+                    .entry(*selected)
+                    .or_insert(ReflectionWidgetState::None),
+            ),
+            ReflectionWidgetState::List { .. } => None,
+            ReflectionWidgetState::Struct {
+                selected, fields, ..
+            } => fields.get_mut(*selected).map(|(_, field)| field),
+            ReflectionWidgetState::Map {
+                column,
+                row,
+                len,
+                fields,
+                ..
+            } if *row < *len => {
+                let (key, value) = fields
+                    .entry(*row)
+                    .or_insert((ReflectionWidgetState::None, ReflectionWidgetState::None));
+                Some(if *column == 0 { key } else { value })
+            }
+            ReflectionWidgetState::Map { .. } => None,
+        }
+    }
+
+    /// Move the selection to the given cell, focus this widget and defocus any previous field
+    fn select_cell(&mut self, cell_column: usize, cell_row: usize) {
+        self.defocus_field();
+
         match self {
             ReflectionWidgetState::None => (),
-            ReflectionWidgetState::List { focused, .. } => *focused = false,
-            ReflectionWidgetState::Struct { focused, .. } => *focused = false,
-            ReflectionWidgetState::Map { focused, .. } => *focused = false,
+            ReflectionWidgetState::List {
+                selected, focused, ..
+            }
+            | ReflectionWidgetState::Struct {
+                selected, focused, ..
+            }
+            | ReflectionWidgetState::ByteArray {
+                selected, focused, ..
+            } => {
+                *selected = cell_row;
+                *focused = true;
+            }
+            ReflectionWidgetState::Map {
+                column,
+                row,
+                focused,
+                ..
+            } => {
+                *column = cell_column;
+                *row = cell_row;
+                *focused = true;
+            }
+        }
+    }
+
+    /// Mark the selected field as focused, as if it had been entered via keyboard
+    fn enter_selected(&mut self) {
+        let focus = match self.selected_field_mut() {
+            Some(field) => field.is_focused() || field.focus(),
+            None => false,
+        };
+
+        if !focus {
+            return;
+        }
+
+        match self {
+            ReflectionWidgetState::None | ReflectionWidgetState::ByteArray { .. } => (),
+            ReflectionWidgetState::List {
+                selected,
+                focused_field,
+                ..
+            }
+            | ReflectionWidgetState::Struct {
+                selected,
+                focused_field,
+                ..
+            } => *focused_field = Some(*selected),
+            ReflectionWidgetState::Map {
+                column,
+                row,
+                focused_field,
+                ..
+            } => *focused_field = Some((*column, *row)),
+        }
+    }
+
+    /// Hit-test a mouse input against the most recently rendered layout
+    ///
+    /// Returns true if the input landed on this widget or one of its descendants.
+    pub fn handle_mouse(&mut self, input: &MouseInput) -> bool {
+        let hitbox = match self.hitbox() {
+            Some(hitbox) => hitbox,
+            None => return false,
+        };
+
+        let inside = hitbox.contains(input.column, input.row);
+        let item = hitbox.item_at(input.column, input.row);
+
+        // The detail panel of the selected field is drawn outside of this widget
+        if !inside && self.is_focused() {
+            let handled = match self.selected_field_mut() {
+                Some(field) => field.handle_mouse(input),
+                None => false,
+            };
+
+            if handled {
+                self.enter_selected();
+            }
+            return handled;
+        }
+
+        if !inside {
+            return false;
+        }
+
+        match input.kind {
+            MouseInputKind::ScrollUp | MouseInputKind::ScrollDown => {
+                self.defocus_field();
+
+                if !self.is_focused() {
+                    self.focus();
+                } else if self.field_count() > 0 {
+                    match input.kind {
+                        MouseInputKind::ScrollUp => self.select_prev(),
+                        _ => self.select_next(),
+                    }
+                }
+            }
+            MouseInputKind::Click => match item {
+                Some((column, row)) => self.select_cell(column, row),
+                None => {
+                    self.focus();
+                }
+            },
+            MouseInputKind::DoubleClick => {
+                if let Some((column, row)) = item {
+                    self.select_cell(column, row);
+                    self.enter_selected();
+                }
+            }
         }
+
+        true
     }
 
     fn field_count(&mut self) -> usize {
         match self {
             ReflectionWidgetState::None => 0,
-            ReflectionWidgetState::List { fields, .. } => fields.len(),
+            ReflectionWidgetState::List { len, .. } => *len,
             ReflectionWidgetState::Struct { fields, .. } => fields.len(),
-            ReflectionWidgetState::Map { fields, .. } => fields.len(),
+            ReflectionWidgetState::Map { len, .. } => *len,
+            ReflectionWidgetState::ByteArray { len, .. } => *len,
         }
-        
     }
 
     fn select_next(&mut self) {
@@ -91,6 +372,9 @@ impl ReflectionWidgetState {
                     *selected = 0
                 }
             }
+            ReflectionWidgetState::ByteArray { .. } => {
+                self.move_byte_selection(BYTES_PER_ROW as isize)
+            }
             _ => (),
         }
     }
@@ -106,27 +390,106 @@ impl ReflectionWidgetState {
                     *selected = field_count - 1
                 }
             }
+            ReflectionWidgetState::ByteArray { .. } => {
+                self.move_byte_selection(-(BYTES_PER_ROW as isize))
+            }
             _ => (),
         }
     }
 
+    /// Move a byte array selection by `delta` bytes, clamping to its bounds
+    fn move_byte_selection(&mut self, delta: isize) {
+        if let ReflectionWidgetState::ByteArray { selected, len, .. } = self {
+            let target = *selected as isize + delta;
+            *selected = target.max(0).min(len.saturating_sub(1) as isize) as usize;
+        }
+    }
+
     pub fn handle_input(&mut self, event: &Event) -> bool {
         match self {
             ReflectionWidgetState::None => false,
+            ReflectionWidgetState::ByteArray {
+                selected,
+                page,
+                len,
+                focused,
+                preview,
+                ..
+            } => {
+                if let Event::Key(KeyEvent { code, .. }) = event {
+                    let page = (*page * BYTES_PER_ROW) as isize;
+                    match code {
+                        KeyCode::Esc => false,
+                        KeyCode::Char('h') => {
+                            if *selected % BYTES_PER_ROW == 0 {
+                                false
+                            } else {
+                                *selected -= 1;
+                                true
+                            }
+                        }
+                        KeyCode::Char('l') => {
+                            self.move_byte_selection(1);
+                            true
+                        }
+                        KeyCode::Char('j') => {
+                            if !*focused {
+                                *focused = true;
+                            } else {
+                                self.select_next();
+                            }
+                            true
+                        }
+                        KeyCode::Char('k') => {
+                            if !*focused {
+                                *focused = true;
+                            } else {
+                                self.select_prev();
+                            }
+                            true
+                        }
+                        KeyCode::PageDown => {
+                            self.move_byte_selection(page);
+                            true
+                        }
+                        KeyCode::PageUp => {
+                            self.move_byte_selection(-page);
+                            true
+                        }
+                        KeyCode::Home => {
+                            *selected = 0;
+                            true
+                        }
+                        KeyCode::End => {
+                            *selected = len.saturating_sub(1);
+                            true
+                        }
+                        KeyCode::Char('p') => {
+                            *preview = !*preview;
+                            true
+                        }
+                        _ => true,
+                    }
+                } else {
+                    true
+                }
+            }
             ReflectionWidgetState::List {
                 selected,
+                len,
                 focused,
                 focused_field,
                 fields,
                 ..
             } => {
                 if let Some(i) = focused_field {
-                    if *i >= fields.len() {
+                    let field = if let Some(field) = fields.get_mut(i) {
+                        field
+                    } else {
                         *focused_field = None;
                         return true;
-                    }
+                    };
 
-                    let field = fields.get_mut(*i).unwrap();
                     if !field.handle_input(event) {
                         field.defocus();
                         *focused_field = None;
@@ -153,9 +516,13 @@ impl ReflectionWidgetState {
                                 true
                             }
                             KeyCode::Char('l') | KeyCode::Enter => {
-                                let field = fields.get_mut(*selected).unwrap();
-                                if field.focus() {
-                                    *focused_field = Some(*selected);
+                                if *selected < *len {
+                                    let field = fields
+                                        .entry(*selected)
+                                        .or_insert(ReflectionWidgetState::None);
+                                    if field.focus() {
+                                        *focused_field = Some(*selected);
+                                    }
                                 }
                                 true
                             }
@@ -222,18 +589,19 @@ impl ReflectionWidgetState {
             ReflectionWidgetState::Map {
                 column,
                 row,
+                len,
                 focused,
                 focused_field,
                 fields,
                 ..
             } => {
                 if let Some((column, row)) = focused_field {
-                    if *row >= fields.len() {
+                    let (key, value) = if let Some(field) = fields.get_mut(row) {
+                        field
+                    } else {
                         *focused_field = None;
                         return true;
-                    }
-
-                    let (key, value) = fields.get_mut(*row).unwrap();
+                    };
 
                     let field = if *column == 0 { key } else { value };
 
@@ -275,11 +643,16 @@ impl ReflectionWidgetState {
                                 true
                             }
                             KeyCode::Enter => {
-                                let (key, value) = fields.get_mut(*row).unwrap();
-                                let field = if *column == 0 { key } else { value };
+                                if *row < *len {
+                                    let (key, value) = fields.entry(*row).or_insert((
+                                        ReflectionWidgetState::None,
+                                        ReflectionWidgetState::None,
+                                    ));
+                                    let field = if *column == 0 { key } else { value };
 
-                                if field.focus() {
-                                    *focused_field = Some((*column, *row));
+                                    if field.focus() {
+                                        *focused_field = Some((*column, *row));
+                                    }
                                 }
                                 true
                             }
@@ -1,51 +1,37 @@
 // TODO: Improve API
 //       Structure
 //       Report item querying
-//          Logical / Physical / Normalized lookup for axes / hat switches
 //          Convenience functions for indexed button checking
-//       Formalize device polling
-//          Event queue structure via shared state is preferable
-//          Change events for individual items
 //
 // TODO: Fix USBDK lockup
 //       Seems to have trouble with DualShock 4
 //       May be better to go back to report reading being an optional codepath
 //
-// TODO: Load fallbacks from disk
-//       Will either need to make a wrapper type, or make fallbacks own their report bytes
-//
 // TODO: Test with mouse
 // TODO: Test with keyboard
-//
-// TODO: Provide means to access collection paths from ItemData
-//       Can't use Vec since it's non-copy
-//       May be better to use a similar solution to names and use a lookup table
 
 mod error;
-mod fallbacks;
 
 #[cfg(test)]
 mod tests;
 
 use antigen_hid::{
-    devices::{DeviceId, Devices},
+    devices::Devices,
+    fallbacks::fallback_t16000m,
+    polling::Poller,
     report::{
         global_state::UsagePage,
+        input_report::ReportValue,
         local_state::{GenericDesktopUsage, Usage},
-        input_report::{InputReport, ReportValue},
     },
 };
 use error::*;
-use fallbacks::*;
 
-use std::{
-    collections::BTreeMap,
-    fmt::Write,
-    sync::{Arc, RwLock},
-    time::Duration,
-};
+use std::{collections::VecDeque, fmt::Write, path::Path, time::Duration};
 
 const USE_USBDK: bool = false;
+const REPORT_DESCRIPTOR_DIR: &str = "report_descriptors";
+const EVENT_LOG_LEN: usize = 8;
 
 fn main() -> Result<(), Error> {
     // Initialize rusb
@@ -60,37 +46,27 @@ fn main() -> Result<(), Error> {
     }?;
 
     // Enumerate valid devices
-    let report_descriptors = fallback_t16000m();
+    let mut report_descriptors = fallback_t16000m();
     //report_descriptors.dump(&Path::new("."))?;
 
+    // Descriptors on disk take precedence over built-in fallbacks
+    let report_descriptor_dir = Path::new(REPORT_DESCRIPTOR_DIR);
+    if report_descriptor_dir.is_dir() {
+        for e in report_descriptors.load_dir(report_descriptor_dir)? {
+            eprintln!("Failed to load report descriptor: {}", e);
+        }
+    }
+
     let mut devices = Devices::new();
     devices.enumerate(context, &report_descriptors)?;
 
-    // Shared state for transferring input data between threads
-    let state: Arc<RwLock<BTreeMap<DeviceId, InputReport>>> = Default::default();
-
-    // Spawn one thread per valid input device
+    // Poll each valid input device on its own thread
+    let mut poller = Poller::new();
     let (devices, device_names) = devices.take();
     for (device_id, device) in devices {
-        let state = state.clone();
-        std::thread::spawn(move || {
-            let mut buf = vec![0; device.input_buffer_len()];
-            let device_handle = device.open().unwrap();
-
-            loop {
-                device_handle
-                    .read_interrupt(
-                        device.interrupt_input(),
-                        &mut buf,
-                        Duration::from_secs(1000),
-                    )
-                    .unwrap();
-
-                let result = device.parse(buf.iter().copied());
-
-                state.write().unwrap().insert(device_id, result);
-            }
-        });
+        if let Err(e) = poller.poll(device_id, device) {
+            eprintln!("Failed to open device: {}", e);
+        }
     }
 
     // Initialize TUI
@@ -118,6 +94,7 @@ fn main() -> Result<(), Error> {
     });
 
     // Run main loop
+    let mut event_log = VecDeque::new();
     'main: loop {
         // Handle crossterm events
         while let Ok(event) = crossterm_rx.try_recv() {
@@ -133,10 +110,18 @@ fn main() -> Result<(), Error> {
             }
         }
 
+        // Keep a log of the most recent device events
+        for event in poller.events().try_iter() {
+            if event_log.len() == EVENT_LOG_LEN {
+                event_log.pop_front();
+            }
+            event_log.push_back(event.to_string());
+        }
+
         // Write TUI output to string buffer
         let mut buf = String::default();
 
-        for (device_id, results) in state.read().unwrap().iter() {
+        for (device_id, results) in poller.snapshots().iter() {
             let device_name = device_names.get(device_id).unwrap();
 
             writeln!(
@@ -154,7 +139,7 @@ fn main() -> Result<(), Error> {
             let mut hat_switches = vec![];
             let mut other = vec![];
 
-            for input_value in results.iter() {
+            for input_value in results.values() {
                 let data_item = input_value.data_item();
                 if let Some(UsagePage::Button) = data_item.global_state.usage_page {
                     buttons.push(input_value);
@@ -205,15 +190,22 @@ fn main() -> Result<(), Error> {
                     let data_item = input_value.data_item();
                     let value = input_value.report_value();
                     let name = format!("{}", data_item.local_state.usage.unwrap());
-                    writeln!(buf, "{}: {:?}", name, value).unwrap();
+                    match (input_value.normalized_signed(), input_value.physical()) {
+                        (Some(normalized), Some(physical)) => {
+                            writeln!(buf, "{}: {:+.3} ({})", name, normalized, physical).unwrap()
+                        }
+                        _ => writeln!(buf, "{}: {:?}", name, value).unwrap(),
+                    }
                 }
             }
 
             if hat_switches.len() > 0 {
                 write!(buf, "Hat Switches: ")?;
                 for input_value in hat_switches {
-                    let value = input_value.report_value();
-                    write!(buf, "{}, ", value).unwrap();
+                    match input_value.hat_switch() {
+                        Some(direction) => write!(buf, "{}, ", direction).unwrap(),
+                        None => write!(buf, "{}, ", input_value.report_value()).unwrap(),
+                    }
                 }
             }
 
@@ -235,6 +227,11 @@ fn main() -> Result<(), Error> {
             writeln!(buf)?;
         }
 
+        writeln!(buf, "Events:")?;
+        for event in event_log.iter() {
+            writeln!(buf, "{}", event)?;
+        }
+
         // Render TUI
         terminal.draw(|f| {
             let size = f.size();
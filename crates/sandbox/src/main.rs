@@ -17,6 +17,7 @@ use remote_channel::*;
 
 use legion::*;
 use parking_lot::{Mutex, RwLock};
+use tracing_subscriber::layer::SubscriberExt;
 use std::{
     sync::{atomic::AtomicBool, Arc},
     time::{Duration, Instant},
@@ -72,6 +73,7 @@ pub struct Shared {
     trace_archetypes: Arc<RwLock<Archetypes>>,
     trace_entities: Arc<RwLock<Entities>>,
     trace_resources: Arc<RwLock<tui_debugger::Resources>>,
+    trace_queries: Arc<RwLock<tui_debugger::SearchQueries>>,
 }
 
 impl SharedState for Shared {
@@ -80,11 +82,19 @@ impl SharedState for Shared {
         resources.insert(self.trace_archetypes.clone());
         resources.insert(self.trace_entities.clone());
         resources.insert(self.trace_resources.clone());
+        resources.insert(self.trace_queries.clone());
         resources
     }
 }
 
 fn main() {
+    // Collect profiling spans for the schedule debugger
+    let (trace_sender, trace_receiver) = antigen_tracing::TraceSender::unbounded();
+    tracing::subscriber::set_global_default(
+        tracing_subscriber::registry::Registry::default().with(trace_sender),
+    )
+    .expect("Failed to set global default tracing subscriber");
+
     profiling::scope!("Main");
 
     let shared_state = Shared::default();
@@ -145,6 +155,7 @@ fn main() {
     let tui_render_thread_handle = std::thread::spawn(threads::tui_render_thread(
         shared_state,
         crossterm_rx,
+        trace_receiver,
         main_loop_break.clone(),
     ));
 
@@ -1,26 +1,38 @@
+use antigen_tracing::TraceTree;
 use legion_debugger::{Archetypes, Entities};
 use parking_lot::RwLock;
 use std::sync::Arc;
 
-use crossterm::event::KeyCode;
 use legion::{system, Resources as LegionResources, World};
 
-use tui_debugger::{Resources as TuiDebuggerResources, TuiDebugger, TuiDebuggerState};
+use tui_debugger::{
+    Resources as TuiDebuggerResources, SearchQueries, TuiDebugger, TuiDebuggerConfig,
+    TuiDebuggerState,
+};
 
 use crate::resources::CrosstermEventQueue;
 
+/// Create debugger state with the keymap and theme from the config file, if one exists
+pub fn tui_debugger_state() -> TuiDebuggerState {
+    let config = TuiDebuggerConfig::from_env().unwrap_or_else(|e| {
+        log::error!("Failed to load tui-debugger config: {:?}", e);
+        Default::default()
+    });
+    TuiDebuggerState::new(config)
+}
+
 #[profiling::function]
 pub fn tui_debugger_handle_input(
     events: &CrosstermEventQueue,
     tui_debugger_state: &mut TuiDebuggerState,
 ) {
     for event in events.iter() {
-        if let crossterm::event::Event::Key(crossterm::event::KeyEvent {
-            code: KeyCode::Char(code),
-            ..
-        }) = event
-        {
-            tui_debugger_state.handle_input(*code)
+        match event {
+            crossterm::event::Event::Key(key_event) => tui_debugger_state.handle_key(key_event),
+            crossterm::event::Event::Mouse(mouse_event) => {
+                tui_debugger_state.handle_mouse(mouse_event)
+            }
+            _ => (),
         }
     }
 }
@@ -31,11 +43,18 @@ pub fn tui_debugger_parse_archetypes_thread_local() -> impl FnMut(&mut World, &m
     let (world_serializer, entity_serializer) = legion_debugger::world_serializers();
 
     move |world, resources| {
+        let filter = resources
+            .get::<Arc<RwLock<SearchQueries>>>()
+            .unwrap()
+            .read()
+            .archetypes
+            .layout_filter(&world_serializer);
+
         resources
             .get_mut::<Arc<RwLock<Archetypes>>>()
             .unwrap()
             .write()
-            .parse_archetypes(world, &legion::any(), &world_serializer, &entity_serializer)
+            .parse_archetypes(world, &filter, &world_serializer, &entity_serializer)
     }
 }
 
@@ -44,11 +63,18 @@ pub fn tui_debugger_parse_entities_thread_local() -> impl FnMut(&mut World, &mut
     let (world_serializer, entity_serializer) = legion_debugger::world_serializers();
 
     move |world, resources| {
+        let filter = resources
+            .get::<Arc<RwLock<SearchQueries>>>()
+            .unwrap()
+            .read()
+            .entities
+            .layout_filter(&world_serializer);
+
         resources
             .get_mut::<Arc<RwLock<Entities>>>()
             .unwrap()
             .write()
-            .parse_entities(world, &legion::any(), &world_serializer, &entity_serializer)
+            .parse_entities(world, &filter, &world_serializer, &entity_serializer)
     }
 }
 
@@ -71,6 +97,19 @@ pub fn tui_debugger_draw(
     #[resource] archetypes: &Archetypes,
     #[resource] entities: &Entities,
     #[resource] resources: &TuiDebuggerResources,
+    #[resource] search_queries: &Arc<RwLock<SearchQueries>>,
+    #[resource] trace_tree: &TraceTree,
 ) {
-    tui_debugger.draw(tui_debugger_state, archetypes, entities, resources);
+    let queries = tui_debugger_state.search_queries();
+    if *search_queries.read() != queries {
+        *search_queries.write() = queries;
+    }
+
+    tui_debugger.draw(
+        tui_debugger_state,
+        archetypes,
+        entities,
+        resources,
+        Some(trace_tree),
+    );
 }
@@ -7,36 +7,105 @@ use std::{
     time::Duration,
 };
 
+use antigen_tracing::{TraceLeafVariant, TraceReceiver, TraceTree};
 use crossbeam_channel::Receiver;
 use crossterm::event::Event;
 use reflection::data::Data;
 use reflection_tui::{standard_widgets, DataWidget, ReflectionWidget, ReflectionWidgetState};
 use tui_debugger::TuiDebugger;
+use tui_widgets::{ClickTracker, MouseInputKind};
 
 use crate::{
     resources::CrosstermEventQueue,
     spin_loop,
-    systems::{crossterm_input_buffer_clear, crossterm_input_buffer_fill},
+    systems::{
+        crossterm_input_buffer_clear, crossterm_input_buffer_fill, tui_debugger_handle_input,
+        tui_debugger_state,
+    },
     Shared,
 };
 
+/// Draw the full debugger instead of a plain reflection view of the world
+const TUI_DEBUGGER: bool = true;
+
 const TUI_TICK_HZ: f64 = 60.0;
 const TUI_TICK_SECS: f64 = 1.0 / TUI_TICK_HZ;
 
+/// Number of ticks that closed spans are kept in the trace tree for
+const TRACE_HISTORY: usize = 60;
+
 #[profiling::function]
 pub fn tui_render_thread(
     shared_state: Shared,
     crossterm_rx: Receiver<Event>,
+    trace_receiver: TraceReceiver,
     main_loop_break: Arc<AtomicBool>,
 ) -> impl FnOnce() {
     let mut tui_debugger = TuiDebugger::start().unwrap();
+    let mut tui_debugger_state = tui_debugger_state();
+    let mut trace_tree = TraceTree::default();
     let mut crossterm_event_queue = CrosstermEventQueue::default();
     let mut reflection_widget_state = ReflectionWidgetState::None;
+    let mut click_tracker = ClickTracker::default();
+    let mut viewport_offset: usize = 0;
 
     spin_loop(Duration::from_secs_f64(TUI_TICK_SECS), move || {
+        // Drop closed spans and events older than the history window, keeping open parents.
+        // Pruning before the flush ensures the debugger has ingested every event being dropped.
+        if let Some(oldest) = trace_tree.generation().checked_sub(TRACE_HISTORY) {
+            trace_tree.prune_by(|_, leaf| {
+                leaf.generation < oldest
+                    && !matches!(leaf.variant, TraceLeafVariant::Span { open: true, .. })
+            });
+        }
+
+        trace_receiver.flush(&mut trace_tree);
+
         crossterm_input_buffer_fill(&crossterm_rx, &mut crossterm_event_queue);
+
+        if TUI_DEBUGGER {
+            tui_debugger_handle_input(&crossterm_event_queue, &mut tui_debugger_state);
+            crossterm_input_buffer_clear(&mut crossterm_event_queue);
+
+            let queries = tui_debugger_state.search_queries();
+            if *shared_state.trace_queries.read() != queries {
+                *shared_state.trace_queries.write() = queries;
+            }
+
+            tui_debugger.draw(
+                &mut tui_debugger_state,
+                &shared_state.trace_archetypes.read(),
+                &shared_state.trace_entities.read(),
+                &shared_state.trace_resources.read(),
+                Some(&trace_tree),
+            );
+
+            return main_loop_break.load(Ordering::Relaxed);
+        }
+
         for event in crossterm_event_queue.iter() {
-            reflection_widget_state.handle_input(event);
+            match event {
+                Event::Mouse(mouse_event) => {
+                    if let Some(input) = click_tracker.input(mouse_event) {
+                        // Scrolling outside of any widget pans the viewport
+                        if !reflection_widget_state.handle_mouse(&input) {
+                            match input.kind {
+                                MouseInputKind::ScrollUp => {
+                                    viewport_offset = (viewport_offset + 1)
+                                        .min(reflection_widget_state.depth().saturating_sub(1))
+                                }
+                                MouseInputKind::ScrollDown => {
+                                    viewport_offset = viewport_offset.saturating_sub(1)
+                                }
+                                _ => (),
+                            }
+                        }
+                    }
+                }
+                event => {
+                    reflection_widget_state.handle_input(event);
+                }
+            }
         }
         crossterm_input_buffer_clear(&mut crossterm_event_queue);
 
@@ -62,7 +131,8 @@ pub fn tui_render_thread(
                 .terminal()
                 .draw(|f| {
                     f.render_stateful_widget(
-                        ReflectionWidget::new(&mut debugger_data, &widget_rules),
+                        ReflectionWidget::new(&mut debugger_data, &widget_rules)
+                            .offset(viewport_offset),
                         f.size(),
                         &mut reflection_widget_state,
                     )
@@ -1,9 +1,5 @@
-mod trace_span;
-mod trace_tree;
 mod layers;
 mod env_log_tracer;
 
-pub use trace_span::*;
-pub use trace_tree::*;
 pub use layers::*;
 pub use env_log_tracer::*;
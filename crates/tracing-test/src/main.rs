@@ -180,15 +180,15 @@ fn print_callsites(trace_tree: &TraceTree) {
             .filter(|(_, leaf)| leaf.metadata.callsite() == id)
         {
             match &leaf.variant {
-                TraceLeafVariant::Span {
-                    entered, exited, ..
-                } => {
-                    println!(
-                        "\t{:?} entered {:?}, exited {:?}",
-                        leaf.thread.id(),
-                        entered,
-                        exited
-                    );
+                TraceLeafVariant::Span { intervals, .. } => {
+                    for interval in intervals {
+                        println!(
+                            "\t{:?} entered {:?}, exited {:?}",
+                            leaf.thread.id(),
+                            interval.entered,
+                            interval.exited
+                        );
+                    }
                 }
                 TraceLeafVariant::Event { instant } => {
                     println!("\t{:?} at {:?}", leaf.thread.id(), instant);
@@ -1,18 +1,24 @@
-use legion_debugger::Archetypes;
+use legion_debugger::{Archetypes, EntityQuery};
 
-use tui_widgets::TabContainer;
+use tui_widgets::{ListHitbox, MouseInput, TabAreas, TabContainer};
 
 use tui::{
     buffer::Buffer,
-    layout::{Constraint, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     style::Style,
     text::Spans,
-    widgets::{Row, StatefulWidget, TableState, Widget},
+    widgets::{Paragraph, Row, StatefulWidget, TableState, Widget},
+};
+
+use super::{
+    navigation::{navigate, page_size, scroll_action},
+    Action, Input, SearchBar, SearchState, Theme,
 };
 
 #[derive(Debug, Copy, Clone)]
 pub enum ArchetypesFocus {
     None,
+    Search,
     Tabs,
     Table,
 }
@@ -26,74 +32,130 @@ impl Default for ArchetypesFocus {
 #[derive(Debug, Default, Clone)]
 pub struct ArchetypeState {
     focus: ArchetypesFocus,
+    search: SearchState,
     table_state: TableState,
     active_table: usize,
     archetype_count: usize,
     entity_count: usize,
+    page_size: usize,
+    search_area: Rect,
+    tab_areas: TabAreas,
+    table_hitbox: ListHitbox,
 }
 
 impl ArchetypeState {
+    pub fn query(&self) -> &EntityQuery {
+        self.search.query()
+    }
+
+    pub fn searching(&self) -> bool {
+        matches!(self.focus, ArchetypesFocus::Search)
+    }
+
     pub fn set_focus(&mut self, focus: ArchetypesFocus) {
         self.focus = focus;
+
+        if let ArchetypesFocus::Search = self.focus {
+            self.search.begin();
+        }
     }
 
-    pub fn handle_input(&mut self, input: char) -> ArchetypesFocus {
+    pub fn handle_input(&mut self, input: Input) -> ArchetypesFocus {
         match self.focus {
             ArchetypesFocus::None => (),
-            ArchetypesFocus::Tabs => match input {
-                'h' => {
+            ArchetypesFocus::Search => {
+                if !self.search.handle_input(input) {
+                    self.active_table = 0;
+                    self.set_focus(ArchetypesFocus::Tabs);
+                }
+            }
+            ArchetypesFocus::Tabs => match input.action {
+                Some(Action::Search) => self.set_focus(ArchetypesFocus::Search),
+                Some(Action::Left) if self.archetype_count > 0 => {
                     self.active_table = self
                         .active_table
                         .checked_sub(1)
                         .unwrap_or(self.archetype_count - 1)
                 }
-                'l' => {
+                Some(Action::Right) if self.archetype_count > 0 => {
                     self.active_table = self
                         .active_table
                         .wrapping_add(1)
                         .wrapping_rem(self.archetype_count)
                 }
-                'j' => {
+                Some(Action::First) => self.active_table = 0,
+                Some(Action::Last) => self.active_table = self.archetype_count.saturating_sub(1),
+                Some(Action::Down) | Some(Action::Confirm) if self.archetype_count > 0 => {
                     self.table_state.select(Some(0));
                     self.focus = ArchetypesFocus::Table;
                 }
-                'k' => self.focus = ArchetypesFocus::None,
+                Some(Action::Up) | Some(Action::Cancel) => self.focus = ArchetypesFocus::None,
                 _ => (),
             },
-            ArchetypesFocus::Table => match input {
-                'h' => {
+            ArchetypesFocus::Table => match input.action {
+                Some(Action::Search) => self.set_focus(ArchetypesFocus::Search),
+                Some(Action::Left) | Some(Action::Cancel) => {
                     self.table_state.select(None);
                     self.focus = ArchetypesFocus::Tabs;
                 }
-                'j' => self.table_state.select(Some(
-                    self.table_state
-                        .selected()
-                        .unwrap_or_default()
-                        .wrapping_add(1)
-                        .wrapping_rem(self.entity_count),
+                Some(action) => self.table_state.select(navigate(
+                    self.table_state.selected(),
+                    self.entity_count,
+                    self.page_size,
+                    action,
                 )),
-                'k' => self.table_state.select(Some(
-                    self.table_state
-                        .selected()
-                        .unwrap_or_default()
-                        .checked_sub(1)
-                        .unwrap_or(self.entity_count),
-                )),
-                _ => (),
+                None => (),
             },
         }
 
         self.focus
     }
+
+    /// Select the archetype tab or entity row under the cursor
+    ///
+    /// Scrolling over the tab bar cycles through archetypes.
+    pub fn handle_mouse(&mut self, input: MouseInput) -> ArchetypesFocus {
+        let (column, row) = (input.column, input.row);
+
+        if let Some(action) = scroll_action(&input) {
+            if input.hit(self.tab_areas.bar) {
+                self.handle_input(Input::action(match action {
+                    Action::Up => Action::Left,
+                    _ => Action::Right,
+                }));
+            } else if self.table_hitbox.contains(column, row) {
+                self.table_state.select(navigate(
+                    self.table_state.selected(),
+                    self.entity_count,
+                    self.page_size,
+                    action,
+                ));
+            }
+        } else if input.hit(self.search_area) {
+            self.set_focus(ArchetypesFocus::Search);
+        } else if let Some(index) = self.tab_areas.tab_at(column, row) {
+            if index != self.active_table {
+                self.active_table = index;
+                self.table_state.select(None);
+            }
+            self.focus = ArchetypesFocus::Tabs;
+        } else if let Some(index) = self.table_hitbox.index_at(column, row) {
+            self.table_state.select(Some(index));
+            self.focus = ArchetypesFocus::Table;
+        }
+
+        self.focus
+    }
 }
 
 pub struct ArchetypeDebugger<'a> {
     archetypes: &'a Archetypes,
+    theme: &'a Theme,
 }
 
 impl<'a> ArchetypeDebugger<'a> {
-    pub fn new(archetypes: &'a Archetypes) -> Self {
-        Self { archetypes }
+    pub fn new(archetypes: &'a Archetypes, theme: &'a Theme) -> Self {
+        Self { archetypes, theme }
     }
 }
 
@@ -101,31 +163,78 @@ impl<'a> StatefulWidget for ArchetypeDebugger<'a> {
     type State = ArchetypeState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        /*
-        TabContainer::new(super::style::tabs)
+        let theme = self.theme;
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+
+        SearchBar::new(
+            &state.search,
+            theme,
+            matches!(state.focus, ArchetypesFocus::Search),
+        )
+        .render(chunks[0], buf);
+        state.search_area = chunks[0];
+
+        let archetypes = match self.archetypes.parse() {
+            Ok(archetypes) => archetypes,
+            Err(e) => {
+                Paragraph::new(format!("{:#?}", e))
+                    .block(theme.block("Archetypes"))
+                    .render(chunks[1], buf);
+                return;
+            }
+        };
+
+        let query = state.search.query();
+        let archetypes = archetypes
+            .into_iter()
+            .filter(|archetype| query.matches_archetype(archetype))
+            .collect::<Vec<_>>();
+
+        state.archetype_count = archetypes.len();
+        if state.active_table >= state.archetype_count {
+            state.active_table = 0;
+        }
+
+        state.entity_count = archetypes
+            .get(state.active_table)
+            .map(|archetype| archetype.entities().len())
+            .unwrap_or_default();
+        // Account for the tab bar and table header
+        state.page_size = page_size(chunks[1].height.saturating_sub(4));
+
+        if let Some(selected) = state.table_state.selected() {
+            if selected >= state.entity_count {
+                state.table_state.select(state.entity_count.checked_sub(1));
+            }
+        }
+
+        let focus = state.focus;
+        let table_state = &mut state.table_state;
+        let table_hitbox = &mut state.table_hitbox;
+
+        TabContainer::new(|titles, focused| theme.tabs(titles, focused))
             .titles(
-                self.archetypes
+                archetypes
                     .iter()
-                    .map(|archetype| {
-                        archetype
-                            .components()
-                            .keys()
-                            .map(String::as_str)
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    })
+                    .map(|archetype| archetype.layout().collect::<Vec<_>>().join(", "))
                     .map(Spans::from)
                     .collect::<Vec<_>>(),
             )
-            .block(super::style::block("Archetypes"))
+            .block(theme.block(format!("Archetypes ({})", state.archetype_count)))
             .select(state.active_table)
-            .highlight(matches!(state.focus, ArchetypesFocus::Tabs))
+            .highlight(matches!(focus, ArchetypesFocus::Tabs))
+            .areas(&mut state.tab_areas)
             .next(|area, buf, index| {
-                if self.archetypes.is_empty() {
+                let archetype = if let Some(archetype) = archetypes.get(index) {
+                    archetype
+                } else {
                     return;
-                }
+                };
 
-                let archetype = &self.archetypes[index];
                 let entities = archetype.entities();
                 let components = archetype.components();
 
@@ -133,7 +242,7 @@ impl<'a> StatefulWidget for ArchetypeDebugger<'a> {
                     .chain(components.keys().cloned())
                     .collect::<Vec<_>>();
 
-                let column_count = components.len();
+                let column_count = components.len().max(1);
                 let table_constraints = std::iter::once(Constraint::Min(12))
                     .chain(
                         std::iter::repeat(Constraint::Ratio(1, column_count as u32))
@@ -143,46 +252,57 @@ impl<'a> StatefulWidget for ArchetypeDebugger<'a> {
 
                 let mut rows = entities
                     .iter()
-                    .map(|entity| vec![format!("{:?}", entity,)])
+                    .map(|entity| vec![format!("{}", entity)])
                     .collect::<Vec<_>>();
 
                 for (i, row) in rows.iter_mut().enumerate() {
                     for column in components.values() {
-                        row.push(format!("{:#?}", column[i]))
+                        row.push(
+                            column
+                                .get(i)
+                                .map(|data| format!("{:#?}", data))
+                                .unwrap_or_default(),
+                        )
                     }
                 }
 
-                let rows = rows.into_iter().map(|row| {
-                    let height = row
-                        .iter()
-                        .map(|item| item.matches('\n').count())
-                        .max()
-                        .expect("No row items")
-                        + 1;
+                let heights = rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|item| item.matches('\n').count())
+                            .max()
+                            .unwrap_or_default() as u16
+                            + 1
+                    })
+                    .collect::<Vec<_>>();
 
-                    Row::new(row).height(height as u16)
-                });
+                let rows = rows
+                    .into_iter()
+                    .zip(heights.iter())
+                    .map(|(row, height)| Row::new(row).height(*height));
 
                 let header_row = Row::new(header)
-                    .style(Style::default().fg(super::style::COLOR_INFO))
+                    .style(Style::default().fg(theme.info))
                     .bottom_margin(1);
 
-                let table =
-                    super::style::table(rows, matches!(state.focus, ArchetypesFocus::Table))
-                        .header(header_row)
-                        .block(super::style::block(
-                            components
-                                .keys()
-                                .map(String::as_str)
-                                .collect::<Vec<_>>()
-                                .join(", "),
-                        ))
-                        .widths(&*table_constraints)
-                        .column_spacing(1);
-
-                StatefulWidget::render(table, area, buf, &mut state.table_state);
+                let block = theme.block(archetype.layout().collect::<Vec<_>>().join(", "));
+
+                // Rows start below the header and its bottom margin
+                let mut rows_area = block.inner(area);
+                rows_area.y = rows_area.y.saturating_add(2).min(rows_area.bottom());
+                rows_area.height = rows_area.height.saturating_sub(2);
+                table_hitbox.update(rows_area, heights.clone(), table_state.selected());
+
+                let table = theme
+                    .table(rows, matches!(focus, ArchetypesFocus::Table))
+                    .header(header_row)
+                    .block(block)
+                    .widths(&table_constraints)
+                    .column_spacing(1);
+
+                StatefulWidget::render(table, area, buf, table_state);
             })
-            .render(area, buf);
-            */
+            .render(chunks[1], buf);
     }
 }
@@ -0,0 +1,119 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Keymap, Theme};
+
+/// Default config file path, relative to the working directory
+pub const CONFIG_PATH: &str = "tui-debugger.ron";
+
+/// Environment variable overriding [`CONFIG_PATH`]
+pub const CONFIG_PATH_ENV: &str = "TUI_DEBUGGER_CONFIG";
+
+/// User-facing debugger configuration, loaded from a RON file
+///
+/// Missing fields fall back to their defaults.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuiDebuggerConfig {
+    pub keymap: Keymap,
+    pub theme: Theme,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse(ron::Error),
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(e: std::io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+impl From<ron::Error> for ConfigError {
+    fn from(e: ron::Error) -> Self {
+        ConfigError::Parse(e)
+    }
+}
+
+impl TuiDebuggerConfig {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let config = std::fs::read_to_string(path)?;
+        Ok(ron::from_str(&config)?)
+    }
+
+    /// Load from `path`, returning the default config if the file does not exist
+    pub fn load_or_default<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        match Self::load(path) {
+            Err(ConfigError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Default::default())
+            }
+            result => result,
+        }
+    }
+
+    /// Load from the path in [`CONFIG_PATH_ENV`], or [`CONFIG_PATH`] if unset
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let path = std::env::var(CONFIG_PATH_ENV).unwrap_or_else(|_| CONFIG_PATH.into());
+        Self::load_or_default(path)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ConfigError> {
+        Ok(ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::default(),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::Action;
+
+    #[test]
+    fn partial_config_keeps_defaults() {
+        let config: TuiDebuggerConfig = ron::from_str(
+            r#"(
+                keymap: { Down: ["s", "ctrl+n"] },
+                theme: (highlight: Yellow),
+            )"#,
+        )
+        .unwrap();
+
+        let keymap = &config.keymap;
+        let key = |code, modifiers| keymap.action(&KeyEvent::new(code, modifiers));
+
+        assert_eq!(
+            key(KeyCode::Char('s'), KeyModifiers::NONE),
+            Some(Action::Down)
+        );
+        assert_eq!(
+            key(KeyCode::Char('n'), KeyModifiers::CONTROL),
+            Some(Action::Down)
+        );
+        assert_eq!(key(KeyCode::Char('j'), KeyModifiers::NONE), None);
+        assert_eq!(
+            key(KeyCode::Char('k'), KeyModifiers::NONE),
+            Some(Action::Up)
+        );
+        assert_eq!(
+            key(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            Some(Action::Last)
+        );
+
+        assert_eq!(config.theme.highlight, tui::style::Color::Yellow);
+        assert_eq!(config.theme.info, Theme::default().info);
+    }
+
+    #[test]
+    fn config_round_trip() {
+        let config = TuiDebuggerConfig::default();
+        let ron = config.to_ron().unwrap();
+        assert_eq!(ron::from_str::<TuiDebuggerConfig>(&ron).unwrap(), config);
+    }
+}
@@ -1,23 +1,21 @@
-use legion::{
-    query::EntityFilter,
-    serialize::{CustomEntitySerializer, WorldSerializer},
-    World,
-};
-
 use tui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
-    widgets::{ListItem, ListState, StatefulWidget},
+    widgets::{ListItem, ListState, Paragraph, StatefulWidget, Widget},
 };
 
-use legion_debugger::{Entities, ParseEntitiesError};
-use reflection::data::Data;
+use legion_debugger::{Entities, EntityQuery};
+use tui_widgets::{ListHitbox, MouseInput, MouseInputKind};
 
-use super::parse_world_data;
+use super::{
+    navigation::{navigate, page_size, scroll_action},
+    Action, Input, SearchBar, SearchState, Theme,
+};
 
 #[derive(Debug, Copy, Clone)]
 pub enum EntitiesFocus {
     None,
+    Search,
     Entities,
     Components,
 }
@@ -31,17 +29,32 @@ impl Default for EntitiesFocus {
 #[derive(Debug, Default, Clone)]
 pub struct EntityState {
     focus: EntitiesFocus,
+    search: SearchState,
     entity_list_state: ListState,
     component_list_state: ListState,
     entity_count: usize,
+    component_count: usize,
+    page_size: usize,
+    search_area: Rect,
+    entity_hitbox: ListHitbox,
+    component_hitbox: ListHitbox,
 }
 
 impl EntityState {
+    pub fn query(&self) -> &EntityQuery {
+        self.search.query()
+    }
+
+    pub fn searching(&self) -> bool {
+        matches!(self.focus, EntitiesFocus::Search)
+    }
+
     pub fn set_focus(&mut self, focus: EntitiesFocus) {
         self.focus = focus;
 
         match self.focus {
             EntitiesFocus::None => (),
+            EntitiesFocus::Search => self.search.begin(),
             EntitiesFocus::Entities => {
                 if self.entity_list_state.selected().is_none() {
                     self.entity_list_state.select(Some(0));
@@ -54,65 +67,98 @@ impl EntityState {
         }
     }
 
-    pub fn handle_input(&mut self, input: char) -> EntitiesFocus {
+    pub fn handle_input(&mut self, input: Input) -> EntitiesFocus {
         match self.focus {
             EntitiesFocus::None => (),
-            EntitiesFocus::Entities => match input {
-                'h' => {
+            EntitiesFocus::Search => {
+                if !self.search.handle_input(input) {
+                    self.entity_list_state.select(Some(0));
+                    self.set_focus(EntitiesFocus::Entities);
+                }
+            }
+            EntitiesFocus::Entities => match input.action {
+                Some(Action::Search) => self.set_focus(EntitiesFocus::Search),
+                Some(Action::Left) | Some(Action::Cancel) => {
                     self.set_focus(EntitiesFocus::None);
                 }
-                'j' => self.entity_list_state.select(Some(
-                    self.entity_list_state
-                        .selected()
-                        .unwrap_or_default()
-                        .wrapping_add(1)
-                        .wrapping_rem(self.entity_count),
-                )),
-                'k' => self.entity_list_state.select(Some(
-                    self.entity_list_state
-                        .selected()
-                        .unwrap_or_default()
-                        .checked_sub(1)
-                        .unwrap_or(self.entity_count - 1),
-                )),
-                'l' => {
+                Some(Action::Right) | Some(Action::Confirm) => {
                     self.set_focus(EntitiesFocus::Components);
                 }
-                _ => (),
+                Some(action) => self.entity_list_state.select(navigate(
+                    self.entity_list_state.selected(),
+                    self.entity_count,
+                    self.page_size,
+                    action,
+                )),
+                None => (),
             },
-            EntitiesFocus::Components => match input {
-                'h' => {
+            EntitiesFocus::Components => match input.action {
+                Some(Action::Search) => self.set_focus(EntitiesFocus::Search),
+                Some(Action::Left) | Some(Action::Cancel) => {
                     self.set_focus(EntitiesFocus::Entities);
                 }
-                'j' => self.component_list_state.select(Some(
-                    self.component_list_state
-                        .selected()
-                        .unwrap_or_default()
-                        .wrapping_add(1)
-                        .wrapping_rem(self.entity_count),
-                )),
-                'k' => self.component_list_state.select(Some(
-                    self.component_list_state
-                        .selected()
-                        .unwrap_or_default()
-                        .checked_sub(1)
-                        .unwrap_or(self.entity_count - 1),
+                Some(action) => self.component_list_state.select(navigate(
+                    self.component_list_state.selected(),
+                    self.component_count,
+                    self.page_size,
+                    action,
                 )),
-                _ => (),
+                None => (),
             },
         }
 
         self.focus
     }
+
+    /// Focus and select whichever list is under the cursor
+    ///
+    /// Double-clicking an entity opens its component list.
+    pub fn handle_mouse(&mut self, input: MouseInput) -> EntitiesFocus {
+        let (column, row) = (input.column, input.row);
+
+        if let Some(action) = scroll_action(&input) {
+            if self.entity_hitbox.contains(column, row) {
+                self.entity_list_state.select(navigate(
+                    self.entity_list_state.selected(),
+                    self.entity_count,
+                    self.page_size,
+                    action,
+                ));
+                self.component_list_state.select(None);
+            } else if self.component_hitbox.contains(column, row) {
+                self.component_list_state.select(navigate(
+                    self.component_list_state.selected(),
+                    self.component_count,
+                    self.page_size,
+                    action,
+                ));
+            }
+        } else if input.hit(self.search_area) {
+            self.set_focus(EntitiesFocus::Search);
+        } else if let Some(index) = self.entity_hitbox.index_at(column, row) {
+            self.entity_list_state.select(Some(index));
+            if let MouseInputKind::DoubleClick = input.kind {
+                self.set_focus(EntitiesFocus::Components);
+            } else {
+                self.set_focus(EntitiesFocus::Entities);
+            }
+        } else if let Some(index) = self.component_hitbox.index_at(column, row) {
+            self.focus = EntitiesFocus::Components;
+            self.component_list_state.select(Some(index));
+        }
+
+        self.focus
+    }
 }
 
 pub struct EntityDebugger<'a> {
     entities: &'a Entities,
+    theme: &'a Theme,
 }
 
 impl<'a> EntityDebugger<'a> {
-    pub fn new(entities: &'a Entities) -> Self {
-        EntityDebugger { entities }
+    pub fn new(entities: &'a Entities, theme: &'a Theme) -> Self {
+        EntityDebugger { entities, theme }
     }
 }
 
@@ -120,34 +166,108 @@ impl<'a> StatefulWidget for EntityDebugger<'a> {
     type State = EntityState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        /*
-        let mut entities = vec![];
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+
+        SearchBar::new(
+            &state.search,
+            self.theme,
+            matches!(state.focus, EntitiesFocus::Search),
+        )
+        .render(rows[0], buf);
+        state.search_area = rows[0];
+
+        let entities = match self.entities.parse() {
+            Ok(entities) => entities,
+            Err(e) => {
+                Paragraph::new(format!("{:#?}", e))
+                    .block(self.theme.block("Entities"))
+                    .render(rows[1], buf);
+                return;
+            }
+        };
+
+        let query = state.search.query();
+        let entities = entities
+            .into_iter()
+            .filter(|(uuid, components)| query.matches_entity(uuid, components))
+            .collect::<Vec<_>>();
 
-        for (key, _) in self.entities.iter() {
-            entities.push(ListItem::new(format!("{:?}", key)));
+        state.entity_count = entities.len();
+        if let Some(selected) = state.entity_list_state.selected() {
+            if selected >= state.entity_count {
+                state
+                    .entity_list_state
+                    .select(state.entity_count.checked_sub(1));
+            }
         }
 
+        state.page_size = page_size(rows[1].height);
+
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(vec![Constraint::Min(16), Constraint::Ratio(1, 1)])
-            .split(area);
+            .split(rows[1]);
+
+        let items = entities
+            .iter()
+            .map(|(uuid, components)| {
+                let name = components
+                    .get(legion_debugger::NAME_COMPONENT)
+                    .and_then(legion_debugger::entity_name)
+                    .map(|name| format!(" {}", name))
+                    .unwrap_or_default();
+                ListItem::new(format!("{}{}", uuid, name))
+            })
+            .collect::<Vec<_>>();
 
-        super::style::list(entities, matches!(state.focus, EntitiesFocus::Entities))
-            .block(super::style::block("Entities"))
-            .render(chunks[0], buf, &mut state.entity_list_state);
+        let entity_block = self
+            .theme
+            .block(format!("Entities ({})", state.entity_count));
+        state.entity_hitbox.update(
+            entity_block.inner(chunks[0]),
+            items.iter().map(|item| item.height() as u16).collect(),
+            state.entity_list_state.selected(),
+        );
+
+        let entity_list = self
+            .theme
+            .list(items, matches!(state.focus, EntitiesFocus::Entities))
+            .block(entity_block);
+        StatefulWidget::render(entity_list, chunks[0], buf, &mut state.entity_list_state);
 
         let mut components = vec![];
 
-        if let Some(selected) = state.entity_list_state.selected() {
-            let (_, entity_components) = &self.entities[selected];
+        if let Some((_, entity_components)) = state
+            .entity_list_state
+            .selected()
+            .and_then(|selected| entities.get(selected))
+        {
             for (key, value) in entity_components.iter() {
                 components.push(ListItem::new(format!("{}: {:#?}", key, value)));
             }
         }
 
-        super::style::list(components, matches!(state.focus, EntitiesFocus::Components))
-            .block(super::style::block("Components"))
-            .render(chunks[1], buf, &mut state.component_list_state);
-        */
+        state.component_count = components.len();
+
+        let component_block = self.theme.block("Components");
+        state.component_hitbox.update(
+            component_block.inner(chunks[1]),
+            components.iter().map(|item| item.height() as u16).collect(),
+            state.component_list_state.selected(),
+        );
+
+        let component_list = self
+            .theme
+            .list(components, matches!(state.focus, EntitiesFocus::Components))
+            .block(component_block);
+        StatefulWidget::render(
+            component_list,
+            chunks[1],
+            buf,
+            &mut state.component_list_state,
+        );
     }
 }
@@ -0,0 +1,283 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Debugger action that can be bound to one or more keys
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Action {
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    NextTab,
+    PrevTab,
+    Confirm,
+    Cancel,
+    Erase,
+    Search,
+    Sort,
+    ZoomIn,
+    ZoomOut,
+    Pause,
+}
+
+/// A key event resolved against a [`Keymap`]
+///
+/// Carries the typed character alongside the bound action so text inputs can take precedence.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Input {
+    pub action: Option<Action>,
+    pub char: Option<char>,
+}
+
+impl Input {
+    pub fn action(action: Action) -> Self {
+        Input {
+            action: Some(action),
+            char: None,
+        }
+    }
+}
+
+/// A single key combination, ex. `ctrl+d` or `PageDown`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        KeyBinding { code, modifiers }.normalized()
+    }
+
+    /// Shift is implied by the character itself for char and backtab keys
+    fn normalized(mut self) -> Self {
+        if let KeyCode::Char(_) | KeyCode::BackTab = self.code {
+            self.modifiers.remove(KeyModifiers::SHIFT);
+        }
+        self
+    }
+}
+
+impl From<KeyCode> for KeyBinding {
+    fn from(code: KeyCode) -> Self {
+        KeyBinding::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl From<char> for KeyBinding {
+    fn from(c: char) -> Self {
+        KeyBinding::from(KeyCode::Char(c))
+    }
+}
+
+impl From<&KeyEvent> for KeyBinding {
+    fn from(event: &KeyEvent) -> Self {
+        KeyBinding::new(event.code, event.modifiers)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKeyBindingError(pub String);
+
+impl Display for ParseKeyBindingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid key binding: {}", self.0)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = ParseKeyBindingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseKeyBindingError(s.to_string());
+
+        // A trailing '+' is the plus key itself
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None if s == "+" => ("", "+"),
+            None => match s.rfind('+') {
+                Some(idx) => (&s[..idx], &s[idx + 1..]),
+                None => ("", s),
+            },
+        };
+
+        let mut mods = KeyModifiers::NONE;
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            mods |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(err()),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "insert" => KeyCode::Insert,
+                "delete" => KeyCode::Delete,
+                f => match f.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if n > 0 => KeyCode::F(n),
+                    _ => return Err(err()),
+                },
+            },
+        };
+
+        Ok(KeyBinding::new(code, mods))
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+impl Serialize for KeyBinding {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBinding {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Mapping from [`Action`]s to the keys that trigger them
+///
+/// Deserialized keymaps are merged over the defaults, so a config only needs to list overrides.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    from = "BTreeMap<Action, Vec<KeyBinding>>",
+    into = "BTreeMap<Action, Vec<KeyBinding>>"
+)]
+pub struct Keymap(BTreeMap<Action, Vec<KeyBinding>>);
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let ctrl = |c| KeyBinding::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+        Keymap(
+            vec![
+                (Action::Left, vec!['h'.into(), KeyCode::Left.into()]),
+                (Action::Right, vec!['l'.into(), KeyCode::Right.into()]),
+                (Action::Up, vec!['k'.into(), KeyCode::Up.into()]),
+                (Action::Down, vec!['j'.into(), KeyCode::Down.into()]),
+                (Action::PageUp, vec![ctrl('u'), KeyCode::PageUp.into()]),
+                (Action::PageDown, vec![ctrl('d'), KeyCode::PageDown.into()]),
+                (Action::First, vec!['g'.into(), KeyCode::Home.into()]),
+                (Action::Last, vec!['G'.into(), KeyCode::End.into()]),
+                (Action::NextTab, vec![KeyCode::Tab.into()]),
+                (Action::PrevTab, vec![KeyCode::BackTab.into()]),
+                (Action::Confirm, vec![KeyCode::Enter.into()]),
+                (Action::Cancel, vec![KeyCode::Esc.into()]),
+                (Action::Erase, vec![KeyCode::Backspace.into()]),
+                (Action::Search, vec!['/'.into()]),
+                (Action::Sort, vec!['s'.into()]),
+                (Action::ZoomIn, vec!['+'.into(), '='.into()]),
+                (Action::ZoomOut, vec!['-'.into()]),
+                (Action::Pause, vec!['p'.into()]),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+}
+
+impl From<BTreeMap<Action, Vec<KeyBinding>>> for Keymap {
+    fn from(overrides: BTreeMap<Action, Vec<KeyBinding>>) -> Self {
+        let mut keymap = Keymap::default();
+        keymap.0.extend(overrides);
+        keymap
+    }
+}
+
+impl From<Keymap> for BTreeMap<Action, Vec<KeyBinding>> {
+    fn from(keymap: Keymap) -> Self {
+        keymap.0
+    }
+}
+
+impl Keymap {
+    pub fn bindings(&self, action: Action) -> &[KeyBinding] {
+        self.0.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn bind(&mut self, action: Action, bindings: Vec<KeyBinding>) {
+        self.0.insert(action, bindings);
+    }
+
+    /// Find the action bound to a given key event
+    pub fn action(&self, event: &KeyEvent) -> Option<Action> {
+        let binding = KeyBinding::from(event);
+        self.0
+            .iter()
+            .find(|(_, bindings)| bindings.contains(&binding))
+            .map(|(action, _)| *action)
+    }
+
+    /// Resolve a key event into an [`Input`]
+    pub fn input(&self, event: &KeyEvent) -> Input {
+        let char = match event.code {
+            KeyCode::Char(c)
+                if !event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                Some(c)
+            }
+            _ => None,
+        };
+
+        Input {
+            action: self.action(event),
+            char,
+        }
+    }
+}
@@ -1,17 +1,30 @@
 mod archetype_debugger;
+mod config;
 mod entity_debugger;
+mod keymap;
+mod log_console;
+mod navigation;
 mod resource_debugger;
 mod schedule_debugger;
+mod search;
+mod timeline_debugger;
 
 mod style;
 
 pub use archetype_debugger::*;
+pub use config::*;
 pub use entity_debugger::*;
+pub use keymap::*;
+pub use log_console::*;
 pub use resource_debugger::*;
 pub use schedule_debugger::*;
+pub use search::*;
+pub use style::Theme;
+pub use timeline_debugger::*;
 
 use std::io::Stdout;
 
+use crossterm::event::{KeyEvent, MouseEvent};
 use tui::{
     backend::CrosstermBackend,
     buffer::Buffer,
@@ -21,23 +34,10 @@ use tui::{
     Terminal,
 };
 
-use reflection::data::Data;
-
+use antigen_tracing::TraceTree;
 use legion_debugger::{Archetypes, Entities, ParseArchetypesError, ParseEntitiesError};
 
-use tui_widgets::TabContainer;
-
-fn parse_world_data(world: Data) -> (Data, Data) {
-    // Top-le,vel structure is a Map
-    let mut map = if let Data::Map(map) = world {
-        map
-    } else {
-        panic!("World is not a map: {:?}", world);
-    };
-
-    // Map contains a single field
-    map.pop().expect("No data in top-level map")
-}
+use tui_widgets::{ClickTracker, MouseInput, MouseInputKind, TabAreas, TabContainer};
 
 #[derive(Debug, Copy, Clone)]
 pub enum DebuggerFocus {
@@ -46,6 +46,8 @@ pub enum DebuggerFocus {
     Entities,
     Resources,
     Schedules,
+    Timeline,
+    Log,
 }
 
 impl Default for DebuggerFocus {
@@ -60,6 +62,8 @@ pub enum RootTabs {
     Entity,
     Resource,
     Schedule,
+    Timeline,
+    Log,
 }
 
 impl Default for RootTabs {
@@ -95,21 +99,148 @@ pub enum ParseTracingError {}
 /// Internal state for debug UI
 #[derive(Debug, Default, Clone)]
 pub struct TuiDebuggerState {
+    config: TuiDebuggerConfig,
     active_tab: RootTabs,
     focus: DebuggerFocus,
     archetype_state: ArchetypeState,
     entity_state: EntityState,
     resource_state: ResourceState,
     schedule_state: ScheduleState,
+    timeline_state: TimelineState,
+    log_state: LogConsoleState,
+    click_tracker: ClickTracker,
+    root_tabs: TabAreas,
 }
 
 impl TuiDebuggerState {
-    pub fn handle_input(&mut self, input: char) {
+    pub fn new(config: TuiDebuggerConfig) -> Self {
+        TuiDebuggerState {
+            config,
+            ..Default::default()
+        }
+    }
+
+    pub fn config(&self) -> &TuiDebuggerConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: TuiDebuggerConfig) {
+        self.config = config;
+    }
+
+    /// Collect new events from `trace_tree` into the log scrollback
+    ///
+    /// Called on every draw regardless of which tab is visible,
+    /// so events are retained after their leaves are pruned from the tree.
+    pub fn ingest(&mut self, trace_tree: &TraceTree) {
+        self.log_state.buffer_mut().ingest(trace_tree);
+    }
+
+    /// Current search queries, used to filter world data before it is parsed
+    pub fn search_queries(&self) -> SearchQueries {
+        SearchQueries {
+            archetypes: self.archetype_state.query().clone(),
+            entities: self.entity_state.query().clone(),
+        }
+    }
+
+    /// Returns true if a search bar is capturing text input
+    pub fn searching(&self) -> bool {
         match self.focus {
-            DebuggerFocus::RootTabs => match input {
-                'h' => self.prev_tab(),
-                'l' => self.next_tab(),
-                'j' => match self.active_tab {
+            DebuggerFocus::Archetypes => self.archetype_state.searching(),
+            DebuggerFocus::Entities => self.entity_state.searching(),
+            DebuggerFocus::Log => self.log_state.searching(),
+            _ => false,
+        }
+    }
+
+    /// Resolve a key event against the configured [`Keymap`] and handle it
+    pub fn handle_key(&mut self, event: &KeyEvent) {
+        let input = self.config.keymap.input(event);
+        self.handle_input(input)
+    }
+
+    /// Hit-test a mouse event against the most recently rendered layout and handle it
+    pub fn handle_mouse(&mut self, event: &MouseEvent) {
+        if let Some(input) = self.click_tracker.input(event) {
+            self.handle_mouse_input(input)
+        }
+    }
+
+    pub fn handle_mouse_input(&mut self, input: MouseInput) {
+        if input.hit(self.root_tabs.bar) {
+            if let MouseInputKind::ScrollUp = input.kind {
+                self.prev_tab();
+            } else if let MouseInputKind::ScrollDown = input.kind {
+                self.next_tab();
+            } else if let Some(index) = self.root_tabs.tab_at(input.column, input.row) {
+                self.active_tab = match index {
+                    0 => RootTabs::Archetype,
+                    1 => RootTabs::Entity,
+                    2 => RootTabs::Resource,
+                    3 => RootTabs::Schedule,
+                    4 => RootTabs::Timeline,
+                    _ => RootTabs::Log,
+                };
+            }
+            self.focus = DebuggerFocus::RootTabs;
+            return;
+        }
+
+        if !input.hit(self.root_tabs.content) {
+            return;
+        }
+
+        self.focus = match self.active_tab {
+            RootTabs::Archetype => match self.archetype_state.handle_mouse(input) {
+                ArchetypesFocus::None => DebuggerFocus::RootTabs,
+                _ => DebuggerFocus::Archetypes,
+            },
+            RootTabs::Entity => match self.entity_state.handle_mouse(input) {
+                EntitiesFocus::None => DebuggerFocus::RootTabs,
+                _ => DebuggerFocus::Entities,
+            },
+            RootTabs::Resource => match self.resource_state.handle_mouse(input) {
+                ResourcesFocus::None => DebuggerFocus::RootTabs,
+                _ => DebuggerFocus::Resources,
+            },
+            RootTabs::Schedule => match self.schedule_state.handle_mouse(input) {
+                SchedulesFocus::None => DebuggerFocus::RootTabs,
+                _ => DebuggerFocus::Schedules,
+            },
+            RootTabs::Timeline => match self.timeline_state.handle_mouse(input) {
+                TimelineFocus::None => DebuggerFocus::RootTabs,
+                _ => DebuggerFocus::Timeline,
+            },
+            RootTabs::Log => match self.log_state.handle_mouse(input) {
+                LogConsoleFocus::None => DebuggerFocus::RootTabs,
+                _ => DebuggerFocus::Log,
+            },
+        }
+    }
+
+    pub fn handle_input(&mut self, input: Input) {
+        if !self.searching() {
+            match input.action {
+                Some(Action::NextTab) => {
+                    self.next_tab();
+                    self.focus = DebuggerFocus::RootTabs;
+                    return;
+                }
+                Some(Action::PrevTab) => {
+                    self.prev_tab();
+                    self.focus = DebuggerFocus::RootTabs;
+                    return;
+                }
+                _ => (),
+            }
+        }
+
+        match self.focus {
+            DebuggerFocus::RootTabs => match input.action {
+                Some(Action::Left) => self.prev_tab(),
+                Some(Action::Right) => self.next_tab(),
+                Some(Action::Down) | Some(Action::Confirm) => match self.active_tab {
                     RootTabs::Archetype => {
                         self.archetype_state.set_focus(ArchetypesFocus::Tabs);
                         self.focus = DebuggerFocus::Archetypes;
@@ -126,6 +257,14 @@ impl TuiDebuggerState {
                         self.schedule_state.set_focus(SchedulesFocus::List);
                         self.focus = DebuggerFocus::Schedules;
                     }
+                    RootTabs::Timeline => {
+                        self.timeline_state.set_focus(TimelineFocus::Timeline);
+                        self.focus = DebuggerFocus::Timeline;
+                    }
+                    RootTabs::Log => {
+                        self.log_state.set_focus(LogConsoleFocus::List);
+                        self.focus = DebuggerFocus::Log;
+                    }
                 },
                 _ => (),
             },
@@ -153,6 +292,18 @@ impl TuiDebuggerState {
                     self.focus = DebuggerFocus::RootTabs;
                 }
             }
+            DebuggerFocus::Timeline => {
+                let new_focus = self.timeline_state.handle_input(input);
+                if let TimelineFocus::None = new_focus {
+                    self.focus = DebuggerFocus::RootTabs;
+                }
+            }
+            DebuggerFocus::Log => {
+                let new_focus = self.log_state.handle_input(input);
+                if let LogConsoleFocus::None = new_focus {
+                    self.focus = DebuggerFocus::RootTabs;
+                }
+            }
         }
     }
 
@@ -161,16 +312,20 @@ impl TuiDebuggerState {
             RootTabs::Archetype => RootTabs::Entity,
             RootTabs::Entity => RootTabs::Resource,
             RootTabs::Resource => RootTabs::Schedule,
-            RootTabs::Schedule => RootTabs::Archetype,
+            RootTabs::Schedule => RootTabs::Timeline,
+            RootTabs::Timeline => RootTabs::Log,
+            RootTabs::Log => RootTabs::Archetype,
         }
     }
 
     pub fn prev_tab(&mut self) {
         self.active_tab = match self.active_tab {
-            RootTabs::Archetype => RootTabs::Schedule,
+            RootTabs::Archetype => RootTabs::Log,
             RootTabs::Entity => RootTabs::Archetype,
             RootTabs::Resource => RootTabs::Entity,
             RootTabs::Schedule => RootTabs::Resource,
+            RootTabs::Timeline => RootTabs::Schedule,
+            RootTabs::Log => RootTabs::Timeline,
         }
     }
 }
@@ -222,11 +377,16 @@ impl TuiDebugger {
         archetypes: &Archetypes,
         entities: &Entities,
         resources: &Resources,
+        trace_tree: Option<&TraceTree>,
     ) {
+        if let Some(trace_tree) = trace_tree {
+            state.ingest(trace_tree);
+        }
+
         self.terminal
             .draw(|f| {
                 f.render_stateful_widget(
-                    TuiDebuggerWidget::new(archetypes, entities, resources),
+                    TuiDebuggerWidget::new(archetypes, entities, resources, trace_tree),
                     f.size(),
                     state,
                 );
@@ -258,6 +418,7 @@ struct TuiDebuggerWidget<'a> {
     archetypes: &'a Archetypes,
     entities: &'a Entities,
     resources: &'a Resources,
+    trace_tree: Option<&'a TraceTree>,
 }
 
 impl<'a> TuiDebuggerWidget<'a> {
@@ -265,11 +426,13 @@ impl<'a> TuiDebuggerWidget<'a> {
         archetypes: &'a Archetypes,
         entities: &'a Entities,
         resources: &'a Resources,
+        trace_tree: Option<&'a TraceTree>,
     ) -> Self {
         Self {
             archetypes,
             entities,
             resources,
+            trace_tree,
         }
     }
 }
@@ -281,27 +444,53 @@ impl<'a> StatefulWidget for TuiDebuggerWidget<'a> {
         let archetypes = self.archetypes;
         let entities = self.entities;
         let resources = self.resources;
-
-        TabContainer::new(style::tabs)
+        let trace_tree = self.trace_tree;
+        let theme = state.config.theme;
+
+        let TuiDebuggerState {
+            active_tab,
+            focus,
+            archetype_state,
+            entity_state,
+            resource_state,
+            schedule_state,
+            timeline_state,
+            log_state,
+            root_tabs,
+            ..
+        } = state;
+
+        TabContainer::new(|titles, focused| theme.tabs(titles, focused))
             .titles(
-                std::array::IntoIter::new(["Archetypes", "Entities", "Resources", "Tracing"])
-                    .map(Spans::from)
-                    .collect(),
+                std::array::IntoIter::new([
+                    "Archetypes",
+                    "Entities",
+                    "Resources",
+                    "Tracing",
+                    "Timeline",
+                    "Log",
+                ])
+                .map(Spans::from)
+                .collect(),
             )
-            .block(style::block("Legion Debugger"))
-            .select(match state.active_tab {
+            .block(theme.block("Legion Debugger"))
+            .select(match active_tab {
                 RootTabs::Archetype => 0,
                 RootTabs::Entity => 1,
                 RootTabs::Resource => 2,
                 RootTabs::Schedule => 3,
+                RootTabs::Timeline => 4,
+                RootTabs::Log => 5,
             })
-            .highlight(matches!(state.focus, DebuggerFocus::RootTabs))
-            .next(move |area, buf, index| match index {
-                0 => {
-                    ArchetypeDebugger::new(archetypes).render(area, buf, &mut state.archetype_state)
-                }
-                1 => EntityDebugger::new(entities).render(area, buf, &mut state.entity_state),
-                2 => ResourceDebugger::new(resources).render(area, buf, &mut state.resource_state),
+            .highlight(matches!(focus, DebuggerFocus::RootTabs))
+            .areas(root_tabs)
+            .next(|area, buf, index| match index {
+                0 => ArchetypeDebugger::new(archetypes, &theme).render(area, buf, archetype_state),
+                1 => EntityDebugger::new(entities, &theme).render(area, buf, entity_state),
+                2 => ResourceDebugger::new(resources, &theme).render(area, buf, resource_state),
+                3 => ScheduleDebugger::new(trace_tree, &theme).render(area, buf, schedule_state),
+                4 => TimelineDebugger::new(trace_tree, &theme).render(area, buf, timeline_state),
+                5 => LogConsole::new(&theme).render(area, buf, log_state),
                 _ => (),
             })
             .render(area, buf)
@@ -0,0 +1,610 @@
+use std::{
+    collections::VecDeque,
+    ops::Bound,
+    time::{Duration, Instant},
+};
+
+use antigen_tracing::{TraceField, TraceLeafId, TraceLeafVariant, TraceTree};
+use tracing::Level;
+use tui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Span, Spans},
+    widgets::{ListItem, ListState, StatefulWidget, Widget},
+};
+use tui_widgets::{ListHitbox, MouseInput};
+
+use super::{
+    navigation::{navigate, page_size, scroll_action},
+    Action, Input, SearchBar, SearchQuery, SearchState, Theme,
+};
+
+/// Default number of entries retained by a [`LogBuffer`]
+pub const DEFAULT_SCROLLBACK: usize = 1000;
+
+/// Field prefix used by `tracing-log` for bridged `log` record metadata
+const LOG_FIELD_PREFIX: &str = "log.";
+
+/// Single tracing event, formatted for display
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Monotonic index, unaffected by entries being dropped from the scrollback
+    pub seq: usize,
+    pub instant: Instant,
+    pub level: Level,
+    pub target: String,
+    pub thread: String,
+    pub message: String,
+    pub fields: String,
+}
+
+fn format_field(field: &TraceField) -> String {
+    match field {
+        TraceField::Debug(value) | TraceField::Str(value) | TraceField::Error(value) => {
+            value.clone()
+        }
+        TraceField::I64(value) => value.to_string(),
+        TraceField::U64(value) => value.to_string(),
+        TraceField::Bool(value) => value.to_string(),
+    }
+}
+
+/// Bounded scrollback of events collected from a [`TraceTree`]
+///
+/// Leaves are only read once, so the tree can be pruned freely between calls to [`LogBuffer::ingest`].
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    last_leaf: Option<TraceLeafId>,
+    origin: Option<Instant>,
+    next_seq: usize,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        LogBuffer::new(DEFAULT_SCROLLBACK)
+    }
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        LogBuffer {
+            entries: Default::default(),
+            capacity,
+            last_leaf: None,
+            origin: None,
+            next_seq: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Sequence number that will be assigned to the next ingested entry
+    pub fn next_seq(&self) -> usize {
+        self.next_seq
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Time elapsed between the first ingested entry and `entry`
+    pub fn timestamp(&self, entry: &LogEntry) -> Duration {
+        self.origin
+            .map(|origin| entry.instant.saturating_duration_since(origin))
+            .unwrap_or_default()
+    }
+
+    /// Append any events added to `trace_tree` since the last call
+    pub fn ingest(&mut self, trace_tree: &TraceTree) {
+        let range = match self.last_leaf {
+            Some(id) => trace_tree
+                .leaves
+                .range((Bound::Excluded(id), Bound::Unbounded)),
+            None => trace_tree.leaves.range(..),
+        };
+
+        for (id, leaf) in range {
+            self.last_leaf = Some(*id);
+
+            let instant = match leaf.variant {
+                TraceLeafVariant::Event { instant } => instant,
+                _ => continue,
+            };
+
+            let metadata = leaf.metadata;
+            let mut target = metadata.target().to_string();
+            let mut message = String::new();
+            let mut fields = vec![];
+
+            for (name, field) in leaf.fields.iter() {
+                if *name == "message" {
+                    message = format_field(field);
+                } else if let Some(name) = name.strip_prefix(LOG_FIELD_PREFIX) {
+                    if name == "target" {
+                        target = format_field(field);
+                    }
+                } else {
+                    fields.push(format!("{}={}", name, format_field(field)));
+                }
+            }
+
+            let thread = leaf
+                .thread
+                .name()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| format!("{:?}", leaf.thread.id()));
+
+            self.origin.get_or_insert(instant);
+            self.entries.push_back(LogEntry {
+                seq: self.next_seq,
+                instant,
+                level: *metadata.level(),
+                target,
+                thread,
+                message,
+                fields: fields.join(" "),
+            });
+            self.next_seq += 1;
+        }
+
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+}
+
+/// Single whitespace-separated term of a [`LogQuery`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogQueryTerm {
+    /// `level:warn` - entries at this level or more severe
+    Level(Level),
+    /// `target:foo` - target prefix
+    Target(String),
+    /// Bare text - case-insensitive substring of the message or fields
+    Text(String),
+}
+
+/// Search query over log entries, parsed from a search bar string
+///
+/// Terms are separated by whitespace and must all match.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LogQuery {
+    terms: Vec<LogQueryTerm>,
+}
+
+impl LogQuery {
+    pub fn terms(&self) -> &[LogQueryTerm] {
+        &self.terms
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        self.terms.iter().all(|term| match term {
+            LogQueryTerm::Level(level) => entry.level <= *level,
+            LogQueryTerm::Target(target) => entry.target.starts_with(target.as_str()),
+            LogQueryTerm::Text(text) => {
+                let text = text.to_lowercase();
+                entry.message.to_lowercase().contains(&text)
+                    || entry.fields.to_lowercase().contains(&text)
+            }
+        })
+    }
+}
+
+impl SearchQuery for LogQuery {
+    fn parse(input: &str) -> Self {
+        let terms = input
+            .split_whitespace()
+            .map(|token| {
+                if let Some(level) = token
+                    .strip_prefix("level:")
+                    .and_then(|level| level.parse().ok())
+                {
+                    LogQueryTerm::Level(level)
+                } else if let Some(target) = token.strip_prefix("target:") {
+                    LogQueryTerm::Target(target.to_string())
+                } else {
+                    LogQueryTerm::Text(token.to_string())
+                }
+            })
+            .collect();
+
+        LogQuery { terms }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum LogConsoleFocus {
+    None,
+    Search,
+    List,
+}
+
+impl Default for LogConsoleFocus {
+    fn default() -> Self {
+        LogConsoleFocus::None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogConsoleState {
+    focus: LogConsoleFocus,
+    search: SearchState<LogQuery>,
+    buffer: LogBuffer,
+    /// Keep the newest entry selected as new entries arrive
+    follow: bool,
+    /// Sequence number after which entries are hidden while paused
+    paused: Option<usize>,
+    list_state: ListState,
+    entry_count: usize,
+    page_size: usize,
+    search_area: Rect,
+    hitbox: ListHitbox,
+}
+
+impl Default for LogConsoleState {
+    fn default() -> Self {
+        LogConsoleState {
+            focus: Default::default(),
+            search: Default::default(),
+            buffer: Default::default(),
+            follow: true,
+            paused: None,
+            list_state: Default::default(),
+            entry_count: 0,
+            page_size: 0,
+            search_area: Default::default(),
+            hitbox: Default::default(),
+        }
+    }
+}
+
+impl LogConsoleState {
+    pub fn buffer(&self) -> &LogBuffer {
+        &self.buffer
+    }
+
+    pub fn buffer_mut(&mut self) -> &mut LogBuffer {
+        &mut self.buffer
+    }
+
+    pub fn query(&self) -> &LogQuery {
+        self.search.query()
+    }
+
+    pub fn searching(&self) -> bool {
+        matches!(self.focus, LogConsoleFocus::Search)
+    }
+
+    pub fn following(&self) -> bool {
+        self.follow
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Freeze the visible entries, or resume showing new ones
+    pub fn toggle_pause(&mut self) {
+        self.paused = match self.paused {
+            Some(_) => None,
+            None => Some(self.buffer.next_seq()),
+        };
+    }
+
+    pub fn set_focus(&mut self, focus: LogConsoleFocus) {
+        self.focus = focus;
+
+        match self.focus {
+            LogConsoleFocus::None => (),
+            LogConsoleFocus::Search => self.search.begin(),
+            LogConsoleFocus::List => {
+                if self.list_state.selected().is_none() {
+                    self.list_state.select(self.entry_count.checked_sub(1));
+                }
+            }
+        }
+    }
+
+    fn select(&mut self, selected: Option<usize>) {
+        self.list_state.select(selected);
+        self.follow = selected.map_or(true, |selected| selected + 1 >= self.entry_count);
+    }
+
+    pub fn handle_input(&mut self, input: Input) -> LogConsoleFocus {
+        match self.focus {
+            LogConsoleFocus::None => (),
+            LogConsoleFocus::Search => {
+                if !self.search.handle_input(input) {
+                    self.follow = true;
+                    self.set_focus(LogConsoleFocus::List);
+                }
+            }
+            LogConsoleFocus::List => match input.action {
+                Some(Action::Search) => self.set_focus(LogConsoleFocus::Search),
+                Some(Action::Pause) => self.toggle_pause(),
+                Some(Action::Left) | Some(Action::Cancel) => self.set_focus(LogConsoleFocus::None),
+                Some(action) => self.select(navigate(
+                    self.list_state.selected(),
+                    self.entry_count,
+                    self.page_size,
+                    action,
+                )),
+                None => (),
+            },
+        }
+
+        self.focus
+    }
+
+    pub fn handle_mouse(&mut self, input: MouseInput) -> LogConsoleFocus {
+        if let Some(action) = scroll_action(&input) {
+            if self.hitbox.contains(input.column, input.row) {
+                self.select(navigate(
+                    self.list_state.selected(),
+                    self.entry_count,
+                    self.page_size,
+                    action,
+                ));
+            }
+        } else if input.hit(self.search_area) {
+            self.set_focus(LogConsoleFocus::Search);
+        } else if let Some(index) = self.hitbox.index_at(input.column, input.row) {
+            self.focus = LogConsoleFocus::List;
+            self.select(Some(index));
+        }
+
+        self.focus
+    }
+}
+
+fn level_color(level: &Level) -> Color {
+    match *level {
+        Level::ERROR => Color::Red,
+        Level::WARN => Color::Yellow,
+        Level::INFO => Color::Green,
+        Level::DEBUG => Color::Blue,
+        Level::TRACE => Color::Magenta,
+    }
+}
+
+/// Scrolling console of tracing events, including bridged `log` records
+///
+/// Entries are read from the state's [`LogBuffer`],
+/// which is filled independently of rendering via [`LogBuffer::ingest`].
+pub struct LogConsole<'a> {
+    theme: &'a Theme,
+}
+
+impl<'a> LogConsole<'a> {
+    pub fn new(theme: &'a Theme) -> Self {
+        LogConsole { theme }
+    }
+}
+
+impl<'a> StatefulWidget for LogConsole<'a> {
+    type State = LogConsoleState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let theme = self.theme;
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+
+        SearchBar::new(
+            &state.search,
+            theme,
+            matches!(state.focus, LogConsoleFocus::Search),
+        )
+        .render(chunks[0], buf);
+        state.search_area = chunks[0];
+
+        let query = state.search.query();
+        let paused = state.paused;
+        let buffer = &state.buffer;
+
+        let items = buffer
+            .entries()
+            .filter(|entry| paused.map_or(true, |paused| entry.seq < paused))
+            .filter(|entry| query.matches(entry))
+            .map(|entry| {
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{:>10} ", format!("{:.3?}", buffer.timestamp(entry))),
+                        Style::default().fg(theme.trim),
+                    ),
+                    Span::styled(
+                        format!("{:<5} ", entry.level.to_string()),
+                        Style::default().fg(level_color(&entry.level)),
+                    ),
+                    Span::styled(
+                        format!("{} ", entry.target),
+                        Style::default().fg(theme.info),
+                    ),
+                    Span::styled(
+                        format!("[{}] ", entry.thread),
+                        Style::default().fg(theme.lowlight),
+                    ),
+                    Span::styled(entry.message.clone(), Style::default().fg(theme.titles)),
+                ];
+
+                if !entry.fields.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" {}", entry.fields),
+                        Style::default().fg(theme.trim),
+                    ));
+                }
+
+                ListItem::new(Spans::from(spans))
+            })
+            .collect::<Vec<_>>();
+
+        state.entry_count = items.len();
+        state.page_size = page_size(chunks[1].height);
+
+        if state.follow {
+            state.list_state.select(state.entry_count.checked_sub(1));
+        } else if let Some(selected) = state.list_state.selected() {
+            if selected >= state.entry_count {
+                state.list_state.select(state.entry_count.checked_sub(1));
+            }
+        }
+
+        let mode = if state.paused.is_some() {
+            "paused"
+        } else if state.follow {
+            "following"
+        } else {
+            "scrolled"
+        };
+        let block = theme.block(format!(
+            "Log ({}/{}, {})",
+            state.entry_count,
+            state.buffer.len(),
+            mode
+        ));
+
+        state.hitbox.update(
+            block.inner(chunks[1]),
+            items.iter().map(|item| item.height() as u16).collect(),
+            state.list_state.selected(),
+        );
+
+        let list = theme
+            .list(items, matches!(state.focus, LogConsoleFocus::List))
+            .block(block);
+        StatefulWidget::render(list, chunks[1], buf, &mut state.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use antigen_tracing::{TraceReceiver, TraceSender};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    /// Record the tracing events emitted by `f` into `trace_tree`
+    fn trace<F: FnOnce()>(trace_tree: &mut TraceTree, f: F) {
+        let (sender, receiver): (TraceSender, TraceReceiver) = TraceSender::unbounded();
+        tracing::subscriber::with_default(
+            tracing_subscriber::registry::Registry::default().with(sender),
+            f,
+        );
+        receiver.flush(trace_tree);
+    }
+
+    fn messages(buffer: &LogBuffer) -> Vec<&str> {
+        buffer
+            .entries()
+            .map(|entry| entry.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_ingest_across_pruning() {
+        let mut trace_tree = TraceTree::default();
+        let mut buffer = LogBuffer::default();
+
+        trace(&mut trace_tree, || {
+            tracing::info!(count = 1, "first");
+            tracing::warn!("second");
+        });
+        buffer.ingest(&trace_tree);
+        buffer.ingest(&trace_tree);
+        assert_eq!(messages(&buffer), ["first", "second"]);
+        assert_eq!(buffer.entries().next().unwrap().fields, "count=1");
+
+        // Entries outlive their leaves, and pruned leaves aren't read again
+        trace_tree.prune_by(|_, _| true);
+        trace(&mut trace_tree, || tracing::error!("third"));
+        buffer.ingest(&trace_tree);
+
+        assert_eq!(messages(&buffer), ["first", "second", "third"]);
+        assert_eq!(
+            buffer.entries().map(|entry| entry.seq).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut trace_tree = TraceTree::default();
+        let mut buffer = LogBuffer::new(2);
+
+        trace(&mut trace_tree, || {
+            for i in 0..3 {
+                tracing::info!("{}", i);
+            }
+        });
+        buffer.ingest(&trace_tree);
+        assert_eq!(messages(&buffer), ["1", "2"]);
+        assert_eq!(buffer.next_seq(), 3);
+
+        buffer.set_capacity(1);
+        assert_eq!(messages(&buffer), ["2"]);
+    }
+
+    #[test]
+    fn test_query() {
+        let query = LogQuery::parse("level:warn target:antigen Failed level:loud");
+        assert_eq!(
+            query.terms(),
+            [
+                LogQueryTerm::Level(Level::WARN),
+                LogQueryTerm::Target("antigen".into()),
+                LogQueryTerm::Text("Failed".into()),
+                LogQueryTerm::Text("level:loud".into()),
+            ]
+        );
+        assert!(LogQuery::parse("  ").is_empty());
+
+        let entry = |level, target: &str, message: &str| LogEntry {
+            seq: 0,
+            instant: Instant::now(),
+            level,
+            target: target.into(),
+            thread: "main".into(),
+            message: message.into(),
+            fields: "path=level:loud".into(),
+        };
+
+        let query = LogQuery::parse("level:warn target:antigen failed");
+        assert!(query.matches(&entry(Level::ERROR, "antigen_hid", "Failed to open")));
+        assert!(!query.matches(&entry(Level::INFO, "antigen_hid", "Failed to open")));
+        assert!(!query.matches(&entry(Level::WARN, "legion", "Failed to open")));
+        assert!(!query.matches(&entry(Level::WARN, "antigen", "Opened")));
+        assert!(LogQuery::parse("LOUD").matches(&entry(Level::INFO, "antigen", "Opened")));
+    }
+}
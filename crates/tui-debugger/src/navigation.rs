@@ -0,0 +1,53 @@
+use tui_widgets::{MouseInput, MouseInputKind};
+
+use super::Action;
+
+/// Page size used before a list has been rendered and measured
+const DEFAULT_PAGE_SIZE: usize = 10;
+
+/// Compute the new selection of a vertical list for a given [`Action`]
+///
+/// Selection wraps for single steps and clamps for page and first / last jumps.
+/// Actions that don't move vertically leave the selection unchanged.
+pub(crate) fn navigate(
+    selected: Option<usize>,
+    count: usize,
+    page_size: usize,
+    action: Action,
+) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+
+    let page_size = if page_size == 0 {
+        DEFAULT_PAGE_SIZE
+    } else {
+        page_size
+    };
+
+    let current = selected.unwrap_or_default().min(count - 1);
+
+    Some(match action {
+        Action::Down => current.wrapping_add(1).wrapping_rem(count),
+        Action::Up => current.checked_sub(1).unwrap_or(count - 1),
+        Action::PageDown => (current + page_size).min(count - 1),
+        Action::PageUp => current.saturating_sub(page_size),
+        Action::First => 0,
+        Action::Last => count - 1,
+        _ => return selected,
+    })
+}
+
+/// Number of list rows visible inside a bordered block of the given height
+pub(crate) fn page_size(height: u16) -> usize {
+    height.saturating_sub(2) as usize
+}
+
+/// Vertical navigation action corresponding to a scroll wheel input
+pub(crate) fn scroll_action(input: &MouseInput) -> Option<Action> {
+    match input.kind {
+        MouseInputKind::ScrollUp => Some(Action::Up),
+        MouseInputKind::ScrollDown => Some(Action::Down),
+        _ => None,
+    }
+}
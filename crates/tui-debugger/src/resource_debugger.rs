@@ -1,11 +1,15 @@
-use std::ops::Deref;
-
 use reflection::data::Data;
 use tui::{
     buffer::Buffer,
     layout::Rect,
     widgets::{ListItem, ListState, StatefulWidget},
 };
+use tui_widgets::{ListHitbox, MouseInput};
+
+use super::{
+    navigation::{navigate, page_size, scroll_action},
+    Action, Input, Theme,
+};
 
 #[derive(Debug, Copy, Clone)]
 pub enum ResourcesFocus {
@@ -43,6 +47,8 @@ pub struct ResourceState {
     focus: ResourcesFocus,
     list_state: ListState,
     resource_count: usize,
+    page_size: usize,
+    hitbox: ListHitbox,
 }
 
 impl ResourceState {
@@ -61,40 +67,51 @@ impl ResourceState {
         }
     }
 
-    pub fn handle_input(&mut self, input: char) -> ResourcesFocus {
+    pub fn handle_input(&mut self, input: Input) -> ResourcesFocus {
         match self.focus {
             ResourcesFocus::None => (),
-            ResourcesFocus::List => match input {
-                'h' => self.set_focus(ResourcesFocus::None),
-                'j' => self.list_state.select(Some(
-                    self.list_state
-                        .selected()
-                        .unwrap_or_default()
-                        .wrapping_add(1)
-                        .wrapping_rem(self.resource_count),
+            ResourcesFocus::List => match input.action {
+                Some(Action::Left) | Some(Action::Cancel) => self.set_focus(ResourcesFocus::None),
+                Some(action) => self.list_state.select(navigate(
+                    self.list_state.selected(),
+                    self.resource_count,
+                    self.page_size,
+                    action,
                 )),
-                'k' => self.list_state.select(Some(
-                    self.list_state
-                        .selected()
-                        .unwrap_or_default()
-                        .checked_sub(1)
-                        .unwrap_or(self.resource_count - 1),
-                )),
-                _ => (),
+                None => (),
             },
         }
         self.focus
     }
+
+    pub fn handle_mouse(&mut self, input: MouseInput) -> ResourcesFocus {
+        if let Some(action) = scroll_action(&input) {
+            if self.hitbox.contains(input.column, input.row) {
+                self.list_state.select(navigate(
+                    self.list_state.selected(),
+                    self.resource_count,
+                    self.page_size,
+                    action,
+                ));
+            }
+        } else if let Some(index) = self.hitbox.index_at(input.column, input.row) {
+            self.focus = ResourcesFocus::List;
+            self.list_state.select(Some(index));
+        }
+
+        self.focus
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ResourceDebugger<'a> {
     resources: &'a Resources,
+    theme: &'a Theme,
 }
 
 impl<'a> ResourceDebugger<'a> {
-    pub fn new(resources: &'a Resources) -> Self {
-        Self { resources }
+    pub fn new(resources: &'a Resources, theme: &'a Theme) -> Self {
+        Self { resources, theme }
     }
 }
 
@@ -102,20 +119,28 @@ impl<'a> StatefulWidget for ResourceDebugger<'a> {
     type State = ResourceState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        /*
-        let mut resources = vec![];
-
-        for resource in self.resources.iter() {
-            resources.push(ListItem::new(format!(
-                "{}: {:#?}",
-                resource.name(),
-                resource
-            )));
-        }
-
-        super::style::list(resources, matches!(state.focus, ResourcesFocus::List))
-            .block(super::style::block("Resources"))
-            .render(area, buf, &mut state.list_state);
-        */
+        let resources = match self.resources.resources() {
+            Some(Data::Seq(resources)) => resources
+                .iter()
+                .map(|resource| ListItem::new(format!("{}: {:#?}", resource.name(), resource)))
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+
+        state.resource_count = resources.len();
+        state.page_size = page_size(area.height);
+
+        let block = self.theme.block("Resources");
+        state.hitbox.update(
+            block.inner(area),
+            resources.iter().map(|item| item.height() as u16).collect(),
+            state.list_state.selected(),
+        );
+
+        let list = self
+            .theme
+            .list(resources, matches!(state.focus, ResourcesFocus::List))
+            .block(block);
+        StatefulWidget::render(list, area, buf, &mut state.list_state);
     }
 }
@@ -1,6 +1,22 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+use antigen_tracing::{TraceLeaf, TraceLeafId, TraceLeafVariant, TraceThread, TraceTree};
+use tracing::{callsite::Identifier, Metadata};
 use tui::{
-    text::Text,
-    widgets::{ListItem, ListState, StatefulWidget},
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Style,
+    widgets::{Paragraph, Row, StatefulWidget, TableState, Widget},
+};
+use tui_widgets::{ListHitbox, MouseInput, MouseInputKind};
+
+use super::{
+    navigation::{navigate, page_size, scroll_action},
+    Action, Input, Theme,
 };
 
 #[derive(Debug, Copy, Clone)]
@@ -15,68 +31,425 @@ impl Default for SchedulesFocus {
     }
 }
 
+/// Column used to order the system timing table
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimingSort {
+    Total,
+    Average,
+    Max,
+    Calls,
+    Name,
+}
+
+impl Default for TimingSort {
+    fn default() -> Self {
+        TimingSort::Total
+    }
+}
+
+impl TimingSort {
+    pub fn next(self) -> Self {
+        match self {
+            TimingSort::Total => TimingSort::Average,
+            TimingSort::Average => TimingSort::Max,
+            TimingSort::Max => TimingSort::Calls,
+            TimingSort::Calls => TimingSort::Name,
+            TimingSort::Name => TimingSort::Total,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TimingSort::Total => "total",
+            TimingSort::Average => "average",
+            TimingSort::Max => "max",
+            TimingSort::Calls => "calls",
+            TimingSort::Name => "name",
+        }
+    }
+
+    pub fn sort(self, timings: &mut [SystemTimings]) {
+        match self {
+            TimingSort::Total => timings.sort_by_key(|timing| Reverse(timing.total)),
+            TimingSort::Average => timings.sort_by_key(|timing| Reverse(timing.average())),
+            TimingSort::Max => timings.sort_by_key(|timing| Reverse(timing.max)),
+            TimingSort::Calls => timings.sort_by_key(|timing| Reverse(timing.calls())),
+            TimingSort::Name => {
+                timings.sort_by_key(|timing| (timing.metadata.name(), timing.metadata.target()))
+            }
+        }
+    }
+}
+
+/// Aggregated timings for all spans sharing a callsite
+#[derive(Debug)]
+pub struct SystemTimings<'a> {
+    pub callsite: Identifier,
+    pub metadata: &'static Metadata<'static>,
+    pub leaves: Vec<TraceLeafId>,
+    /// Number of spans that have been both entered and exited
+    pub timed: u32,
+    pub total: Duration,
+    pub max: Duration,
+    pub last: Option<Duration>,
+    /// Thread of the most recently entered span
+    pub thread: &'a TraceThread,
+    last_entered: Option<Instant>,
+}
+
+impl<'a> SystemTimings<'a> {
+    fn new(leaf: &'a TraceLeaf) -> Self {
+        SystemTimings {
+            callsite: leaf.metadata.callsite(),
+            metadata: leaf.metadata,
+            leaves: Default::default(),
+            timed: 0,
+            total: Duration::default(),
+            max: Duration::default(),
+            last: None,
+            thread: &leaf.thread,
+            last_entered: None,
+        }
+    }
+
+    fn push(&mut self, id: TraceLeafId, leaf: &'a TraceLeaf) {
+        self.leaves.push(id);
+
+        let entered = match (&leaf.variant, leaf.entered()) {
+            (TraceLeafVariant::Span { .. }, Some(entered)) => entered,
+            _ => return,
+        };
+
+        let duration = leaf.busy_time();
+        if let Some(duration) = duration {
+            self.timed += 1;
+            self.total += duration;
+            self.max = self.max.max(duration);
+        }
+
+        if self.last_entered.map_or(true, |last| entered >= last) {
+            self.last_entered = Some(entered);
+            self.last = duration;
+            self.thread = &leaf.thread;
+        }
+    }
+
+    pub fn calls(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn average(&self) -> Duration {
+        if self.timed == 0 {
+            Duration::default()
+        } else {
+            self.total / self.timed
+        }
+    }
+
+    /// Group the span leaves in `ids` by callsite
+    pub fn group<I>(trace_tree: &'a TraceTree, ids: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = TraceLeafId>,
+    {
+        let mut timings = HashMap::<Identifier, SystemTimings>::default();
+        for id in ids {
+            let leaf = match trace_tree.leaves.get(&id) {
+                Some(leaf) if matches!(leaf.variant, TraceLeafVariant::Span { .. }) => leaf,
+                _ => continue,
+            };
+
+            timings
+                .entry(leaf.metadata.callsite())
+                .or_insert_with(|| SystemTimings::new(leaf))
+                .push(id, leaf);
+        }
+
+        timings.into_iter().map(|(_, timing)| timing).collect()
+    }
+}
+
+/// A drill-down step into the child spans of a callsite
+#[derive(Debug, Clone)]
+struct DrillDown {
+    callsite: Identifier,
+    metadata: &'static Metadata<'static>,
+    selected: Option<usize>,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct ScheduleState {
     focus: SchedulesFocus,
-    list_state: ListState,
-    label_count: usize,
+    table_state: TableState,
+    sort: TimingSort,
+    path: Vec<DrillDown>,
+    callsites: Vec<(Identifier, &'static Metadata<'static>)>,
+    page_size: usize,
+    hitbox: ListHitbox,
 }
 
-
 impl ScheduleState {
+    pub fn sort(&self) -> TimingSort {
+        self.sort
+    }
+
+    pub fn set_sort(&mut self, sort: TimingSort) {
+        self.sort = sort;
+    }
+
     pub fn set_focus(&mut self, focus: SchedulesFocus) {
         self.focus = focus;
 
         match self.focus {
             SchedulesFocus::None => (),
-            SchedulesFocus::List => self.list_state.select(Some(0)),
+            SchedulesFocus::List => self.table_state.select(Some(0)),
+        }
+    }
+
+    /// Descend into the child spans of the selected callsite
+    fn drill_down(&mut self) {
+        let selected = self.table_state.selected();
+        if let Some((callsite, metadata)) = selected.and_then(|i| self.callsites.get(i)).cloned() {
+            self.path.push(DrillDown {
+                callsite,
+                metadata,
+                selected,
+            });
+            self.table_state.select(Some(0));
+        }
+    }
+
+    /// Return to the parent callsite, returning false if already at the top level
+    fn drill_up(&mut self) -> bool {
+        match self.path.pop() {
+            Some(step) => {
+                self.table_state.select(step.selected);
+                true
+            }
+            None => false,
         }
     }
 
-    pub fn handle_input(&mut self, input: char) -> SchedulesFocus {
+    pub fn handle_input(&mut self, input: Input) -> SchedulesFocus {
         match self.focus {
             SchedulesFocus::None => (),
-            SchedulesFocus::List => match input {
-                'h' => {
-                    self.set_focus(SchedulesFocus::None);
+            SchedulesFocus::List => match input.action {
+                Some(Action::Left) | Some(Action::Cancel) => {
+                    if !self.drill_up() {
+                        self.set_focus(SchedulesFocus::None);
+                    }
                 }
-                'j' => self.list_state.select(Some(
-                    self.list_state
-                        .selected()
-                        .unwrap_or_default()
-                        .wrapping_add(1)
-                        .wrapping_rem(self.label_count),
-                )),
-                'k' => self.list_state.select(Some(
-                    self.list_state
-                        .selected()
-                        .unwrap_or_default()
-                        .checked_sub(1)
-                        .unwrap_or(self.label_count - 1),
+                Some(Action::Right) | Some(Action::Confirm) => self.drill_down(),
+                Some(Action::Sort) => self.sort = self.sort.next(),
+                Some(action) => self.table_state.select(navigate(
+                    self.table_state.selected(),
+                    self.callsites.len(),
+                    self.page_size,
+                    action,
                 )),
-                _ => (),
+                None => (),
             },
         }
 
         self.focus
     }
+
+    /// Select the row under the cursor, drilling down on double-click
+    pub fn handle_mouse(&mut self, input: MouseInput) -> SchedulesFocus {
+        if let Some(action) = scroll_action(&input) {
+            if self.hitbox.contains(input.column, input.row) {
+                self.table_state.select(navigate(
+                    self.table_state.selected(),
+                    self.callsites.len(),
+                    self.page_size,
+                    action,
+                ));
+            }
+        } else if let Some(index) = self.hitbox.index_at(input.column, input.row) {
+            self.focus = SchedulesFocus::List;
+            self.table_state.select(Some(index));
+
+            if let MouseInputKind::DoubleClick = input.kind {
+                self.drill_down();
+            }
+        }
+
+        self.focus
+    }
 }
 
-pub struct ScheduleDebugger;
+/// Per-system timing table built from a [`TraceTree`]
+///
+/// Spans are grouped by callsite, and can be drilled into to show timings for their children.
+pub struct ScheduleDebugger<'a> {
+    trace_tree: Option<&'a TraceTree>,
+    theme: &'a Theme,
+}
+
+impl<'a> ScheduleDebugger<'a> {
+    pub fn new(trace_tree: Option<&'a TraceTree>, theme: &'a Theme) -> Self {
+        ScheduleDebugger { trace_tree, theme }
+    }
+
+    /// Children of the innermost callsite of the drill-down path, or root leaves if it is empty
+    fn path_leaves(trace_tree: &TraceTree, path: &[DrillDown]) -> Vec<TraceLeafId> {
+        let mut children = BTreeMap::<TraceLeafId, Vec<TraceLeafId>>::default();
+        for (id, leaf) in trace_tree.leaves.iter() {
+            if let Some(parent_id) = leaf.parent_id {
+                children.entry(parent_id).or_default().push(*id);
+            }
+        }
 
-impl StatefulWidget for ScheduleDebugger {
+        let mut path = path.iter();
+        let mut leaves = match path.next() {
+            Some(step) => trace_tree
+                .callsite_leaves(step.callsite.clone())
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>(),
+            None => {
+                return trace_tree
+                    .leaves
+                    .iter()
+                    .filter(|(_, leaf)| leaf.parent_id.is_none())
+                    .map(|(id, _)| *id)
+                    .collect()
+            }
+        };
+
+        for step in path {
+            leaves = leaves
+                .iter()
+                .flat_map(|id| children.get(id).into_iter().flatten())
+                .filter(|id| trace_tree.leaves[id].metadata.callsite() == step.callsite)
+                .copied()
+                .collect();
+        }
+
+        leaves
+            .iter()
+            .flat_map(|id| children.get(id).into_iter().flatten())
+            .copied()
+            .collect()
+    }
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    duration
+        .map(|duration| format!("{:.2?}", duration))
+        .unwrap_or_else(|| "-".into())
+}
+
+fn format_thread(thread: &TraceThread) -> String {
+    thread
+        .name()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| format!("{:?}", thread.id()))
+}
+
+impl<'a> StatefulWidget for ScheduleDebugger<'a> {
     type State = ScheduleState;
 
-    fn render(
-        self,
-        area: tui::layout::Rect,
-        buf: &mut tui::buffer::Buffer,
-        state: &mut Self::State,
-    ) {
-        let mut labels = vec![];
-
-        super::style::list(labels, matches!(state.focus, SchedulesFocus::List))
-            .block(super::style::block("Tracing"))
-            .render(area, buf, &mut state.list_state);
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let theme = self.theme;
+
+        let trace_tree = match self.trace_tree {
+            Some(trace_tree) => trace_tree,
+            None => {
+                state.callsites.clear();
+                Paragraph::new("No trace data")
+                    .style(Style::default().fg(theme.lowlight))
+                    .block(theme.block("Tracing"))
+                    .render(area, buf);
+                return;
+            }
+        };
+
+        let mut timings =
+            SystemTimings::group(trace_tree, Self::path_leaves(trace_tree, &state.path));
+        state.sort.sort(&mut timings);
+
+        state.callsites = timings
+            .iter()
+            .map(|timing| (timing.callsite.clone(), timing.metadata))
+            .collect();
+        // Account for the table header
+        state.page_size = page_size(area.height.saturating_sub(2));
+
+        if let Some(selected) = state.table_state.selected() {
+            if selected >= state.callsites.len() {
+                state
+                    .table_state
+                    .select(state.callsites.len().checked_sub(1));
+            }
+        }
+
+        let header = [
+            "System", "Target", "Calls", "Total", "Last", "Average", "Max", "Thread",
+        ];
+        let sort_column = match state.sort {
+            TimingSort::Name => 0,
+            TimingSort::Calls => 2,
+            TimingSort::Total => 3,
+            TimingSort::Average => 5,
+            TimingSort::Max => 6,
+        };
+        let header_row = Row::new(header.iter().enumerate().map(|(i, title)| {
+            if i == sort_column {
+                format!("{} ▼", title)
+            } else {
+                title.to_string()
+            }
+        }))
+        .style(Style::default().fg(theme.info))
+        .bottom_margin(1);
+
+        let rows = timings
+            .iter()
+            .map(|timing| {
+                Row::new(vec![
+                    timing.metadata.name().to_string(),
+                    timing.metadata.target().to_string(),
+                    timing.calls().to_string(),
+                    format_duration(Some(timing.total).filter(|_| timing.timed > 0)),
+                    format_duration(timing.last),
+                    format_duration(Some(timing.average()).filter(|_| timing.timed > 0)),
+                    format_duration(Some(timing.max).filter(|_| timing.timed > 0)),
+                    format_thread(timing.thread),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let title = std::iter::once("Tracing")
+            .chain(state.path.iter().map(|step| step.metadata.name()))
+            .collect::<Vec<_>>()
+            .join(" > ");
+        let block = theme.block(format!("{} (by {})", title, state.sort.label()));
+
+        // Rows start below the header and its bottom margin
+        let mut rows_area = block.inner(area);
+        rows_area.y = rows_area.y.saturating_add(2).min(rows_area.bottom());
+        rows_area.height = rows_area.height.saturating_sub(2);
+        state
+            .hitbox
+            .update(rows_area, vec![1; rows.len()], state.table_state.selected());
+
+        let widths = [
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Ratio(1, 3),
+        ];
+
+        let table = theme
+            .table(rows, matches!(state.focus, SchedulesFocus::List))
+            .header(header_row)
+            .block(block)
+            .widths(&widths)
+            .column_spacing(1);
+
+        StatefulWidget::render(table, area, buf, &mut state.table_state);
     }
 }
@@ -0,0 +1,126 @@
+use legion_debugger::EntityQuery;
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Paragraph, Widget},
+};
+
+use super::{Action, Input, Theme};
+
+/// Search queries for the debugger tabs that support filtering
+///
+/// Shared with the world parsing code so unmatched archetypes can be skipped during serialization.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SearchQueries {
+    pub archetypes: EntityQuery,
+    pub entities: EntityQuery,
+}
+
+/// A query that can be parsed from search bar input
+pub trait SearchQuery: Default {
+    fn parse(input: &str) -> Self;
+}
+
+impl SearchQuery for EntityQuery {
+    fn parse(input: &str) -> Self {
+        EntityQuery::parse(input)
+    }
+}
+
+/// Incremental search bar state
+#[derive(Debug, Default, Clone)]
+pub struct SearchState<Q = EntityQuery> {
+    input: String,
+    query: Q,
+    editing: bool,
+}
+
+impl<Q> SearchState<Q>
+where
+    Q: SearchQuery,
+{
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn query(&self) -> &Q {
+        &self.query
+    }
+
+    pub fn editing(&self) -> bool {
+        self.editing
+    }
+
+    pub fn begin(&mut self) {
+        self.editing = true;
+    }
+
+    /// Handle a key of input, returning true while the search bar remains focused
+    ///
+    /// Typed characters take precedence over bound actions.
+    pub fn handle_input(&mut self, input: Input) -> bool {
+        match (input.char, input.action) {
+            (Some(c), _) => self.input.push(c),
+            (None, Some(Action::Confirm)) => self.editing = false,
+            (None, Some(Action::Cancel)) => {
+                self.input.clear();
+                self.editing = false;
+            }
+            (None, Some(Action::Erase)) => {
+                self.input.pop();
+            }
+            _ => (),
+        }
+
+        self.query = Q::parse(&self.input);
+        self.editing
+    }
+}
+
+/// Single-line search bar displaying the current query input
+pub struct SearchBar<'a, Q = EntityQuery> {
+    state: &'a SearchState<Q>,
+    theme: &'a Theme,
+    focused: bool,
+}
+
+impl<'a, Q> SearchBar<'a, Q> {
+    pub fn new(state: &'a SearchState<Q>, theme: &'a Theme, focused: bool) -> Self {
+        SearchBar {
+            state,
+            theme,
+            focused,
+        }
+    }
+}
+
+impl<'a, Q> Widget for SearchBar<'a, Q> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        let mut spans = vec![Span::styled(
+            self.state.input.as_str(),
+            Style::default().fg(theme.titles),
+        )];
+
+        if self.focused {
+            spans.push(Span::styled(" ", theme.style_highlight(true)));
+        } else if self.state.input.is_empty() {
+            spans.push(Span::styled(
+                "Search to filter",
+                Style::default().fg(theme.trim),
+            ));
+        }
+
+        Paragraph::new(Spans::from(spans))
+            .block(theme.block("Search").border_style(if self.focused {
+                Style::default().fg(theme.highlight)
+            } else {
+                Style::default().fg(theme.trim)
+            }))
+            .render(area, buf)
+    }
+}
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+use serde::{Deserialize, Serialize};
 use tui::{
     style::{Color, Modifier, Style},
     symbols::DOT,
@@ -7,52 +8,71 @@ use tui::{
     widgets::{Block, Borders, List, ListItem, Row, Table, Tabs},
 };
 
-pub const COLOR_TITLES: Color = Color::White;
-pub const COLOR_TRIM: Color = Color::DarkGray;
-pub const COLOR_HIGHLIGHT: Color = Color::Magenta;
-pub const COLOR_LOWLIGHT: Color = Color::LightBlue;
-pub const COLOR_INFO: Color = Color::Cyan;
-
-pub fn style_highlight(focused: bool) -> Style {
-    let style = Style::default().fg(COLOR_HIGHLIGHT);
-    if focused {
-        style.add_modifier(Modifier::REVERSED)
-    } else {
-        style
-    }
+/// Color scheme for debugger widgets
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub titles: Color,
+    pub trim: Color,
+    pub highlight: Color,
+    pub lowlight: Color,
+    pub info: Color,
 }
 
-pub fn tabs<'a>(titles: Vec<Spans<'a>>, focused: bool) -> Tabs<'a> {
-    tui::widgets::Tabs::new(titles)
-        .style(Style::default().fg(COLOR_LOWLIGHT))
-        .divider(DOT)
-        .highlight_style(style_highlight(focused))
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            titles: Color::White,
+            trim: Color::DarkGray,
+            highlight: Color::Magenta,
+            lowlight: Color::LightBlue,
+            info: Color::Cyan,
+        }
+    }
 }
 
-pub fn list<'a, T>(titles: T, focused: bool) -> List<'a>
-where
-    T: Into<Vec<ListItem<'a>>>,
-{
-    List::new(titles)
-        .style(Style::default().fg(COLOR_LOWLIGHT))
-        .highlight_style(super::style::style_highlight(focused))
-}
+impl Theme {
+    pub fn style_highlight(&self, focused: bool) -> Style {
+        let style = Style::default().fg(self.highlight);
+        if focused {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style
+        }
+    }
 
-pub fn table<'a, T>(rows: T, focused: bool) -> Table<'a>
-where
-    T: IntoIterator<Item = Row<'a>>,
-{
-    Table::new(rows)
-        .style(Style::default().fg(COLOR_LOWLIGHT))
-        .highlight_style(super::style::style_highlight(focused))
-}
+    pub fn tabs<'a>(&self, titles: Vec<Spans<'a>>, focused: bool) -> Tabs<'a> {
+        tui::widgets::Tabs::new(titles)
+            .style(Style::default().fg(self.lowlight))
+            .divider(DOT)
+            .highlight_style(self.style_highlight(focused))
+    }
 
-pub fn block<'a, T>(title: T) -> Block<'a>
-where
-    T: Into<Cow<'a, str>>,
-{
-    Block::default()
-        .title(Span::styled(title, Style::default().fg(COLOR_TITLES)))
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(COLOR_TRIM))
+    pub fn list<'a, T>(&self, titles: T, focused: bool) -> List<'a>
+    where
+        T: Into<Vec<ListItem<'a>>>,
+    {
+        List::new(titles)
+            .style(Style::default().fg(self.lowlight))
+            .highlight_style(self.style_highlight(focused))
+    }
+
+    pub fn table<'a, T>(&self, rows: T, focused: bool) -> Table<'a>
+    where
+        T: IntoIterator<Item = Row<'a>>,
+    {
+        Table::new(rows)
+            .style(Style::default().fg(self.lowlight))
+            .highlight_style(self.style_highlight(focused))
+    }
+
+    pub fn block<'a, T>(&self, title: T) -> Block<'a>
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        Block::default()
+            .title(Span::styled(title, Style::default().fg(self.titles)))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.trim))
+    }
 }
@@ -0,0 +1,477 @@
+use std::{collections::BTreeMap, time::Duration, time::Instant};
+
+use antigen_tracing::{TraceLeaf, TraceLeafId, TraceLeafVariant, TraceThread, TraceTree};
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    symbols::line::HORIZONTAL,
+    widgets::{Paragraph, StatefulWidget, Widget},
+};
+use tui_widgets::{MouseInput, MouseInputKind};
+
+use super::{Action, Input, Theme};
+
+/// Width of the thread name column
+const LABEL_WIDTH: u16 = 16;
+
+/// Columns between time axis labels
+const AXIS_STEP: u16 = 16;
+
+/// Deepest zoom level, where the view covers 2^-MAX_ZOOM of the trace
+const MAX_ZOOM: i32 = 24;
+
+/// Symbol used to mark events on a thread lane
+const EVENT_TICK: &str = "╵";
+
+#[derive(Debug, Copy, Clone)]
+pub enum TimelineFocus {
+    None,
+    Timeline,
+}
+
+impl Default for TimelineFocus {
+    fn default() -> Self {
+        TimelineFocus::None
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TimelineState {
+    focus: TimelineFocus,
+    zoom: i32,
+    /// Start of the view as a fraction of the trace duration
+    pan: f64,
+    lane_offset: usize,
+    lane_count: usize,
+    plot_area: Rect,
+}
+
+impl TimelineState {
+    pub fn set_focus(&mut self, focus: TimelineFocus) {
+        self.focus = focus;
+    }
+
+    /// Fraction of the trace duration covered by the view
+    fn window(&self) -> f64 {
+        0.5f64.powi(self.zoom)
+    }
+
+    fn pan_by(&mut self, delta: f64) {
+        self.pan = (self.pan + delta).clamp(0.0, 1.0 - self.window());
+    }
+
+    /// Zoom by `delta` levels, keeping the point at `anchor` (0..1 across the view) stationary
+    fn zoom_by(&mut self, delta: i32, anchor: f64) {
+        let time = self.pan + anchor * self.window();
+        self.zoom = (self.zoom + delta).clamp(0, MAX_ZOOM);
+        self.pan = time - anchor * self.window();
+        self.pan_by(0.0);
+    }
+
+    pub fn handle_input(&mut self, input: Input) -> TimelineFocus {
+        match self.focus {
+            TimelineFocus::None => (),
+            TimelineFocus::Timeline => match input.action {
+                Some(Action::Cancel) => self.focus = TimelineFocus::None,
+                Some(Action::Left) => self.pan_by(-self.window() / 8.0),
+                Some(Action::Right) => self.pan_by(self.window() / 8.0),
+                Some(Action::PageUp) => self.pan_by(-self.window()),
+                Some(Action::PageDown) => self.pan_by(self.window()),
+                Some(Action::First) => self.pan = 0.0,
+                Some(Action::Last) => self.pan_by(1.0),
+                Some(Action::Up) => self.lane_offset = self.lane_offset.saturating_sub(1),
+                Some(Action::Down) => {
+                    self.lane_offset = (self.lane_offset + 1).min(self.lane_count.saturating_sub(1))
+                }
+                Some(Action::ZoomIn) => self.zoom_by(1, 0.5),
+                Some(Action::ZoomOut) => self.zoom_by(-1, 0.5),
+                _ => (),
+            },
+        }
+
+        self.focus
+    }
+
+    /// Scrolling over the plot zooms around the cursor
+    pub fn handle_mouse(&mut self, input: MouseInput) -> TimelineFocus {
+        if !input.hit(self.plot_area) {
+            return self.focus;
+        }
+
+        let anchor = (input.column - self.plot_area.x) as f64 / self.plot_area.width as f64;
+        match input.kind {
+            MouseInputKind::ScrollUp => self.zoom_by(1, anchor),
+            MouseInputKind::ScrollDown => self.zoom_by(-1, anchor),
+            _ => self.focus = TimelineFocus::Timeline,
+        }
+
+        self.focus
+    }
+}
+
+/// Horizontal bar chart of trace spans, with one lane per thread
+///
+/// Spans are drawn as one bar per enter / exit interval and stacked by depth,
+/// events are drawn as ticks along the top of their thread's lane.
+pub struct TimelineDebugger<'a> {
+    trace_tree: Option<&'a TraceTree>,
+    theme: &'a Theme,
+}
+
+impl<'a> TimelineDebugger<'a> {
+    pub fn new(trace_tree: Option<&'a TraceTree>, theme: &'a Theme) -> Self {
+        TimelineDebugger { trace_tree, theme }
+    }
+}
+
+/// Spans and events recorded on a single thread
+struct Lane<'a> {
+    thread: &'a TraceThread,
+    leaves: Vec<(&'a TraceLeaf, usize)>,
+    depth: usize,
+}
+
+impl<'a> Lane<'a> {
+    fn height(&self) -> u16 {
+        self.depth as u16 + 1
+    }
+
+    fn name(&self) -> String {
+        self.thread
+            .name()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| format!("{:?}", self.thread.id()))
+    }
+
+    /// Split a trace tree into per-thread lanes, with each span paired with its depth
+    fn collect(trace_tree: &'a TraceTree) -> Vec<Self> {
+        let mut depths = BTreeMap::<TraceLeafId, usize>::default();
+        let mut lanes = Vec::<Lane>::default();
+
+        for (id, leaf) in trace_tree.leaves.iter() {
+            let depth = Self::depth(trace_tree, &mut depths, *id);
+
+            let lane = match lanes.iter().position(|lane| *lane.thread == leaf.thread) {
+                Some(i) => &mut lanes[i],
+                None => {
+                    lanes.push(Lane {
+                        thread: &leaf.thread,
+                        leaves: Default::default(),
+                        depth: 0,
+                    });
+                    lanes.last_mut().unwrap()
+                }
+            };
+
+            if let TraceLeafVariant::Span { .. } = leaf.variant {
+                lane.depth = lane.depth.max(depth + 1);
+            }
+            lane.leaves.push((leaf, depth));
+        }
+
+        lanes.sort_by_key(|lane| lane.name());
+        lanes
+    }
+
+    /// Number of span ancestors on the same thread
+    fn depth(
+        trace_tree: &TraceTree,
+        depths: &mut BTreeMap<TraceLeafId, usize>,
+        id: TraceLeafId,
+    ) -> usize {
+        if let Some(depth) = depths.get(&id) {
+            return *depth;
+        }
+
+        let leaf = &trace_tree.leaves[&id];
+        let depth = match leaf.parent_id {
+            Some(parent_id) => match trace_tree.leaves.get(&parent_id) {
+                Some(parent) if parent.thread == leaf.thread => match parent.variant {
+                    TraceLeafVariant::Span { .. } => Self::depth(trace_tree, depths, parent_id) + 1,
+                    _ => 0,
+                },
+                _ => 0,
+            },
+            None => 0,
+        };
+
+        depths.insert(id, depth);
+        depth
+    }
+}
+
+/// Earliest and latest instants recorded in a trace tree
+fn time_range(trace_tree: &TraceTree) -> Option<(Instant, Instant)> {
+    trace_tree
+        .leaves
+        .values()
+        .flat_map(|leaf| match &leaf.variant {
+            TraceLeafVariant::Span { intervals, .. } => intervals
+                .iter()
+                .flat_map(|interval| std::iter::once(interval.entered).chain(interval.exited))
+                .collect(),
+            TraceLeafVariant::Event { instant } => vec![*instant],
+        })
+        .fold(None, |acc, instant| match acc {
+            Some((start, end)) => Some((instant.min(start), instant.max(end))),
+            None => Some((instant, instant)),
+        })
+}
+
+/// Maps instants onto columns of the plot area
+struct Projection {
+    origin: Instant,
+    view_start: f64,
+    view_len: f64,
+    area: Rect,
+}
+
+impl Projection {
+    fn column(&self, instant: Instant) -> f64 {
+        let t = instant.saturating_duration_since(self.origin).as_secs_f64();
+        (t - self.view_start) / self.view_len * self.area.width as f64
+    }
+
+    /// Visible column range covered by an interval, or None if it lies outside the view
+    fn columns(&self, from: Instant, to: Instant) -> Option<(u16, u16)> {
+        let (x0, x1) = (self.column(from), self.column(to));
+        let width = self.area.width as f64;
+        if x1 < 0.0 || x0 >= width {
+            return None;
+        }
+
+        let x0 = x0.max(0.0).floor() as u16;
+        let x1 = (x1.min(width).ceil() as u16)
+            .max(x0 + 1)
+            .min(self.area.width);
+        Some((self.area.x + x0, self.area.x + x1))
+    }
+}
+
+impl<'a> StatefulWidget for TimelineDebugger<'a> {
+    type State = TimelineState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let theme = self.theme;
+
+        let range = self
+            .trace_tree
+            .and_then(|trace_tree| time_range(trace_tree).map(|range| (trace_tree, range)));
+
+        let (trace_tree, (origin, end)) = match range {
+            Some(range) => range,
+            None => {
+                state.lane_count = 0;
+                state.plot_area = Rect::default();
+                Paragraph::new("No trace data")
+                    .style(Style::default().fg(theme.lowlight))
+                    .block(theme.block("Timeline"))
+                    .render(area, buf);
+                return;
+            }
+        };
+
+        let total = end
+            .saturating_duration_since(origin)
+            .as_secs_f64()
+            .max(1e-6);
+        let view_start = total * state.pan;
+        let view_len = total * state.window();
+
+        let block = theme.block(format!(
+            "Timeline ({:.2?} of {:.2?})",
+            Duration::from_secs_f64(view_len),
+            Duration::from_secs_f64(total)
+        ));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.width <= LABEL_WIDTH + 1 || inner.height < 2 {
+            return;
+        }
+
+        let plot_area = Rect {
+            x: inner.x + LABEL_WIDTH + 1,
+            y: inner.y + 1,
+            width: inner.width - LABEL_WIDTH - 1,
+            height: inner.height - 1,
+        };
+        state.plot_area = plot_area;
+
+        let projection = Projection {
+            origin,
+            view_start,
+            view_len,
+            area: plot_area,
+        };
+
+        // Time axis
+        let axis_style = Style::default().fg(theme.info);
+        let mut x = 0;
+        while x + 1 < plot_area.width {
+            let offset = view_start + view_len * x as f64 / plot_area.width as f64;
+            buf.set_stringn(
+                plot_area.x + x,
+                inner.y,
+                format!("|{:.2?}", Duration::from_secs_f64(offset)),
+                (AXIS_STEP - 1).min(plot_area.width - x) as usize,
+                axis_style,
+            );
+            x += AXIS_STEP;
+        }
+
+        let lanes = Lane::collect(trace_tree);
+        state.lane_count = lanes.len();
+        state.lane_offset = state.lane_offset.min(lanes.len().saturating_sub(1));
+
+        let palette = [theme.lowlight, theme.info];
+        let trim_style = Style::default().fg(theme.trim);
+
+        let mut y = plot_area.y;
+        for lane in lanes.iter().skip(state.lane_offset) {
+            if y >= plot_area.bottom() {
+                break;
+            }
+
+            // Lane header: thread name, baseline and event ticks
+            buf.set_stringn(
+                inner.x,
+                y,
+                lane.name(),
+                LABEL_WIDTH as usize,
+                Style::default().fg(theme.titles),
+            );
+            buf.set_string(
+                plot_area.x,
+                y,
+                HORIZONTAL.repeat(plot_area.width as usize),
+                trim_style,
+            );
+
+            for (i, (leaf, depth)) in lane.leaves.iter().enumerate() {
+                match leaf.variant {
+                    TraceLeafVariant::Event { instant } => {
+                        if let Some((x, _)) = projection.columns(instant, instant) {
+                            buf.get_mut(x, y)
+                                .set_symbol(EVENT_TICK)
+                                .set_style(Style::default().fg(theme.highlight));
+                        }
+                    }
+                    TraceLeafVariant::Span { ref intervals, .. } => {
+                        let bar_y = y + 1 + *depth as u16;
+                        if bar_y >= plot_area.bottom() {
+                            continue;
+                        }
+
+                        let style = Style::default()
+                            .fg(Color::Black)
+                            .bg(palette[(i + depth) % palette.len()]);
+
+                        // Re-entered spans are drawn as one bar per interval
+                        for interval in intervals {
+                            let (x0, x1) = match projection
+                                .columns(interval.entered, interval.exited.unwrap_or(end))
+                            {
+                                Some(columns) => columns,
+                                None => continue,
+                            };
+
+                            let bar = Rect {
+                                x: x0,
+                                y: bar_y,
+                                width: x1 - x0,
+                                height: 1,
+                            };
+                            buf.set_style(bar, style);
+                            buf.set_stringn(
+                                x0,
+                                bar_y,
+                                leaf.metadata.name(),
+                                bar.width as usize,
+                                style,
+                            );
+                        }
+                    }
+                }
+            }
+
+            y += lane.height();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trace time at the `anchor` fraction across the view
+    fn time_at(state: &TimelineState, anchor: f64) -> f64 {
+        state.pan + anchor * state.window()
+    }
+
+    #[test]
+    fn test_zoom_keeps_anchor() {
+        let mut state = TimelineState::default();
+
+        for (delta, anchor) in [(1, 0.25), (2, 0.75), (-1, 0.5), (3, 0.0), (-2, 1.0)].iter() {
+            let time = time_at(&state, *anchor);
+            state.zoom_by(*delta, *anchor);
+            assert_eq!(time_at(&state, *anchor), time);
+        }
+        assert_eq!(state.zoom, 3);
+        assert_eq!(state.pan, 0.25);
+    }
+
+    #[test]
+    fn test_zoom_and_pan_stay_in_bounds() {
+        let mut state = TimelineState::default();
+
+        state.zoom_by(-1, 0.5);
+        assert_eq!(state.zoom, 0);
+        assert_eq!(state.pan, 0.0);
+
+        state.zoom_by(MAX_ZOOM + 8, 0.5);
+        assert_eq!(state.zoom, MAX_ZOOM);
+
+        state.zoom = 1;
+        state.pan_by(-1.0);
+        assert_eq!(state.pan, 0.0);
+        state.pan_by(2.0);
+        assert_eq!(state.pan, 0.5);
+
+        // Zooming out near the end shifts the view back inside the trace
+        state.zoom_by(-1, 0.9);
+        assert_eq!(state.zoom, 0);
+        assert_eq!(state.pan, 0.0);
+    }
+
+    #[test]
+    fn test_columns_clip_to_view() {
+        // One second of trace covers 10 columns, with the view starting one second in
+        let origin = Instant::now();
+        let projection = Projection {
+            origin,
+            view_start: 1.0,
+            view_len: 2.0,
+            area: Rect::new(10, 0, 20, 1),
+        };
+        let columns = |from: f64, to: f64| {
+            projection.columns(
+                origin + Duration::from_secs_f64(from),
+                origin + Duration::from_secs_f64(to),
+            )
+        };
+
+        assert_eq!(columns(0.0, 0.5), None);
+        assert_eq!(columns(0.5, 1.5), Some((10, 15)));
+        assert_eq!(columns(1.5, 2.5), Some((15, 25)));
+        assert_eq!(columns(2.5, 10.0), Some((25, 30)));
+        assert_eq!(columns(0.0, 10.0), Some((10, 30)));
+        assert_eq!(columns(3.0, 4.0), None);
+
+        // Zero-length intervals still cover a column
+        assert_eq!(columns(2.0, 2.0), Some((20, 21)));
+        assert_eq!(columns(2.99, 2.99), Some((29, 30)));
+    }
+}
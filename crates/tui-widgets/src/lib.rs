@@ -1,3 +1,9 @@
+mod list_hitbox;
+mod mouse;
+mod scrollbar;
 mod tab_container;
 
+pub use list_hitbox::*;
+pub use mouse::*;
+pub use scrollbar::*;
 pub use tab_container::*;
@@ -0,0 +1,91 @@
+use tui::layout::Rect;
+
+use super::contains;
+
+/// Mirror of the scroll offset applied by tui's `List` and `Table` widgets
+///
+/// tui keeps list offsets private, so this replays the same bounds calculation
+/// in order to map terminal rows back onto item indices.
+#[derive(Debug, Default, Clone)]
+pub struct ListHitbox {
+    area: Rect,
+    offset: usize,
+    heights: Vec<u16>,
+}
+
+impl ListHitbox {
+    /// Update with the item area and item heights of a list that is about to be rendered
+    pub fn update(&mut self, area: Rect, heights: Vec<u16>, selected: Option<usize>) {
+        self.area = area;
+        self.heights = heights;
+
+        if self.heights.is_empty() {
+            return;
+        }
+
+        let max_height = area.height as usize;
+        let len = self.heights.len();
+        let height_of = |i: usize| self.heights[i] as usize;
+
+        let offset = self.offset.min(len - 1);
+        let mut start = offset;
+        let mut end = offset;
+        let mut height = 0;
+        for i in offset..len {
+            if height + height_of(i) > max_height {
+                break;
+            }
+            height += height_of(i);
+            end += 1;
+        }
+
+        let selected = selected.unwrap_or(0).min(len - 1);
+        while selected >= end {
+            height = height.saturating_add(height_of(end));
+            end += 1;
+            while height > max_height {
+                height = height.saturating_sub(height_of(start));
+                start += 1;
+            }
+        }
+        while selected < start {
+            start -= 1;
+            height = height.saturating_add(height_of(start));
+            while height > max_height {
+                end -= 1;
+                height = height.saturating_sub(height_of(end));
+            }
+        }
+
+        self.offset = start;
+    }
+
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        contains(self.area, column, row)
+    }
+
+    /// Index of the item rendered at the given terminal cell
+    pub fn index_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.contains(column, row) {
+            return None;
+        }
+
+        let mut y = self.area.y;
+        for (i, height) in self.heights.iter().enumerate().skip(self.offset) {
+            if row < y + height {
+                return Some(i);
+            }
+            y += height;
+        }
+
+        None
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use tui::layout::Rect;
+
+/// Maximum delay between two clicks on the same cell for them to count as a double-click
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MouseInputKind {
+    Click,
+    DoubleClick,
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Simplified mouse input used for widget hit-testing
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MouseInput {
+    pub kind: MouseInputKind,
+    pub column: u16,
+    pub row: u16,
+}
+
+impl MouseInput {
+    pub fn new(kind: MouseInputKind, column: u16, row: u16) -> Self {
+        MouseInput { kind, column, row }
+    }
+
+    /// Returns true if this input lies within `area`
+    pub fn hit(&self, area: Rect) -> bool {
+        contains(area, self.column, self.row)
+    }
+
+    pub fn is_click(&self) -> bool {
+        matches!(
+            self.kind,
+            MouseInputKind::Click | MouseInputKind::DoubleClick
+        )
+    }
+
+    pub fn is_scroll(&self) -> bool {
+        matches!(
+            self.kind,
+            MouseInputKind::ScrollUp | MouseInputKind::ScrollDown
+        )
+    }
+}
+
+/// Returns true if the given terminal cell lies within `area`
+pub fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.left() && column < area.right() && row >= area.top() && row < area.bottom()
+}
+
+/// Converts raw crossterm mouse events into [`MouseInput`]s
+///
+/// crossterm doesn't report double-clicks, so they are synthesized from successive left clicks.
+#[derive(Debug, Default, Clone)]
+pub struct ClickTracker {
+    last_click: Option<(Instant, u16, u16)>,
+}
+
+impl ClickTracker {
+    pub fn input(&mut self, event: &MouseEvent) -> Option<MouseInput> {
+        let kind = match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let now = Instant::now();
+                let double = matches!(
+                    self.last_click,
+                    Some((instant, column, row))
+                        if column == event.column
+                            && row == event.row
+                            && now.duration_since(instant) <= DOUBLE_CLICK_INTERVAL
+                );
+
+                if double {
+                    self.last_click = None;
+                    MouseInputKind::DoubleClick
+                } else {
+                    self.last_click = Some((now, event.column, event.row));
+                    MouseInputKind::Click
+                }
+            }
+            MouseEventKind::ScrollUp => MouseInputKind::ScrollUp,
+            MouseEventKind::ScrollDown => MouseInputKind::ScrollDown,
+            _ => return None,
+        };
+
+        Some(MouseInput::new(kind, event.column, event.row))
+    }
+}
@@ -0,0 +1,59 @@
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    symbols::{block::FULL, line::VERTICAL},
+    widgets::Widget,
+};
+
+/// Vertical scrollbar indicating the visible portion of a list
+///
+/// Renders into the first column of its area, typically the right-hand border of a block.
+/// Draws nothing if all content fits within the viewport.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Scrollbar {
+    offset: usize,
+    viewport: usize,
+    content: usize,
+    style: Style,
+}
+
+impl Scrollbar {
+    pub fn new(offset: usize, viewport: usize, content: usize) -> Self {
+        Scrollbar {
+            offset,
+            viewport,
+            content,
+            style: Style::default(),
+        }
+    }
+
+    pub fn style(self, style: Style) -> Self {
+        Scrollbar { style, ..self }
+    }
+}
+
+impl Widget for Scrollbar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 || self.content <= self.viewport {
+            return;
+        }
+
+        let track = area.height as usize;
+        let thumb_len = (track * self.viewport / self.content).max(1).min(track);
+        let max_offset = self.content - self.viewport;
+        let thumb_pos = self.offset.min(max_offset) * (track - thumb_len) / max_offset;
+
+        for i in 0..track {
+            let symbol = if i >= thumb_pos && i < thumb_pos + thumb_len {
+                FULL
+            } else {
+                VERTICAL
+            };
+
+            buf.get_mut(area.x, area.y + i as u16)
+                .set_symbol(symbol)
+                .set_style(self.style);
+        }
+    }
+}
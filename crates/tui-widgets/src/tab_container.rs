@@ -5,8 +5,50 @@ use tui::{
     widgets::{Block, Widget},
 };
 
-#[derive(Debug, Clone)]
-pub struct TabContainer<'a, 'b, TF, NF>
+use super::contains;
+
+/// Screen areas occupied by a rendered [`TabContainer`], used for mouse hit-testing
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TabAreas {
+    pub bar: Rect,
+    pub tabs: Vec<Rect>,
+    pub content: Rect,
+}
+
+impl TabAreas {
+    /// Index of the tab title at the given terminal cell
+    pub fn tab_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.tabs
+            .iter()
+            .position(|rect| contains(*rect, column, row))
+    }
+
+    /// Lay out tab titles the same way tui's `Tabs` widget does, assuming a single-width divider
+    fn layout_titles(&mut self, area: Rect, titles: &[Spans]) {
+        self.tabs.clear();
+
+        if area.height < 1 {
+            return;
+        }
+
+        let mut x = area.left();
+        for title in titles {
+            x = x.saturating_add(1);
+            let remaining_width = area.right().saturating_sub(x);
+            if remaining_width == 0 {
+                break;
+            }
+
+            let width = (title.width() as u16).min(remaining_width);
+            self.tabs.push(Rect::new(x, area.top(), width, 1));
+
+            x = x.saturating_add(width).saturating_add(2);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TabContainer<'a, 'b, 'c, TF, NF>
 where
     TF: FnOnce(Vec<Spans<'a>>, bool) -> tui::widgets::Tabs<'a>,
     NF: FnOnce(Rect, &mut Buffer, usize),
@@ -17,9 +59,10 @@ where
     selected: usize,
     tabs: TF,
     next: Option<NF>,
+    areas: Option<&'c mut TabAreas>,
 }
 
-impl<'a, 'b, TF, NF> TabContainer<'a, 'b, TF, NF>
+impl<'a, 'b, 'c, TF, NF> TabContainer<'a, 'b, 'c, TF, NF>
 where
     TF: FnOnce(Vec<Spans<'a>>, bool) -> tui::widgets::Tabs<'a>,
     NF: FnOnce(Rect, &mut Buffer, usize),
@@ -32,6 +75,7 @@ where
             selected: 0,
             tabs,
             next: None,
+            areas: None,
         }
     }
     pub fn titles(self, titles: Vec<Spans<'a>>) -> Self {
@@ -62,9 +106,17 @@ where
             ..self
         }
     }
+
+    /// Record the rendered tab and content areas into `areas`
+    pub fn areas(self, areas: &'c mut TabAreas) -> Self {
+        Self {
+            areas: Some(areas),
+            ..self
+        }
+    }
 }
 
-impl<'a, 'b, TF, NF> Widget for TabContainer<'a, 'b, TF, NF>
+impl<'a, 'b, 'c, TF, NF> Widget for TabContainer<'a, 'b, 'c, TF, NF>
 where
     TF: FnOnce(Vec<Spans<'a>>, bool) -> tui::widgets::Tabs<'a>,
     NF: FnOnce(Rect, &mut Buffer, usize),
@@ -75,6 +127,16 @@ where
             .constraints(vec![Constraint::Length(3), Constraint::Min(0)])
             .split(area);
 
+        if let Some(areas) = self.areas {
+            let inner = match &self.block {
+                Some(block) => block.inner(chunks[0]),
+                None => chunks[0],
+            };
+            areas.bar = chunks[0];
+            areas.layout_titles(inner, &self.titles);
+            areas.content = chunks[1];
+        }
+
         let tabs = (self.tabs)(self.titles, self.highlighted);
         if let Some(block) = self.block {
             tabs.block(block)